@@ -1,10 +1,11 @@
 pub use super::*;
 pub use frame_support::pallet_prelude::*;
-#[allow(unused_imports)]
-use num_traits::float::FloatCore;
 pub use scale_info::prelude::boxed::Box;
 pub use sp_core::H256;
-use sp_runtime::traits::{StaticLookup, Zero};
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	FixedPointNumber, FixedU128,
+};
 impl<T: Config> Pallet<T> {
 	pub fn approve_representative_role(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
 		let caller = ensure_signed(origin)?;
@@ -85,15 +86,47 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	pub fn calculate_guaranty(collection: T::NftCollectionId, item: T::NftItemId) -> u128 {
-		let coeff = T::Guaranty::get() as u128;
-		let ror = T::RoR::get();
-		let price0 = Onboarding::Pallet::<T>::houses(collection, item).unwrap().price.unwrap();
-		let price1 = Self::onboarding_bal_to_u128(ror.mul_floor(price0)).unwrap();
+	///Monthly rent of an asset: the rent set by the owners,
+	///or the return on rent of the asset price shared between the `Lease` months,
+	///rounded to the nearest unit
+	pub fn asset_rent(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Result<u128, DispatchError> {
+		if let Some(rent) = Self::asset_rents(collection, item) {
+			let rent0 = Self::roles_bal_to_u128(rent).ok_or(Error::<T>::InvalidRentAmount)?;
+			return Ok(rent0)
+		}
+		let house =
+			Onboarding::Pallet::<T>::houses(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+		let price0 = house.price.ok_or(Error::<T>::AssetPriceMissing)?;
+		//The yearly Return on Rent is rounded down
+		let price1 = Self::onboarding_bal_to_u128(T::RoR::get().mul_floor(price0))
+			.ok_or(Error::<T>::InvalidRentAmount)?;
 		let time = <T as Config>::Lease::get();
-		let rent = ((price1 as f64) / time as f64).round();
-		let amount: u128 = coeff * (rent as u128);
-		amount
+		Self::div_round(price1, time as u128)
+	}
+
+	///Lease length of an asset, in number of months
+	pub fn asset_lease(collection: T::NftCollectionId, item: T::NftItemId) -> u32 {
+		Self::asset_leases(collection, item).unwrap_or_else(<T as Config>::Lease::get)
+	}
+
+	pub fn calculate_guaranty(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Result<u128, DispatchError> {
+		let coeff = T::Guaranty::get() as u128;
+		let rent = Self::asset_rent(collection, item)?;
+		let amount = coeff.checked_mul(rent).ok_or(Error::<T>::InvalidRentAmount)?;
+		Ok(amount)
+	}
+
+	///Division rounded to the nearest integer, halves being rounded up
+	pub fn div_round(amount: u128, divisor: u128) -> Result<u128, DispatchError> {
+		let ratio = FixedU128::checked_from_rational(amount, divisor)
+			.ok_or(Error::<T>::InvalidRentAmount)?;
+		Ok(ratio.round().into_inner() / FixedU128::accuracy())
 	}
 
 	pub fn guaranty_payment(
@@ -102,38 +135,59 @@ impl<T: Config> Pallet<T> {
 		collection: T::NftCollectionId,
 		item: T::NftItemId,
 	) -> DispatchResult {
-		let creator = ensure_signed(origin.clone())?;
+		let creator = ensure_signed(origin)?;
+		//A pending request would be lost if it was replaced by a new one
+		ensure!(Self::guaranty(&from, &creator).is_none(), Error::<T>::ExistingPaymentRequest);
 
 		//Calculate guaranty deposit using Return On Rent and guaranty coefficients found in
 		// runtime
-		let amount = Self::calculate_guaranty(collection, item);
+		let amount0 = Self::calculate_guaranty(collection, item)?;
+		let amount = Self::rent_share(&from, &creator).mul_floor(amount0);
 
 		//convert amount to payment_pallet compatible balance
 		let bals0 = BalanceType::<T>::convert_to_balance(amount);
 		let amount1 = bals0.payment_bal;
 
 		//create payment_request
-		Payment::Pallet::<T>::request_payment(origin, from.clone(), amount1).ok();
+		let payment_id =
+			Payment::Pallet::<T>::do_request_payment(from.clone(), creator.clone(), amount1)?;
 
-		//Store payment details
-		let details = Payment::Pallet::<T>::get_payment_details(&from, &creator).unwrap();
-		GuarantyPayment::<T>::insert(from, creator, details);
+		//Store payment id
+		Self::insert_guaranty(&from, &creator, payment_id);
 
 		Ok(())
 	}
 
+	///Store the guaranty deposit payment of a tenant, indexed by asset account
+	pub fn insert_guaranty(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+		payment_id: Payment::PaymentId,
+	) {
+		GuarantyPayment::<T>::insert(tenant, asset_account, payment_id);
+		AssetGuaranties::<T>::insert(asset_account, tenant, payment_id);
+	}
+
+	///Remove the guaranty deposit payment of a tenant, once it was returned
+	pub fn remove_guaranty(tenant: &T::AccountId, asset_account: &T::AccountId) {
+		GuarantyPayment::<T>::remove(tenant, asset_account);
+		AssetGuaranties::<T>::remove(asset_account, tenant);
+	}
+
+	///Details of the guaranty deposit payment requested from a tenant
+	pub fn guaranty<K1, K2>(tenant: K1, asset_account: K2) -> Option<Payment::PaymentDetail<T>>
+	where
+		K1: EncodeLike<T::AccountId>,
+		K2: EncodeLike<T::AccountId>,
+	{
+		Self::guaranty_id(tenant, asset_account)
+			.and_then(<Payment::Pallet<T> as PaymentHandler<T>>::get_payment_details)
+	}
+
 	pub fn owners_infos(asset_account: T::AccountId) -> Option<Share::Ownership<T>> {
 		//Find the asset in Share Distributor using asset account
-		let assets = Share::Virtual::<T>::iter_keys();
-		let mut infos = None;
-		for (i, j) in assets {
-			let ownership = Share::Pallet::<T>::virtual_acc(i, j).unwrap();
-			if asset_account.clone() == ownership.virtual_account {
-				//Get the owners
-				infos = Some(ownership);
-			}
-		}
-		infos
+		let (collection, item) = Share::Pallet::<T>::virtual_asset(&asset_account)?;
+		Share::Pallet::<T>::virtual_acc(collection, item)
 	}
 
 	pub fn vote_helper(share: u128, vote: bool) -> Option<Dem::Vote> {
@@ -155,20 +209,17 @@ impl<T: Config> Pallet<T> {
 		asset_account: T::AccountId,
 	) -> DispatchResult {
 		// Update tenant info
-		//We first get the Return on Rent coeffient
-		let ror = T::RoR::get();
-		Roles::TenantLog::<T>::mutate(&tenant, |val| {
-			let mut val0 = val.clone().unwrap();
-			// get asset price
-			let price0 = Onboarding::Pallet::<T>::houses(collection, item).unwrap().price.unwrap();
-			let price1 = Self::onboarding_bal_to_u128(ror.mul_floor(price0)).unwrap();
+		//We first get the monthly rent and the lease length of the asset
+		let rent0 = Self::rent_share(&tenant, &asset_account)
+			.mul_floor(Self::asset_rent(collection, item)?);
+		let time = Self::asset_lease(collection, item);
+		let rent1 = rent0.checked_mul(time as u128).ok_or(Error::<T>::InvalidRentAmount)?;
+		Roles::TenantLog::<T>::try_mutate(&tenant, |val| -> DispatchResult {
+			let val0 = val.as_mut().ok_or(Error::<T>::NotATenant)?;
 
 			//Update rent in tenant infos added.
-			let time = <T as Config>::Lease::get();
-			let rent0 = ((price1 as f64) / time as f64).round();
-			let rent1 = (rent0 as u128) * time as u128;
 			let now = <frame_system::Pallet<T>>::block_number();
-			let mut bals = BalanceType::<T>::convert_to_balance(rent0 as u128);
+			let mut bals = BalanceType::<T>::convert_to_balance(rent0);
 			let rent = bals.roles_bal;
 			bals = BalanceType::<T>::convert_to_balance(rent1);
 			let year_rent = bals.roles_bal;
@@ -177,19 +228,27 @@ impl<T: Config> Pallet<T> {
 			val0.remaining_rent = year_rent;
 			val0.remaining_payments = time as u8;
 			val0.contract_start = now;
-			*val = Some(val0);
-		});
+			Ok(())
+		})?;
+		TenantLeases::<T>::insert(&tenant, time);
 
 		// Update asset info
-		Onboarding::Houses::<T>::mutate(collection, item, |house| {
-			let mut house0 = house.clone().unwrap();
-			house0.tenants.push(tenant);
-			*house = Some(house0);
-		});
+		Onboarding::Houses::<T>::try_mutate(collection, item, |house| -> DispatchResult {
+			house.as_mut().ok_or(Error::<T>::NotAnAsset)?.tenants.push(tenant);
+			Ok(())
+		})?;
 
 		Ok(())
 	}
 
+	///Share of a tenant in the rent of an asset, the whole rent without co-tenancy
+	pub fn rent_share(tenant: &T::AccountId, asset_account: &T::AccountId) -> Percent {
+		match Self::tenant_shares(tenant) {
+			Some((account, share)) if account == *asset_account => share,
+			_ => Percent::from_percent(100),
+		}
+	}
+
 	pub fn tenant_unlink_asset(
 		tenant: T::AccountId,
 		collection: T::NftCollectionId,
@@ -201,6 +260,8 @@ impl<T: Config> Pallet<T> {
 			val0.asset_account = None;
 			*val = Some(val0);
 		});
+		TenantLeases::<T>::remove(&tenant);
+		TenantShares::<T>::remove(&tenant);
 
 		// Update asset info
 		Onboarding::Houses::<T>::mutate(collection, item, |house| {
@@ -212,6 +273,474 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	///Share the proceeds of an asset sale between the owners, according to their tokens.
+	///The rounding remainder goes to the largest owners.
+	pub fn distribute_sale_proceeds(
+		asset_account: T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<Vec<T::AccountId>, DispatchError> {
+		let owners = Share::Pallet::<T>::tokens_infos(asset_account.clone())
+			.ok_or(Error::<T>::NotAnAssetAccount)?
+			.owners;
+		let weights = owners
+			.iter()
+			.map(|(owner, balance)| {
+				let balance0 = Self::assets_bal_to_u128(*balance).unwrap_or_default();
+				(owner.clone(), BalanceType::<T>::convert_to_balance(balance0).hfund_bal)
+			})
+			.collect::<Vec<_>>();
+		let price0 = Self::manage_bal_to_u128(price).ok_or(Error::<T>::NoneValue)?;
+		let price1 = BalanceType::<T>::convert_to_balance(price0).hfund_bal;
+
+		//Owner's proceeds: sale price * owner tokens / owners tokens
+		let (parts, left) = HFund::Pallet::<T>::share_by_weight(weights, price1, false);
+		ensure!(left.is_zero(), Error::<T>::NoneValue);
+		for (owner, amount) in parts {
+			let amount0 = Self::hfund_bal_to_u128(amount).ok_or(Error::<T>::NoneValue)?;
+			let amount = BalanceType::<T>::convert_to_balance(amount0).manage_bal;
+			<T as Config>::Currency::transfer(
+				&asset_account,
+				&owner,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+		}
+
+		Ok(owners.into_iter().map(|(owner, _)| owner).collect())
+	}
+
+	///Release the maintenance reserve of a sold asset, to share it with the sale proceeds
+	pub fn release_maintenance_reserve(asset_account: T::AccountId) -> BalanceOf<T> {
+		MaintenanceSpendings::<T>::remove(&asset_account);
+		let reserve = MaintenanceReserves::<T>::take(&asset_account);
+		let missing = <T as Config>::Currency::unreserve(&asset_account, reserve);
+		reserve.saturating_sub(missing)
+	}
+
+	///Unlink the tenants and the representative of a sold asset
+	pub fn release_sold_asset(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+		asset_account: T::AccountId,
+	) -> DispatchResult {
+		let house =
+			Onboarding::Pallet::<T>::houses(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+		for tenant in house.tenants {
+			Self::tenant_unlink_asset(tenant, collection, item)?;
+		}
+
+		if let Some(rep) = house.representative {
+			Roles::RepresentativeLog::<T>::mutate(&rep, |val| {
+				let mut val0 = val.clone().unwrap();
+				val0.assets_accounts.retain(|account| *account != asset_account);
+				*val = Some(val0);
+			});
+			Onboarding::Houses::<T>::mutate(collection, item, |house| {
+				let mut house0 = house.clone().unwrap();
+				house0.representative = None;
+				*house = Some(house0);
+			});
+		}
+		RepresentativeTerms::<T>::remove(collection, item);
+		FlatFeePayments::<T>::remove(&asset_account);
+		Reelections::<T>::remove(collection, item);
+		AssetRents::<T>::remove(collection, item);
+		AssetLeases::<T>::remove(collection, item);
+		Insurances::<T>::remove(collection, item);
+		Appraisers::<T>::remove(collection, item);
+		PendingRevaluations::<T>::remove(collection, item);
+
+		Ok(())
+	}
+
+	///Start the re-election of the representative of an asset whose term ended
+	pub fn start_reelection(collection: T::NftCollectionId, item: T::NftItemId) {
+		if Reelections::<T>::contains_key(collection, item) {
+			return
+		}
+		let incumbent =
+			Onboarding::Pallet::<T>::houses(collection, item).and_then(|h| h.representative);
+		let ownership = Share::Pallet::<T>::virtual_acc(collection, item);
+		match (incumbent, ownership) {
+			(Some(incumbent), Some(ownership)) => {
+				let reelection =
+					Reelection::<T>::new(collection, item, ownership.virtual_account, incumbent);
+				Self::deposit_event(Event::ReelectionStarted {
+					incumbent: reelection.incumbent,
+					asset_account: reelection.virtual_account,
+					end: reelection.end,
+				});
+			},
+			_ => RepresentativeTerms::<T>::remove(collection, item),
+		}
+	}
+
+	///Candidate of a re-election with the majority of the votes.
+	///Votes are weighted by the ownership tokens of the voters.
+	pub fn reelection_winner(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+		reelection: &Reelection<T>,
+	) -> Option<T::AccountId> {
+		let token_id = Share::Pallet::<T>::virtual_acc(collection, item)?.token_id;
+		let mut tally: Vec<(T::AccountId, u128)> =
+			reelection.candidates.iter().map(|candidate| (candidate.clone(), 0)).collect();
+		let mut total: u128 = 0;
+		for (owner, candidate) in reelection.votes.iter() {
+			let tokens = Assetss::Pallet::<T>::balance(token_id.into(), owner);
+			let weight = Self::assets_bal_to_u128(tokens)?;
+			total = total.saturating_add(weight);
+			for (account, votes) in tally.iter_mut() {
+				if account == candidate {
+					*votes = votes.saturating_add(weight);
+				}
+			}
+		}
+
+		//New candidates must still be waiting for a representative role
+		tally
+			.into_iter()
+			.find(|(_, votes)| votes.saturating_mul(2) > total)
+			.map(|(candidate, _)| candidate)
+			.filter(|candidate| {
+				*candidate == reelection.incumbent ||
+					Roles::Pallet::<T>::get_pending_representatives(candidate).is_some()
+			})
+	}
+
+	///Count the votes of a re-election, and apply the fallback rule if there is no winner
+	pub fn close_reelection(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+		now: BlockNumberOf<T>,
+	) {
+		let reelection = match Reelections::<T>::take(collection, item) {
+			Some(reelection) => reelection,
+			None => return,
+		};
+		let asset_account = reelection.virtual_account.clone();
+		let origin: OriginFor<T> = RawOrigin::Signed(asset_account.clone()).into();
+
+		if let Some(winner) = Self::reelection_winner(collection, item, &reelection) {
+			//The incumbent is replaced entirely, or not at all
+			let elected = if winner == reelection.incumbent {
+				Ok(())
+			} else {
+				with_storage_layer(|| {
+					Self::demote_representative(
+						origin.clone(),
+						reelection.incumbent.clone(),
+						collection,
+						item,
+					)?;
+					Self::representative_approval(origin.clone(), winner.clone(), collection, item)
+				})
+			};
+
+			match elected {
+				Ok(()) => {
+					let term_end = now.saturating_add(T::RepresentativeTerm::get());
+					RepresentativeTerms::<T>::insert(collection, item, term_end);
+					IncumbentExtensions::<T>::remove(collection, item);
+
+					Self::deposit_event(Event::RepresentativeElected {
+						representative: winner,
+						asset_account,
+						term_end,
+					});
+					return
+				},
+				Err(error) => {
+					log::warn!(
+						"representative of {:?} could not be replaced by {:?}: {:?}",
+						asset_account,
+						winner,
+						error
+					);
+					Self::deposit_event(Event::ReelectionFailed {
+						representative: reelection.incumbent.clone(),
+						candidate: Some(winner),
+						asset_account: asset_account.clone(),
+						error,
+					});
+				},
+			}
+		}
+
+		//Without an elected winner, the incumbent is only kept a limited number of times
+		let extensions = Self::incumbent_extensions(collection, item);
+		match T::ReelectionFallback::get() {
+			ReelectionFallback::KeepIncumbent(period)
+				if extensions < T::MaxIncumbentExtensions::get() =>
+			{
+				let term_end = now.saturating_add(period);
+				RepresentativeTerms::<T>::insert(collection, item, term_end);
+				IncumbentExtensions::<T>::insert(collection, item, extensions.saturating_add(1));
+
+				Self::deposit_event(Event::IncumbentKept {
+					representative: reelection.incumbent,
+					asset_account,
+					term_end,
+				});
+			},
+			_ => {
+				let demoted = with_storage_layer(|| {
+					Self::demote_representative(
+						origin,
+						reelection.incumbent.clone(),
+						collection,
+						item,
+					)
+				});
+
+				match demoted {
+					Ok(()) => Self::deposit_event(Event::ManagementSuspended {
+						representative: reelection.incumbent,
+						asset_account,
+					}),
+					Err(error) => {
+						log::warn!(
+							"representative of {:?} could not be demoted: {:?}",
+							asset_account,
+							error
+						);
+						//A new re-election starts at the next check
+						RepresentativeTerms::<T>::insert(collection, item, now);
+
+						Self::deposit_event(Event::ReelectionFailed {
+							representative: reelection.incumbent,
+							candidate: None,
+							asset_account,
+							error,
+						});
+					},
+				}
+			},
+		}
+	}
+
+	///Pay a servicer invoice from the maintenance reserve of an asset
+	pub fn spend_maintenance_reserve(
+		asset_account: T::AccountId,
+		servicer: T::AccountId,
+		amount: BalanceOf<T>,
+		invoice: T::Hash,
+	) -> DispatchResult {
+		Self::take_maintenance_reserve(&asset_account, amount)?;
+		<T as Config>::Currency::transfer(
+			&asset_account,
+			&servicer,
+			amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		Self::record_maintenance_spend(asset_account, servicer, amount, invoice);
+
+		Ok(())
+	}
+
+	///Put a servicer payment from the maintenance reserve of an asset in a `pallet_payment`
+	///escrow, within the maintenance spend limit of the period. The servicer is payed when the
+	///escrow is released, and a refunded escrow goes back to the maintenance reserve.
+	///Returns the payment id of the escrow.
+	pub fn escrow_maintenance_reserve(
+		asset_account: T::AccountId,
+		servicer: T::AccountId,
+		amount: BalanceOf<T>,
+		invoice: T::Hash,
+	) -> Result<Payment::PaymentId, DispatchError> {
+		Self::record_unvoted_spend(&asset_account, amount)?;
+		Self::take_maintenance_reserve(&asset_account, amount)?;
+
+		//The escrow is not charged an incentive nor a fee
+		let amount0 = Self::manage_bal_to_u128(amount).ok_or(Error::<T>::InvalidRentAmount)?;
+		let payment = BalanceType::<T>::convert_to_balance(amount0).payment_bal;
+		let payment_id =
+			Payment::Pallet::<T>::do_escrow(asset_account.clone(), servicer.clone(), payment)?;
+		MaintenanceEscrows::<T>::insert(payment_id, asset_account.clone());
+
+		Self::record_maintenance_spend(asset_account, servicer, amount, invoice);
+
+		Ok(payment_id)
+	}
+
+	///Record a spending of the maintenance reserve of an asset made without a vote of the
+	///owners. Fails if the spendings of the period would exceed the `MaintenanceSpendLimit`.
+	pub fn record_unvoted_spend(
+		asset_account: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let spent = Self::maintenance_spent(asset_account, now).saturating_add(amount);
+		ensure!(
+			spent <= T::MaintenanceSpendLimit::get(),
+			Error::<T>::MaintenanceSpendLimitExceeded
+		);
+		let period = now.saturating_sub(now % T::MaintenanceSpendPeriod::get());
+		MaintenanceSpendings::<T>::insert(asset_account, (period, spent));
+		Ok(())
+	}
+
+	//The maintenance reserve is kept as reserved balance of the asset account
+	fn take_maintenance_reserve(
+		asset_account: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let reserve = Self::maintenance_reserve(asset_account);
+		ensure!(reserve >= amount, Error::<T>::NotEnoughMaintenanceFunds);
+		let missing = <T as Config>::Currency::unreserve(asset_account, amount);
+		ensure!(missing.is_zero(), Error::<T>::NotEnoughMaintenanceFunds);
+		Ok(())
+	}
+
+	///Put the refunded part of a maintenance escrow back in the maintenance reserve of its asset
+	pub fn refund_maintenance_escrow(
+		payment_id: Payment::PaymentId,
+		payment: &Payment::PaymentDetail<T>,
+		recipient_share: Percent,
+	) {
+		let asset_account = match MaintenanceEscrows::<T>::take(payment_id) {
+			Some(asset_account) => asset_account,
+			None => return,
+		};
+		let refund = payment.amount.saturating_sub(recipient_share.mul_floor(payment.amount));
+		let amount = match Self::payment_bal_to_u128(refund) {
+			Some(refund0) if refund0 > 0 =>
+				BalanceType::<T>::convert_to_balance(refund0).manage_bal,
+			_ => return,
+		};
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		//The refund was sent back to the free balance of the asset account
+		if <T as Config>::Currency::reserve(&asset_account, amount).is_err() {
+			log::warn!("maintenance escrow {:?} refund could not be reserved", payment_id);
+			return
+		}
+		MaintenanceReserves::<T>::mutate(&asset_account, |val| {
+			*val = val.saturating_add(amount);
+		});
+		MaintenanceRecord::<T>::new(
+			asset_account.clone(),
+			MaintenanceOperation::Refund,
+			amount,
+			Some(payment.to.clone()),
+			None,
+		);
+
+		Self::deposit_event(Event::MaintenanceReserveRefunded { asset_account, amount, when: now });
+	}
+
+	///Amount spent without a vote from the maintenance reserve of an asset,
+	///during the current maintenance spend period
+	pub fn maintenance_spent(asset_account: &T::AccountId, now: BlockNumberOf<T>) -> BalanceOf<T> {
+		let period = T::MaintenanceSpendPeriod::get();
+		match Self::maintenance_spendings(asset_account) {
+			Some((start, spent)) if now < start.saturating_add(period) => spent,
+			_ => Zero::zero(),
+		}
+	}
+
+	fn record_maintenance_spend(
+		asset_account: T::AccountId,
+		servicer: T::AccountId,
+		amount: BalanceOf<T>,
+		invoice: T::Hash,
+	) {
+		MaintenanceReserves::<T>::mutate(&asset_account, |val| {
+			*val = val.saturating_sub(amount);
+		});
+		MaintenanceRecord::<T>::new(
+			asset_account.clone(),
+			MaintenanceOperation::Spend,
+			amount,
+			Some(servicer.clone()),
+			Some(invoice),
+		);
+
+		Self::deposit_event(Event::MaintenanceReserveSpent {
+			servicer,
+			asset_account,
+			amount,
+			invoice,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+	}
+
+	///Pay the management fee of a rent to the active representative of an asset.
+	///The fee cannot exceed the amount available for distribution, and a flat fee
+	///is paid once per month of lease.
+	///Returns the amount paid.
+	pub fn pay_representative_fee(
+		asset_account: T::AccountId,
+		token_id: <T as Share::Config>::AssetId,
+		rent: BalanceOf<T>,
+		available: BalanceOf<T>,
+		now: BlockNumberOf<T>,
+	) -> BalanceOf<T> {
+		let asset = Share::Pallet::<T>::token_asset(token_id)
+			.and_then(|(collection, item)| Onboarding::Pallet::<T>::houses(collection, item));
+		let representative = match asset.and_then(|asset| asset.representative) {
+			Some(rep) => rep,
+			None => return Zero::zero(),
+		};
+		//A demoted representative is not paid anymore
+		match Roles::Pallet::<T>::reps(representative.clone()) {
+			Some(rep) if rep.activated => (),
+			_ => return Zero::zero(),
+		}
+
+		let fee = match T::ManagementFee::get() {
+			ManagementFee::Percent(percent) => percent.mul_floor(rent),
+			ManagementFee::Flat(_) if Self::flat_fee_paid(&asset_account, now) => Zero::zero(),
+			ManagementFee::Flat(amount) => amount,
+		}
+		.min(available);
+		if fee.is_zero() {
+			return fee
+		}
+
+		let res = <T as Config>::Currency::transfer(
+			&asset_account,
+			&representative,
+			fee,
+			ExistenceRequirement::KeepAlive,
+		);
+		if res.is_err() {
+			Self::deposit_event(Event::RepresentativeFeeFailed {
+				representative,
+				asset_account,
+				amount: fee,
+				when: now,
+			});
+			return Zero::zero()
+		}
+
+		if let ManagementFee::Flat(_) = T::ManagementFee::get() {
+			FlatFeePayments::<T>::insert(&asset_account, now);
+		}
+		RepresentativeEarnings::<T>::mutate(&representative, &asset_account, |val| {
+			*val = val.saturating_add(fee);
+		});
+		Self::deposit_event(Event::RepresentativeFeePaid {
+			representative,
+			asset_account,
+			amount: fee,
+			when: now,
+		});
+
+		fee
+	}
+
+	///Whether the flat management fee of an asset was paid during the last month of lease
+	fn flat_fee_paid(asset_account: &T::AccountId, now: BlockNumberOf<T>) -> bool {
+		let month = Self::blocknumber_to_u128(<T as Config>::ContractLength::get())
+			.unwrap_or_default() /
+			(<T as Config>::Lease::get() as u128).max(1);
+		Self::flat_fee_payments(asset_account).map_or(false, |paid| {
+			Self::blocknumber_to_u128(now.saturating_sub(paid)).unwrap_or_default() < month
+		})
+	}
+
 	pub fn create_proposal_hash_and_note(
 		caller: T::AccountId,
 		proposal_call: pallet::Call<T>,
@@ -235,6 +764,57 @@ impl<T: Config> Pallet<T> {
 		owners.contains(caller)
 	}
 
+	///Net asset value of an asset: its latest approved valuation, or its purchase price,
+	///minus the outstanding obligations of the asset account
+	pub fn net_asset_value(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Result<u128, DispatchError> {
+		let value = match Self::value_history(collection, item).last() {
+			Some(revaluation) => Self::manage_bal_to_u128(revaluation.value),
+			None => {
+				let house = Onboarding::Pallet::<T>::houses(collection, item)
+					.ok_or(Error::<T>::NotAnAsset)?;
+				let price = house.price.ok_or(Error::<T>::AssetPriceMissing)?;
+				Self::onboarding_bal_to_u128(price)
+			},
+		}
+		.ok_or(Error::<T>::InvalidValuation)?;
+
+		let ownership =
+			Share::Pallet::<T>::virtual_acc(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+		let obligations = Self::asset_obligations(ownership.virtual_account);
+		Ok(value.saturating_sub(obligations))
+	}
+
+	///Outstanding obligations of an asset account: the guaranty deposits to return to the
+	///tenants, and the dividends owed to the owners
+	pub fn asset_obligations(asset_account: T::AccountId) -> u128 {
+		let guaranties: u128 = AssetGuaranties::<T>::iter_prefix_values(&asset_account)
+			.filter_map(|payment_id| Payment::Pallet::<T>::payment(payment_id))
+			.filter(|payment| payment.state == Payment::PaymentState::PaymentCompleted)
+			.map(|payment| Self::payment_bal_to_u128(payment.amount).unwrap_or_default())
+			.sum();
+		let dividends: u128 = Share::UnclaimedDividends::<T>::iter_prefix(&asset_account)
+			.map(|(_, amount)| Self::share_bal_to_u128(amount).unwrap_or_default())
+			.sum();
+		guaranties.saturating_add(dividends)
+	}
+
+	///Net asset value per ownership token of an asset, rounded down
+	pub fn nav_per_token(
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Result<u128, DispatchError> {
+		let nav = Self::net_asset_value(collection, item)?;
+		let ownership =
+			Share::Pallet::<T>::virtual_acc(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+		let supply = Assetss::Pallet::<T>::total_supply(ownership.token_id.into());
+		let supply0 = Self::assets_bal_to_u128(supply).ok_or(Error::<T>::InvalidValuation)?;
+		let nav_per_token = nav.checked_div(supply0).ok_or(Error::<T>::InvalidValuation)?;
+		Ok(nav_per_token)
+	}
+
 	pub fn manage_bal_to_u128(input: BalanceOf<T>) -> Option<u128> {
 		input.try_into().ok()
 	}
@@ -247,6 +827,15 @@ impl<T: Config> Pallet<T> {
 	pub fn onboarding_bal_to_u128(input: Onboarding::BalanceOf<T>) -> Option<u128> {
 		input.try_into().ok()
 	}
+	pub fn share_bal_to_u128(input: Share::BalanceOf<T>) -> Option<u128> {
+		input.try_into().ok()
+	}
+	pub fn payment_bal_to_u128(input: Payment::BalanceOf<T>) -> Option<u128> {
+		input.try_into().ok()
+	}
+	pub fn hfund_bal_to_u128(input: HFund::BalanceOf<T>) -> Option<u128> {
+		input.try_into().ok()
+	}
 
 	// to be deleted//
 
@@ -259,177 +848,416 @@ impl<T: Config> Pallet<T> {
 		call
 	}
 
-	///The function below is monitoring ongoing referendums
-	///in order to update the status of corresponding Proposal Logs
+	///The function below starts a scan of the storages needing background tasks every
+	///`CheckPeriod`: ongoing referendums are checked to update the status of the corresponding
+	///Proposal Logs, the pending rents of the assets are distributed, the rent arrears of the
+	///tenants are checked every `RentCheck`, and the re-elections of the representatives whose
+	///term ended are started and closed.
+	///The scan and the tasks it queues are executed in `on_idle`, see `finish_block`.
 	pub fn begin_block(now: T::BlockNumber) -> Weight {
-		let max_block_weight = Weight::from_ref_time(1000_u64);
-		if (now % <T as Config>::CheckPeriod::get()).is_zero() {
-			let indexes = ProposalsIndexes::<T>::iter();
-			for index in indexes {
-				//check if the status is Finished
-				let ref_infos: RefInfos<T> = Dem::Pallet::<T>::referendum_info(index.1).unwrap();
-				let b = match ref_infos {
-					pallet_democracy::ReferendumInfo::Finished { approved, end: _ } =>
-						(1, approved),
-					_ => (0, false),
-				};
-				if b.0 == 1 {
-					//get the local prop_infos and update vote result if referendum ended
-					ProposalsLog::<T>::mutate(index.1, |val| {
-						let mut val0 = val.clone().unwrap();
-						if b.1 {
-							val0.vote_result = VoteResult::ACCEPTED
-						} else {
-							val0.vote_result = VoteResult::REJECTED
+		if !(now % <T as Config>::CheckPeriod::get()).is_zero() {
+			return Weight::zero()
+		}
+
+		//A scan still running since the previous check goes on
+		let arrears_check = (now % <T as Config>::RentCheck::get()).is_zero();
+		let scan = match Self::background_scan() {
+			Some(mut scan) => {
+				scan.arrears_check = scan.arrears_check || arrears_check;
+				scan
+			},
+			None => ScanCursor {
+				stage: ScanStage::Proposals,
+				last_key: Self::scan_prefix(ScanStage::Proposals),
+				arrears_check,
+			},
+		};
+		BackgroundScan::<T>::put(scan);
+		Self::enqueue_task(Task::Scan);
+
+		//The scan cursor, and the deduplication map, queue and depth of the scan task
+		T::DbWeight::get().reads_writes(4, 4)
+	}
+
+	///Storage prefix of a stage of the background scan
+	pub fn scan_prefix(stage: ScanStage) -> Vec<u8> {
+		match stage {
+			ScanStage::Proposals => ProposalsIndexes::<T>::final_prefix().to_vec(),
+			ScanStage::Tenants => Roles::TenantLog::<T>::final_prefix().to_vec(),
+			ScanStage::RepresentativeTerms => RepresentativeTerms::<T>::final_prefix().to_vec(),
+			ScanStage::Reelections => Reelections::<T>::final_prefix().to_vec(),
+		}
+	}
+
+	///Scan up to `MaxScannedItems` storage items from the position of the background scan, and
+	///queue the tasks they need. The scan is queued again until all the storages are scanned.
+	pub fn scan_storage(now: T::BlockNumber) {
+		let mut scan = match Self::background_scan() {
+			Some(scan) => scan,
+			None => return,
+		};
+		let mut budget = T::MaxScannedItems::get() as usize;
+		loop {
+			let mut scanned = 0;
+			let last_key = match scan.stage {
+				ScanStage::Proposals => {
+					let mut items = ProposalsIndexes::<T>::iter_from(scan.last_key.clone());
+					for (_, index) in items.by_ref().take(budget) {
+						scanned += 1;
+						let awaiting = Self::proposals(index)
+							.map(|proposal| proposal.vote_result == VoteResult::AWAITING)
+							.unwrap_or(false);
+						if awaiting {
+							Self::enqueue_task(Task::ReferendumCheck(index));
 						}
-						*val = Some(val0)
-					});
+					}
+					items.last_raw_key().to_vec()
+				},
+				ScanStage::Tenants => {
+					let mut items = Roles::TenantLog::<T>::iter_from(scan.last_key.clone());
+					for (account, tenant) in items.by_ref().take(budget) {
+						scanned += 1;
+						let asset_account = match tenant.asset_account {
+							Some(asset_account) => asset_account,
+							None => continue,
+						};
+						let rent_nbr = Self::owners_infos(asset_account.clone())
+							.map(|infos| infos.rent_nbr)
+							.unwrap_or_default();
+						if rent_nbr > 0 {
+							Self::enqueue_task(Task::RentDistribution {
+								tenant: account.clone(),
+								asset_account,
+							});
+						}
+						if scan.arrears_check {
+							Self::enqueue_task(Task::ArrearsCheck(account));
+						}
+					}
+					items.last_raw_key().to_vec()
+				},
+				ScanStage::RepresentativeTerms => {
+					let mut items = RepresentativeTerms::<T>::iter_from(scan.last_key.clone());
+					for (collection, item, end) in items.by_ref().take(budget) {
+						scanned += 1;
+						if end <= now && !Reelections::<T>::contains_key(collection, item) {
+							Self::enqueue_task(Task::ReelectionStart { collection, item });
+						}
+					}
+					items.last_raw_key().to_vec()
+				},
+				ScanStage::Reelections => {
+					let mut items = Reelections::<T>::iter_from(scan.last_key.clone());
+					for (collection, item, reelection) in items.by_ref().take(budget) {
+						scanned += 1;
+						if reelection.end <= now {
+							Self::enqueue_task(Task::ReelectionClose { collection, item });
+						}
+					}
+					items.last_raw_key().to_vec()
+				},
+			};
+			budget = budget.saturating_sub(scanned);
+			if budget == 0 {
+				scan.last_key = last_key;
+				break
+			}
+
+			//The storage was fully scanned
+			match scan.stage.next() {
+				Some(stage) => {
+					scan.stage = stage;
+					scan.last_key = Self::scan_prefix(stage);
+				},
+				None => {
+					BackgroundScan::<T>::kill();
+					return
+				},
+			}
+		}
+		BackgroundScan::<T>::put(scan);
+		Self::enqueue_task(Task::Scan);
+	}
+
+	///Add a task to the background task queue, unless it is already queued.
+	///Tasks that don't fit in a full queue are queued again at the next check.
+	pub fn enqueue_task(task: Task<T>) {
+		if QueuedTasks::<T>::contains_key(&task) {
+			return
+		}
+		let queued =
+			TaskQueue::<T>::try_mutate(task.priority(), |queue| queue.try_push(task.clone()));
+		if queued.is_ok() {
+			QueuedTasks::<T>::insert(&task, ());
+			QueueDepth::<T>::mutate(|val| {
+				*val = val.saturating_add(1);
+			});
+		}
+	}
+
+	///The function below executes the queued background tasks, by order of priority,
+	///as long as the remaining weight of the block allows it.
+	///Tasks that could not be executed stay in the queue for the next blocks.
+	pub fn finish_block(now: T::BlockNumber, max_weight: Weight) -> Weight {
+		let mut used_weight = T::DbWeight::get().reads(1);
+		if Self::queue_depth() == 0 {
+			return used_weight
+		}
+		let priorities =
+			[TaskPriority::Scan, TaskPriority::High, TaskPriority::Medium, TaskPriority::Low];
+		for priority in priorities {
+			let queue = Self::task_queue(priority);
+			used_weight = used_weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+			let mut executed = 0;
+			for task in queue.iter() {
+				let task_weight = task.weight();
+				if used_weight.saturating_add(task_weight) > max_weight {
+					break
 				}
+				QueuedTasks::<T>::remove(task);
+				Self::execute_task(task.clone(), now);
+				used_weight = used_weight.saturating_add(task_weight);
+				executed += 1;
+			}
+			if executed > 0 {
+				//The executed tasks may have queued new tasks
+				TaskQueue::<T>::mutate(priority, |queue| {
+					let mut tasks = queue.clone().into_inner();
+					tasks.drain(..executed.min(tasks.len()));
+					*queue = BoundedVec::try_from(tasks).unwrap_or_default();
+				});
+				QueueDepth::<T>::mutate(|val| {
+					*val = val.saturating_sub(executed as u32);
+				});
+			}
+			if used_weight >= max_weight {
+				break
+			}
+		}
+		used_weight
+	}
+
+	pub fn execute_task(task: Task<T>, now: T::BlockNumber) {
+		match task {
+			Task::ReferendumCheck(index) => Self::check_referendum(index),
+			Task::RentDistribution { tenant, asset_account } =>
+				Self::distribute_rent(tenant, asset_account, now),
+			Task::ArrearsCheck(tenant) => Self::check_tenant_arrears(tenant, now),
+			Task::ReelectionStart { collection, item } => Self::start_reelection(collection, item),
+			Task::ReelectionClose { collection, item } =>
+				Self::close_reelection(collection, item, now),
+			Task::Scan => Self::scan_storage(now),
+		}
+	}
+
+	///Update the vote result of a Proposal Log when its referendum is finished
+	pub fn check_referendum(index: Dem::ReferendumIndex) {
+		let ref_infos: Option<RefInfos<T>> = Dem::Pallet::<T>::referendum_info(index);
+		if let Some(pallet_democracy::ReferendumInfo::Finished { approved, end: _ }) = ref_infos {
+			//get the local prop_infos and update vote result
+			ProposalsLog::<T>::mutate(index, |val| {
+				if let Some(val0) = val {
+					if approved {
+						val0.vote_result = VoteResult::ACCEPTED
+					} else {
+						val0.vote_result = VoteResult::REJECTED
+					}
+				}
+			});
+		}
+	}
+
+	///Distribute the rent payed by the tenants to the owners, according to their share.
+	///Maintenance fees and the representative fee are removed from the rent first.
+	pub fn distribute_rent(tenant: T::AccountId, asset_account: T::AccountId, now: T::BlockNumber) {
+		let infos = match Self::owners_infos(asset_account.clone()) {
+			Some(infos) => infos,
+			None => return,
+		};
+		if infos.rent_nbr == 0 {
+			return
+		}
+
+		//Each rent period is shared according to the owners
+		//token balances when the rent was received
+		let periods = Share::Pallet::<T>::pending_rent_periods(asset_account.clone());
+		for (period_id, period) in periods {
+			let rent0 = Self::share_bal_to_u128(period.amount).unwrap_or_default();
+			let bals0 = BalanceType::<T>::convert_to_balance(rent0);
+			let rent1 = bals0.manage_bal;
+
+			//Remove maintenance fees from rent, a small partial payment may not fund the
+			//maintenance reserve
+			let maintenance = T::Maintenance::get().mul_floor(rent1);
+			let distribute = rent1.saturating_sub(maintenance);
+
+			//Pay the representative management fee from the rent
+			let fee = Self::pay_representative_fee(
+				asset_account.clone(),
+				infos.token_id,
+				rent1,
+				distribute,
+				now,
+			);
+			let distribute = distribute.saturating_sub(fee);
+
+			//Reserve maintenance fees in the asset maintenance reserve
+			if !maintenance.is_zero() {
+				Self::fund_maintenance_reserve(&tenant, &asset_account, maintenance, now);
 			}
+
+			//Now distribute rent between owners according to the snapshot
+			let distribute0 = Self::manage_bal_to_u128(distribute).unwrap_or_default();
+			let bals1 = BalanceType::<T>::convert_to_balance(distribute0);
+			Share::Pallet::<T>::distribute_dividends(
+				asset_account.clone(),
+				period_id,
+				bals1.share_bal,
+			)
+			.ok();
+
+			//Emmit rent distribution event
+			let owners = period.snapshot.iter().map(|(owner, _)| owner.clone()).collect();
+			Self::deposit_event(Event::RentDistributed { owners, amount: distribute, when: now });
+		}
+
+		//Now return the awaiting payment number to 0
+		if let Some((collection, item)) = Share::Pallet::<T>::virtual_asset(&asset_account) {
+			Share::Virtual::<T>::mutate(collection, item, |val| {
+				if let Some(val0) = val {
+					val0.rent_nbr = 0;
+				}
+			});
 		}
-		max_block_weight
 	}
 
-	///The function below regularly checks (every 15 days) for active Tenants on the blockchain
-	///when a tenant is fund, his specific Rent-per-block is first calculated.
+	fn fund_maintenance_reserve(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+		maintenance: BalanceOf<T>,
+		now: T::BlockNumber,
+	) {
+		if let Err(err) = <T as Config>::Currency::reserve(asset_account, maintenance) {
+			log::warn!("maintenance fees of {:?} could not be reserved: {:?}", asset_account, err);
+			return
+		}
+		MaintenanceReserves::<T>::mutate(asset_account, |val| {
+			*val = val.saturating_add(maintenance);
+		});
+		MaintenanceRecord::<T>::new(
+			asset_account.clone(),
+			MaintenanceOperation::Deposit,
+			maintenance,
+			None,
+			None,
+		);
+
+		//Emmit maintenance fee payment event
+		Self::deposit_event(Event::MaintenanceFeesPayment {
+			tenant: tenant.clone(),
+			when: now,
+			asset_account: asset_account.clone(),
+			amount: maintenance,
+		});
+	}
+
+	///When a tenant is found, his specific Rent-per-block is first calculated.
 	///Next, based on the number of blocks ellapsed since the day of its activation,
 	///the amount that should have been paid up to this point is calculated, and compared
 	///with the amount that has been actually paid.
 	///If the balance of the Tenant is negative, an event is emitted to notify him of his debt,
 	///If not, nothing happens.
-	///It will also distribute payed rent to the owners, according to their share.
-	pub fn finish_block(now: T::BlockNumber) -> Weight {
-		if (now % <T as Config>::CheckPeriod::get()).is_zero() {
-			//get list of tenants
-			let tenants = Roles::Pallet::<T>::tenant_list();
-			for i in tenants {
-				let tenant = Roles::Pallet::<T>::tenants(i).unwrap();
-				if tenant.asset_account.is_some() {
-					let time = <T as Config>::Lease::get();
-					let remaining_p = tenant.remaining_payments;
-					let contract_begin = tenant.contract_start;
-
-					let rent = Self::roles_bal_to_u128(tenant.rent).unwrap() * time as u128;
-					let rent_float = rent as f64;
-					let rent0 = Self::roles_bal_to_u128(tenant.rent).unwrap();
-
-					//Calculate rent per block
-					let total_blocks = <T as Config>::ContractLength::get();
-					let mut rpb = Self::blocknumber_to_u128(total_blocks).unwrap();
-					let mut rpb_float = rpb as f64;
-					rpb_float = (rent_float / rpb_float).round();
-					rpb = rpb_float as u128;
-
-					//number of blocks from the start of the contract
-					let blocks = Self::blocknumber_to_u128(now - contract_begin).unwrap();
-					let amount_due = blocks.saturating_mul(rpb);
-
-					//check how many rents were payed
-					let payed = (time as u128 - remaining_p as u128) * rent;
-					let asset_account = tenant.asset_account.clone().unwrap();
-					let asset_account_free_balance =
-						<T as Config>::Currency::free_balance(&asset_account);
-
-					let infos = Self::owners_infos(asset_account.clone()).unwrap();
-
-					//Distribute rent to owners if number of rents
-					//awaiting for distribution is greater than 0
-					if infos.rent_nbr > 0 {
-						//Get owners list
-
-						let owners = infos.owners;
-						let bals0 = BalanceType::<T>::convert_to_balance(rent0);
-						let rent1 = bals0.manage_bal;
-
-						//Get Asset_tokens infos
-						let token_id = infos.token_id;
-						let total_issuance = Assetss::Pallet::<T>::total_supply(token_id.into());
-						let total_issuance_float =
-							Self::assets_bal_to_u128(total_issuance).unwrap() as f64;
-
-						//Remove maintenance fees from rent and convert it to f64
-						let maintenance = T::Maintenance::get() * rent1;
-						let distribute = rent1.saturating_sub(maintenance);
-
-						//Get the total amount to distribute
-						let distribute_float = (Self::manage_bal_to_u128(distribute).unwrap() *
-							infos.rent_nbr as u128) as f64;
-
-						debug_assert!(distribute > Zero::zero());
-						debug_assert!(distribute < rent1);
-						debug_assert!(maintenance < asset_account_free_balance);
-
-						//Reserve maintenance fees
-						let reservation =
-							<T as Config>::Currency::reserve(&asset_account, maintenance);
-
-						//Emmit maintenance fee payment event
-						Self::deposit_event(Event::MaintenanceFeesPayment {
-							tenant: tenant.account_id.clone(),
-							when: now,
-							asset_account: tenant.asset_account.unwrap(),
-							amount: maintenance,
-						});
+	pub fn check_tenant_arrears(tenant: T::AccountId, now: T::BlockNumber) {
+		let tenant_debt0 = Self::tenant_debt(&tenant, now);
+		if tenant_debt0 > 0 {
+			let bals0 = BalanceType::<T>::convert_to_balance(tenant_debt0);
+			let debt = bals0.manage_bal;
 
-						debug_assert!(reservation.is_ok());
-
-						//Now distribute rent between owners according to their share
-						for i in owners.clone() {
-							//Get owner's share: we divide
-							//the owner's tokens by the total token issuance, and multiply the
-							// result by the total amount to be distributed.
-							let share = Assetss::Pallet::<T>::balance(token_id.into(), &i);
-							let share_float = Self::assets_bal_to_u128(share).unwrap() as f64 /
-								total_issuance_float;
-							let amount_float = share_float * distribute_float;
-							let bals0 = BalanceType::<T>::convert_to_balance(amount_float as u128);
-							let amount = bals0.manage_bal;
-							<T as Config>::Currency::transfer(
-								&asset_account,
-								&i,
-								amount,
-								ExistenceRequirement::AllowDeath,
-							)
-							.ok();
-						}
+			//Event to inform the tenant of the amount of his debt
+			Self::deposit_event(Event::TenantDebt { tenant, debt, when: now });
+		}
+	}
 
-						//Emmit rent distribution event
-						Self::deposit_event(Event::RentDistributed {
-							owners,
-							amount: distribute,
-							when: now,
-						});
+	///Unpaid rent of a tenant linked to an asset, at the given block
+	pub fn tenant_debt(tenant: &T::AccountId, now: T::BlockNumber) -> u128 {
+		let tenant = match Roles::Pallet::<T>::tenants(tenant) {
+			Some(tenant) => tenant,
+			None => return 0,
+		};
+		if tenant.asset_account.is_none() {
+			return 0
+		}
+		let time =
+			Self::tenant_leases(&tenant.account_id).unwrap_or_else(<T as Config>::Lease::get);
+		let remaining_p = tenant.remaining_payments;
+		let contract_begin = tenant.contract_start;
 
-						//Now return the awaiting payment number to 0
-						let ownership_infos = Share::Virtual::<T>::iter_keys();
-						for (i, j) in ownership_infos {
-							let infos = Share::Pallet::<T>::virtual_acc(i, j).unwrap();
-							if infos.virtual_account == asset_account {
-								Share::Virtual::<T>::mutate(i, j, |val| {
-									let mut val0 = val.clone().unwrap();
-									val0.rent_nbr = 0;
-									*val = Some(val0);
-								});
-							}
-						}
-					}
+		let rent = Self::roles_bal_to_u128(tenant.rent).unwrap_or_default();
+		let lease_rent = rent.saturating_mul(time as u128);
 
-					//Calculate the debt if negative balance
-					if payed < amount_due && (now % <T as Config>::RentCheck::get()).is_zero() {
-						let tenant_debt0 = amount_due - payed;
-						let bals0 = BalanceType::<T>::convert_to_balance(tenant_debt0);
-						let debt = bals0.manage_bal;
-
-						//Event to inform the tenant of the amount of his debt
-						Self::deposit_event(Event::TenantDebt {
-							tenant: tenant.account_id,
-							debt,
-							when: now,
-						});
-					}
-				}
-			}
-		}
-		Weight::zero()
+		//Calculate rent per block, the contract length being given for a lease of
+		//`Lease` months
+		let total_blocks = <T as Config>::ContractLength::get();
+		let lease_blocks = Self::blocknumber_to_u128(total_blocks)
+			.unwrap_or_default()
+			.saturating_mul(time as u128) /
+			(<T as Config>::Lease::get() as u128).max(1);
+		let rpb = FixedU128::checked_from_rational(lease_rent, lease_blocks).unwrap_or_default();
+
+		//number of blocks from the start of the contract, the amount due
+		//being rounded down in favour of the tenant
+		let blocks =
+			Self::blocknumber_to_u128(now.saturating_sub(contract_begin)).unwrap_or_default();
+		let amount_due = rpb.saturating_mul_int(blocks).min(lease_rent);
+
+		//check how much rent was payed, partial payments decreasing only the remaining rent
+		let remaining = Self::roles_bal_to_u128(tenant.remaining_rent).unwrap_or_default();
+		let payed = (time as u128)
+			.saturating_sub(remaining_p as u128)
+			.saturating_mul(rent)
+			.max(lease_rent.saturating_sub(remaining));
+
+		amount_due.saturating_sub(payed)
+	}
+
+	///Start a `Demotion` vote session of a tenant, without the representative.
+	///The session is opened on behalf of the asset representative, or of the asset
+	///virtual account if the asset has no representative.
+	pub fn start_tenant_eviction(
+		tenant: T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Result<Dem::ReferendumIndex, DispatchError> {
+		let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+			.ok_or(Error::<T>::NotAnAsset)?
+			.virtual_account;
+		let house =
+			Onboarding::Pallet::<T>::houses(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+		ensure!(house.tenants.contains(&tenant), Error::<T>::TenantAssetNotLinked);
+		let caller = house.representative.unwrap_or_else(|| asset_account.clone());
+
+		let call = Call::<T>::unlink_tenant_to_asset { tenant: tenant.clone(), collection, item };
+		let proposal_hash = Self::create_proposal_hash_and_note(asset_account.clone(), call);
+		let threshold = Dem::VoteThreshold::SimpleMajority;
+		let delay = <T as Config>::Delay::get();
+		let referendum_index =
+			Dem::Pallet::<T>::internal_start_referendum(proposal_hash, threshold, delay);
+
+		ProposalRecord::<T>::new(
+			caller.clone(),
+			asset_account.clone(),
+			tenant.clone(),
+			referendum_index,
+			collection,
+			item,
+		)?;
+
+		Self::deposit_event(Event::TenantVoteSessionStarted {
+			representative: caller,
+			tenant,
+			asset_account,
+		});
+
+		Ok(referendum_index)
 	}
 }
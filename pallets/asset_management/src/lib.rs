@@ -25,6 +25,15 @@
 //!
 //! * `demote_representative` - Call used as a proposal for Representative demotion.
 //!
+//! * `submit_candidacy` - A pending Representative applies to an ongoing re-election.
+//!   When the term of a Representative ends, a re-election between the incumbent and the new
+//!   candidates starts automatically.
+//!
+//! * `reelection_vote` - Each asset owner votes for a candidate of an ongoing re-election. Votes
+//!   are weighted by the ownership tokens of the voter. If no candidate gathers the majority of
+//!   the votes, the `ReelectionFallback` rule is applied. The incumbent is kept at most
+//!   `MaxIncumbentExtensions` times in a row, before the management of the asset is suspended.
+//!
 //! * `launch_tenant_session` - A Representative creates a referendum for the following available
 //!   proposals:
 //!   - Admit a Tenant for a given asset.
@@ -43,6 +52,45 @@
 //!   asset.
 //!
 //! * `request_guaranty_payment` - Call used to send a guaranty deposit payment request to a tenant.
+//!
+//! * `launch_owners_proposal` - An Owner holding at least `MinimumProposalShare` of the ownership
+//!   tokens creates a referendum for one of the following actions, each with its own threshold:
+//!   - Change the rent of the asset.
+//!   - Pay a renovation from the maintenance reserve.
+//!   - Change the lease length of the asset.
+//!   - Buy an insurance for the asset, from an approved servicer.
+//!   - Sell the asset.
+//!   - Convert a share of the rents into ownership tokens for the tenants (rent-to-own).
+//!
+//! * `enact_owners_proposal` - Call used as a proposal to enact the action of an owners proposal.
+//!
+//! * `list_asset_for_sale` - Call used as a proposal to list the asset for sale. The tenants of
+//!   the asset are given a notice.
+//!
+//! * `pay_maintenance_invoice` - The Representative of an asset pays a servicer invoice from the
+//!   maintenance reserve of the asset. The reserve is funded with the `Maintenance` percentage of
+//!   each rent. Invoices taking the spendings of the `MaintenanceSpendPeriod` above the
+//!   `MaintenanceSpendLimit` need a positive vote of the owners.
+//!
+//! * `execute_maintenance_spend` - Call used as a proposal to pay a servicer invoice from the
+//!   maintenance reserve.
+//!
+//! * `buy_asset` - Once the tenants notice is over, a buyer pays the asking price of a listed
+//!   asset. The proceeds are shared between the owners according to their ownership tokens,
+//!   the tenants are unlinked from the asset, the ownership tokens are burned and the asset
+//!   virtual account is closed.
+//!
+//! * `set_appraiser` - Governance designates the appraiser of an asset.
+//!
+//! * `submit_revaluation` - A notary or the appraiser of an asset submits a new valuation of the
+//!   asset, once every `RevaluationPeriod`. The owners vote on the valuation.
+//!
+//! * `approve_revaluation` - Call used as a proposal, or by governance, to approve a valuation.
+//!   The valuation is added to the value history of the asset, and the net asset value per
+//!   ownership token becomes the price used for buybacks.
+//!
+//! * `consent_rent_to_own` - An Owner consents to sell his ownership tokens to the rent-to-own
+//!   tenants of the asset, at the net asset value per token.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -58,6 +106,7 @@ pub use pallet_roles as Roles;
 pub use pallet_share_distributor as Share;
 
 mod functions;
+pub mod migration;
 mod types;
 pub use crate::types::*;
 pub use functions::*;
@@ -76,8 +125,12 @@ pub mod pallet {
 	use super::*;
 	use frame_system::WeightInfo;
 
+	/// The current storage version
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -141,6 +194,51 @@ pub mod pallet {
 		/// Maintenance fees percentage taken on monthly rent
 		#[pallet::constant]
 		type Maintenance: Get<Percent>;
+
+		/// Notice period given to the tenants of an asset listed for sale, in number of blocks
+		#[pallet::constant]
+		type NoticePeriod: Get<Self::BlockNumber>;
+
+		/// Maximum amount a representative can spend from a maintenance reserve without a vote,
+		/// in each maintenance spend period
+		#[pallet::constant]
+		type MaintenanceSpendLimit: Get<BalanceOf<Self>>;
+
+		/// Length of the periods of the maintenance spend limit, in number of blocks
+		#[pallet::constant]
+		type MaintenanceSpendPeriod: Get<Self::BlockNumber>;
+
+		/// Management fee paid to the representative of an asset, for each rent received
+		#[pallet::constant]
+		type ManagementFee: Get<ManagementFee<BalanceOf<Self>>>;
+
+		/// Term of a representative, in number of blocks
+		#[pallet::constant]
+		type RepresentativeTerm: Get<Self::BlockNumber>;
+
+		/// Rule applied when a re-election has no winner
+		#[pallet::constant]
+		type ReelectionFallback: Get<ReelectionFallback<Self::BlockNumber>>;
+
+		/// Minimum share of the ownership tokens needed to submit an owners proposal
+		#[pallet::constant]
+		type MinimumProposalShare: Get<Percent>;
+
+		/// Minimum number of blocks between two revaluations of an asset
+		#[pallet::constant]
+		type RevaluationPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of background tasks of each priority waiting in the queue
+		#[pallet::constant]
+		type MaxQueuedTasks: Get<u32>;
+
+		/// Maximum number of storage items read by the background scan in one block
+		#[pallet::constant]
+		type MaxScannedItems: Get<u32>;
+
+		/// Maximum number of consecutive re-elections where the incumbent is kept without winner
+		#[pallet::constant]
+		type MaxIncumbentExtensions: Get<u32>;
 	}
 
 	//Store the referendum_index and the struct containing the
@@ -151,14 +249,28 @@ pub mod pallet {
 		StorageMap<_, Blake2_128Concat, Dem::ReferendumIndex, ProposalRecord<T>, OptionQuery>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn guaranty)]
+	#[pallet::getter(fn guaranty_id)]
+	///Guaranty deposit payment requested from a tenant, the payment details are kept in
+	/// `pallet_payment`
 	pub type GuarantyPayment<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId, // payment issuer
 		Blake2_128Concat,
 		T::AccountId, // payment recipient
-		Payment::PaymentDetail<T>,
+		Payment::PaymentId,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_guaranties)]
+	///Guaranty deposit payments of the tenants of each asset account, indexing `GuarantyPayment`
+	pub type AssetGuaranties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // payment recipient
+		Blake2_128Concat,
+		T::AccountId, // payment issuer
+		Payment::PaymentId,
 	>;
 
 	#[pallet::storage]
@@ -166,6 +278,261 @@ pub mod pallet {
 	pub type ProposalsIndexes<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, Dem::ReferendumIndex, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_reserve)]
+	///Maintenance reserve of each asset account
+	pub type MaintenanceReserves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_spendings)]
+	///Start of the current maintenance spend period of each asset account, and the amount
+	///spent without a vote during the period
+	pub type MaintenanceSpendings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(BlockNumberOf<T>, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_log)]
+	///History of the maintenance reserve operations of each asset account
+	pub type MaintenanceLog<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u32,
+		MaintenanceRecord<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_log_id)]
+	///Next maintenance operation id of each asset account
+	pub type MaintenanceLogId<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_escrow)]
+	///Asset account of each maintenance reserve spending kept in a `pallet_payment` escrow
+	pub type MaintenanceEscrows<T: Config> =
+		StorageMap<_, Blake2_128Concat, Payment::PaymentId, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn owners_proposals)]
+	///Action of each owners proposal, by referendum index
+	pub type OwnersProposals<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dem::ReferendumIndex, OwnersAction<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_rents)]
+	///Monthly rent of an asset set by its owners
+	pub type AssetRents<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Roles::BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_leases)]
+	///Lease length of an asset set by its owners, in number of months
+	pub type AssetLeases<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		u32,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tenant_leases)]
+	///Lease length of each linked tenant, in number of months
+	pub type TenantLeases<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tenant_shares)]
+	///Share of the rent and guaranty of a co-tenant, with the virtual account of the shared asset
+	pub type TenantShares<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, Percent), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_to_own_share)]
+	///Share of the rents converted into ownership tokens, for the tenants of a rent-to-own asset
+	pub type RentToOwnShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Percent,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_to_own_sellers)]
+	///Owners of an asset consenting to sell their ownership tokens to rent-to-own tenants
+	pub type RentToOwnSellers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn insurances)]
+	///Insurance policies bought by the owners of an asset
+	pub type Insurances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		InsurancePolicy<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn representative_terms)]
+	///End of the term of the representative of each asset
+	pub type RepresentativeTerms<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		BlockNumberOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn incumbent_extensions)]
+	///Number of consecutive re-elections without winner where the incumbent was kept
+	pub type IncumbentExtensions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reelections)]
+	///Ongoing re-elections of representatives
+	pub type Reelections<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Reelection<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn representative_earnings)]
+	///Management fees earned by each representative, per asset account
+	pub type RepresentativeEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn flat_fee_payments)]
+	///Block of the last flat management fee paid by each asset account
+	pub type FlatFeePayments<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_sales)]
+	///Assets listed for sale after a positive vote of their owners
+	pub type AssetSales<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		SaleRecord<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn appraisers)]
+	///Appraisers designated by governance to value the assets
+	pub type Appraisers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_revaluations)]
+	///Revaluations waiting for the approval of the owners, with their referendum index
+	pub type PendingRevaluations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		(Dem::ReferendumIndex, Revaluation<T>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn value_history)]
+	///Approved valuations of each asset, the latest being the current value of the asset
+	pub type ValueHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Vec<Revaluation<T>>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn task_queue)]
+	///Background tasks waiting to be executed in `on_idle`, by priority
+	pub type TaskQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TaskPriority,
+		BoundedVec<Task<T>, T::MaxQueuedTasks>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queued_tasks)]
+	///Background tasks waiting in the queue, so that they are not queued twice
+	pub type QueuedTasks<T: Config> = StorageMap<_, Blake2_128Concat, Task<T>, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queue_depth)]
+	///Number of background tasks waiting to be executed
+	pub type QueueDepth<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn background_scan)]
+	///Position of the ongoing scan of the storages needing background tasks
+	pub type BackgroundScan<T> = StorageValue<_, ScanCursor, OptionQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -236,6 +603,142 @@ pub mod pallet {
 			asset_account: T::AccountId,
 			amount: BalanceOf<T>,
 		},
+
+		///A voting session for an owners proposal has started
+		OwnersProposalStarted {
+			caller: T::AccountId,
+			asset_account: T::AccountId,
+			session_number: Dem::ReferendumIndex,
+			action: OwnersAction<T>,
+		},
+		///The action of an owners proposal was enacted
+		OwnersProposalEnacted {
+			asset_account: T::AccountId,
+			action: OwnersAction<T>,
+			when: BlockNumberOf<T>,
+		},
+		///The term of a representative ended, and a re-election started
+		ReelectionStarted {
+			incumbent: T::AccountId,
+			asset_account: T::AccountId,
+			end: BlockNumberOf<T>,
+		},
+		///A new candidate applied to a re-election
+		CandidacySubmitted { candidate: T::AccountId, asset_account: T::AccountId },
+		///An owner voted in a re-election
+		ReelectionVoted {
+			caller: T::AccountId,
+			candidate: T::AccountId,
+			asset_account: T::AccountId,
+		},
+		///A representative won a re-election, and starts a new term
+		RepresentativeElected {
+			representative: T::AccountId,
+			asset_account: T::AccountId,
+			term_end: BlockNumberOf<T>,
+		},
+		///A re-election had no winner, and the incumbent stays temporarily in charge
+		IncumbentKept {
+			representative: T::AccountId,
+			asset_account: T::AccountId,
+			term_end: BlockNumberOf<T>,
+		},
+		///A re-election had no winner, and the asset management is suspended
+		ManagementSuspended { representative: T::AccountId, asset_account: T::AccountId },
+		///A management fee was paid to the representative of an asset
+		RepresentativeFeePaid {
+			representative: T::AccountId,
+			asset_account: T::AccountId,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///A servicer invoice was paid from a maintenance reserve
+		MaintenanceReserveSpent {
+			servicer: T::AccountId,
+			asset_account: T::AccountId,
+			amount: BalanceOf<T>,
+			invoice: T::Hash,
+			when: BlockNumberOf<T>,
+		},
+		///A voting session to pay a servicer invoice above the spend limit has started
+		MaintenanceSpendVoteSessionStarted {
+			representative: T::AccountId,
+			servicer: T::AccountId,
+			asset_account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		///An asset was listed for sale
+		AssetListedForSale {
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			asset_account: T::AccountId,
+			price: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///A tenant was given notice of the sale of its asset
+		TenantNoticeGiven {
+			tenant: T::AccountId,
+			asset_account: T::AccountId,
+			notice_end: BlockNumberOf<T>,
+		},
+		///Sale proceeds distributed to owners
+		SaleProceedsDistributed {
+			owners: Vec<T::AccountId>,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///An asset was sold
+		AssetSold {
+			buyer: T::AccountId,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			price: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///An appraiser was designated for an asset
+		AppraiserSet {
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			appraiser: Option<T::AccountId>,
+		},
+		///A revaluation of an asset was submitted to the owners
+		RevaluationSubmitted {
+			appraiser: T::AccountId,
+			asset_account: T::AccountId,
+			value: BalanceOf<T>,
+			session_number: Dem::ReferendumIndex,
+		},
+		///A revaluation of an asset was approved
+		RevaluationApproved {
+			asset_account: T::AccountId,
+			value: BalanceOf<T>,
+			nav_per_token: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///The share of a co-tenant in the rent and guaranty of an asset was set
+		TenantShareSet { tenant: T::AccountId, asset_account: T::AccountId, share: Percent },
+		///An owner consents, or not, to sell ownership tokens to rent-to-own tenants
+		RentToOwnConsentSet { owner: T::AccountId, asset_account: T::AccountId, consent: bool },
+		///The management fee could not be paid to the representative of an asset
+		RepresentativeFeeFailed {
+			representative: T::AccountId,
+			asset_account: T::AccountId,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///A maintenance escrow was refunded, and the amount went back to the maintenance reserve
+		MaintenanceReserveRefunded {
+			asset_account: T::AccountId,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///The representative of an asset could not be replaced or demoted after a re-election
+		ReelectionFailed {
+			representative: T::AccountId,
+			candidate: Option<T::AccountId>,
+			asset_account: T::AccountId,
+			error: DispatchError,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -287,6 +790,46 @@ pub mod pallet {
 		NotEnoughTenantFunds,
 		/// The Tenant did not provide detailed information
 		NotARegisteredTenant,
+		/// The owner does not hold enough ownership tokens to submit a proposal
+		NotEnoughShares,
+		/// The lease length is not valid
+		InvalidLeaseLength,
+		/// There is no ongoing re-election for this asset
+		NoOngoingReelection,
+		/// The re-election votes are being counted
+		ReelectionCompleted,
+		/// The account is already a candidate
+		AlreadyACandidate,
+		/// The account is not a candidate
+		NotACandidate,
+		/// The account is not a servicer
+		NotAServicer,
+		/// Not enough funds in the maintenance reserve
+		NotEnoughMaintenanceFunds,
+		/// The asset is listed for sale
+		AssetListedForSale,
+		/// The asset is not listed for sale
+		AssetNotListedForSale,
+		/// The tenants notice period is not over
+		NoticePeriodNotOver,
+		/// The asset has no price
+		AssetPriceMissing,
+		/// The rent or guaranty amount cannot be computed
+		InvalidRentAmount,
+		/// The account is not a notary or the appraiser of the asset
+		NotAnAppraiser,
+		/// A revaluation of the asset is already waiting for approval
+		RevaluationPending,
+		/// The previous valuation of the asset is too recent
+		RevaluationNotDue,
+		/// There is no revaluation waiting for approval
+		NoPendingRevaluation,
+		/// The value of the asset cannot be computed
+		InvalidValuation,
+		/// The shares of the co-tenants exceed the rent of the asset
+		RentShareExceeded,
+		/// The spending exceeds the maintenance spend limit of the period
+		MaintenanceSpendLimitExceeded,
 	}
 
 	#[pallet::hooks]
@@ -295,8 +838,13 @@ pub mod pallet {
 			Self::begin_block(n)
 		}
 
-		fn on_idle(n: T::BlockNumber, _max_weight: Weight) -> Weight {
-			Self::finish_block(n)
+		fn on_idle(n: T::BlockNumber, max_weight: Weight) -> Weight {
+			Self::finish_block(n, max_weight)
+		}
+
+		/// Key the guaranty payments by the payment ids of `pallet_payment`
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
 		}
 	}
 
@@ -460,12 +1008,12 @@ pub mod pallet {
 			let status = infos.vote_result;
 			ensure!(status == VoteResult::AWAITING, Error::<T>::ReferendumCompleted);
 			//check that caller can vote
-			let ownership =
-				Share::Pallet::<T>::virtual_acc(infos.collection_id, infos.item_id).unwrap();
+			let ownership = Share::Pallet::<T>::virtual_acc(infos.collection_id, infos.item_id)
+				.ok_or(Error::<T>::NotAnAsset)?;
 			ensure!(Self::caller_can_vote(&voter, ownership.clone()), Error::<T>::NotAnOwner);
 			//Get number of FS tokens own by caller
 			let tokens = Assetss::Pallet::<T>::balance(ownership.token_id.into(), &voter);
-			let token0 = Self::assets_bal_to_u128(tokens).unwrap();
+			let token0 = Self::assets_bal_to_u128(tokens).ok_or(Error::<T>::NoneValue)?;
 			let bals0 = BalanceType::<T>::convert_to_balance(token0);
 			let token1 = bals0.dem_bal;
 
@@ -501,8 +1049,9 @@ pub mod pallet {
 			item: T::NftItemId,
 		) -> DispatchResult {
 			let caller = ensure_signed(origin.clone())?;
-			let asset_account =
-				Share::Pallet::<T>::virtual_acc(collection, item).unwrap().virtual_account;
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
 
 			//Check that the caller is a stored virtual account
 			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
@@ -516,6 +1065,14 @@ pub mod pallet {
 			//Approve role request
 			Self::approve_representative_role(origin, rep_account.clone()).ok();
 
+			//Start the term of the representative
+			let now = <frame_system::Pallet<T>>::block_number();
+			RepresentativeTerms::<T>::insert(
+				collection,
+				item,
+				now.saturating_add(T::RepresentativeTerm::get()),
+			);
+
 			Self::deposit_event(Event::RepresentativeCandidateApproved {
 				candidate: rep_account,
 				asset_account: caller,
@@ -540,11 +1097,10 @@ pub mod pallet {
 			let caller = ensure_signed(origin)?;
 
 			//Check that the caller is a stored virtual account
-			ensure!(
-				caller ==
-					Share::Pallet::<T>::virtual_acc(collection, item).unwrap().virtual_account,
-				Error::<T>::NotAnAssetAccount
-			);
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
 
 			//revoke Representative Role
 			Self::revoke_representative_role(rep_account.clone()).ok();
@@ -553,6 +1109,9 @@ pub mod pallet {
 				asset0.representative = None;
 				*asset = Some(asset0);
 			});
+			RepresentativeTerms::<T>::remove(collection, item);
+			Reelections::<T>::remove(collection, item);
+			IncumbentExtensions::<T>::remove(collection, item);
 
 			Self::deposit_event(Event::RepresentativeDemoted {
 				candidate: rep_account,
@@ -563,43 +1122,126 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Using the function below, a representative triggers a vote session with a proposal for a
-		/// tenant to be linked with an asset The origin must be a representative
+		/// The function below allows a pending representative to apply to the ongoing
+		/// re-election of an asset representative.
+		/// The origin must be in the representative waiting list
 		/// - asset_type: type of the asset
 		/// - asset_id: id of the asset
-		/// - tenant: an account with the tenant role
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn launch_tenant_session(
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn submit_candidacy(
 			origin: OriginFor<T>,
 			asset_type: Nft::PossibleCollections,
 			asset_id: T::NftItemId,
-			tenant: T::AccountId,
-			proposal: VoteProposals,
-			judgement: Ident::Judgement<IdentBalanceOf<T>>,
 		) -> DispatchResult {
-			let caller = ensure_signed(origin.clone())?;
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
 
-			// Ensure that the caller is a representative
-			let rep = Roles::Pallet::<T>::reps(caller.clone());
-			ensure!(rep.is_some(), Error::<T>::NotARepresentative);
-			let rep = rep.unwrap();
-			ensure!(rep.activated, Error::<T>::NotAnActiveRepresentative);
+			let reelection = Self::reelections(collection_id, asset_id)
+				.ok_or(Error::<T>::NoOngoingReelection)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < reelection.end, Error::<T>::ReelectionCompleted);
+			ensure!(
+				Roles::Pallet::<T>::get_pending_representatives(&caller).is_some(),
+				Error::<T>::NotAPendingRepresentative
+			);
+			ensure!(!reelection.candidates.contains(&caller), Error::<T>::AlreadyACandidate);
 
-			// Get the asset virtual account if exists
-			let collection_id: T::NftCollectionId = asset_type.value().into();
-			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
-			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			Reelections::<T>::mutate(collection_id, asset_id, |val| {
+				let mut val0 = val.clone().unwrap();
+				val0.candidates.push(caller.clone());
+				*val = Some(val0);
+			});
 
-			//Compare guaranty payment amount+fees with tenant free_balance
-			let guaranty = Self::calculate_guaranty(collection_id, asset_id);
-			let fee0 = Self::manage_bal_to_u128(T::RepFees::get()).unwrap();
-			let bals0 = BalanceType::<T>::convert_to_balance(guaranty);
+			Self::deposit_event(Event::CandidacySubmitted {
+				candidate: caller,
+				asset_account: reelection.virtual_account,
+			});
+
+			Ok(())
+		}
+
+		/// The function below allows an owner to vote for a candidate of an ongoing
+		/// re-election. The vote is weighted by the ownership tokens of the owner when the
+		/// votes are counted. A new vote replaces the previous one.
+		/// The origin must be an owner of the asset
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - candidate: candidate of the re-election
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn reelection_vote(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			candidate: T::AccountId,
+		) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+
+			let reelection = Self::reelections(collection_id, asset_id)
+				.ok_or(Error::<T>::NoOngoingReelection)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < reelection.end, Error::<T>::ReelectionCompleted);
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?;
+			ensure!(Self::caller_can_vote(&voter, ownership), Error::<T>::NotAnOwner);
+			ensure!(reelection.candidates.contains(&candidate), Error::<T>::NotACandidate);
+
+			Reelections::<T>::mutate(collection_id, asset_id, |val| {
+				let mut val0 = val.clone().unwrap();
+				val0.votes.retain(|(owner, _)| *owner != voter);
+				val0.votes.push((voter.clone(), candidate.clone()));
+				*val = Some(val0);
+			});
+
+			Self::deposit_event(Event::ReelectionVoted {
+				caller: voter,
+				candidate,
+				asset_account: reelection.virtual_account,
+			});
+
+			Ok(())
+		}
+
+		/// Using the function below, a representative triggers a vote session with a proposal for a
+		/// tenant to be linked with an asset The origin must be a representative
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - tenant: an account with the tenant role
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		pub fn launch_tenant_session(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			tenant: T::AccountId,
+			proposal: VoteProposals,
+			judgement: Ident::Judgement<IdentBalanceOf<T>>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin.clone())?;
+
+			// Ensure that the caller is a representative
+			let rep = Roles::Pallet::<T>::reps(caller.clone());
+			ensure!(rep.is_some(), Error::<T>::NotARepresentative);
+			let rep = rep.unwrap();
+			ensure!(rep.activated, Error::<T>::NotAnActiveRepresentative);
+
+			// Get the asset virtual account if exists
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
+			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			let asset_account = ownership.unwrap().virtual_account;
+
+			//Compare guaranty payment amount+fees with tenant free_balance, a co-tenant
+			//paying his share of the guaranty
+			let guaranty = Self::rent_share(&tenant, &asset_account)
+				.mul_floor(Self::calculate_guaranty(collection_id, asset_id)?);
+			let fee0 = Self::manage_bal_to_u128(T::RepFees::get()).ok_or(Error::<T>::NoneValue)?;
+			let bals0 = BalanceType::<T>::convert_to_balance(guaranty);
 			let fee1 = T::IncentivePercentage::get() * bals0.manage_bal;
-			let total_amount = guaranty + fee0 + Self::manage_bal_to_u128(fee1).unwrap();
+			let fee2 = Self::manage_bal_to_u128(fee1).ok_or(Error::<T>::NoneValue)?;
+			let total_amount = guaranty + fee0 + fee2;
 			let tenant_bal0: BalanceOf<T> = <T as Config>::Currency::free_balance(&tenant);
-			let tenant_bal = Self::manage_bal_to_u128(tenant_bal0).unwrap();
+			let tenant_bal = Self::manage_bal_to_u128(tenant_bal0).ok_or(Error::<T>::NoneValue)?;
 
-			let asset_account = ownership.unwrap().virtual_account;
 			ensure!(rep.assets_accounts.contains(&asset_account), Error::<T>::AssetOutOfControl);
 
 			// Ensure that provided account is a valid tenant
@@ -617,6 +1259,11 @@ pub mod pallet {
 						tenant0.asset_account.is_none(),
 						Error::<T>::TenantAlreadyLinkedWithAsset
 					);
+					// Ensure that the asset is not being sold
+					ensure!(
+						Self::asset_sales(collection_id, asset_id).is_none(),
+						Error::<T>::AssetListedForSale
+					);
 					//Ensure there is no existing payment request for this asset
 					ensure!(
 						Self::guaranty(&tenant0.account_id, &asset_account).is_none(),
@@ -711,11 +1358,12 @@ pub mod pallet {
 			let caller = ensure_signed(origin)?;
 
 			// Ensure the caller is the virtual account of the asset
-			let asset_account =
-				Share::Pallet::<T>::virtual_acc(collection, item).unwrap().virtual_account;
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
 			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
 
-			Self::tenant_link_asset(tenant.clone(), collection, item, asset_account.clone()).ok();
+			Self::tenant_link_asset(tenant.clone(), collection, item, asset_account.clone())?;
 
 			Self::deposit_event(Event::TenantLinkedToAsset {
 				tenant,
@@ -734,7 +1382,7 @@ pub mod pallet {
 		/// - collection: collection_id of the asset
 		/// - item: item_id of the asset
 		/// - _judgement is provided by the representative while creating a tenant session
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
 		pub fn request_guaranty_payment(
 			origin: OriginFor<T>,
 			from: T::AccountId,
@@ -745,13 +1393,15 @@ pub mod pallet {
 			let creator = ensure_signed(origin.clone())?;
 
 			// Ensure the caller is the virtual account of the asset
-			let asset_account =
-				Share::Pallet::<T>::virtual_acc(collection, item).unwrap().virtual_account;
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
 			ensure!(creator == asset_account, Error::<T>::NotAnAssetAccount);
 
 			//Launch payment request
-			Self::guaranty_payment(origin, from.clone(), collection, item).ok();
-			let payment = Self::guaranty(from.clone(), asset_account).unwrap();
+			Self::guaranty_payment(origin, from.clone(), collection, item)?;
+			let payment =
+				Self::guaranty(from.clone(), asset_account).ok_or(Error::<T>::NoneValue)?;
 			let now = <frame_system::Pallet<T>>::block_number();
 
 			Self::deposit_event(Event::GuarantyPaymentRequested {
@@ -779,8 +1429,9 @@ pub mod pallet {
 			let caller = ensure_signed(origin)?;
 
 			// Ensure the caller is the virtual account of the asset
-			let asset_account =
-				Share::Pallet::<T>::virtual_acc(collection, item).unwrap().virtual_account;
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
 			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
 
 			Self::tenant_unlink_asset(tenant.clone(), collection, item).ok();
@@ -789,5 +1440,699 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// The function below allows a representative to pay a servicer invoice from the
+		/// maintenance reserve of an asset. Invoices above the spend limit trigger a vote
+		/// session of the owners.
+		/// The origin must be the representative of the asset
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - servicer: an account with the servicer role
+		/// - amount: amount of the invoice
+		/// - invoice: hash of the servicer invoice
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4).ref_time())]
+		pub fn pay_maintenance_invoice(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			servicer: T::AccountId,
+			amount: BalanceOf<T>,
+			invoice: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Ensure that the caller is the active representative of the asset
+			let rep = Roles::Pallet::<T>::reps(caller.clone());
+			ensure!(rep.is_some(), Error::<T>::NotARepresentative);
+			ensure!(rep.unwrap().activated, Error::<T>::NotAnActiveRepresentative);
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
+			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			let asset = Onboarding::Pallet::<T>::houses(collection_id, asset_id).unwrap();
+			ensure!(asset.representative == Some(caller.clone()), Error::<T>::AssetOutOfControl);
+
+			// Ensure that the invoice is issued by a servicer
+			ensure!(Roles::Pallet::<T>::servicers(&servicer).is_some(), Error::<T>::NotAServicer);
+
+			let asset_account = ownership.unwrap().virtual_account;
+			ensure!(
+				Self::maintenance_reserve(&asset_account) >= amount,
+				Error::<T>::NotEnoughMaintenanceFunds
+			);
+
+			// Invoices within the spend limit of the period are paid without a vote
+			if Self::record_unvoted_spend(&asset_account, amount).is_ok() {
+				return Self::spend_maintenance_reserve(asset_account, servicer, amount, invoice)
+			}
+
+			let deposit = T::MinimumDeposit::get();
+			<T as Dem::Config>::Currency::transfer(
+				&caller,
+				&asset_account,
+				deposit,
+				ExistenceRequirement::AllowDeath,
+			)
+			.ok();
+
+			let call = Call::<T>::execute_maintenance_spend {
+				servicer: servicer.clone(),
+				collection: collection_id,
+				item: asset_id,
+				amount,
+				invoice,
+			};
+
+			let proposal_hash = Self::create_proposal_hash_and_note(asset_account.clone(), call);
+
+			let threshold = Dem::VoteThreshold::SimpleMajority;
+			let delay = <T as Config>::Delay::get();
+
+			let referendum_index =
+				Dem::Pallet::<T>::internal_start_referendum(proposal_hash, threshold, delay);
+
+			// Create data for proposals Log
+			ProposalRecord::<T>::new(
+				caller.clone(),
+				asset_account.clone(),
+				servicer.clone(),
+				referendum_index,
+				collection_id,
+				asset_id,
+			)
+			.ok();
+
+			Self::deposit_event(Event::MaintenanceSpendVoteSessionStarted {
+				representative: caller,
+				servicer,
+				asset_account,
+				amount,
+			});
+
+			Ok(())
+		}
+
+		/// The function below pays a servicer invoice from the maintenance reserve of an asset.
+		/// This extrinsic is executed after a positive maintenance spend session.
+		/// The origin must be the virtual account connected to the asset
+		/// - servicer: an account with the servicer role
+		/// - collection: collection_id of the asset
+		/// - item: item_id of the asset
+		/// - amount: amount of the invoice
+		/// - invoice: hash of the servicer invoice
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,3).ref_time())]
+		pub fn execute_maintenance_spend(
+			origin: OriginFor<T>,
+			servicer: T::AccountId,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			amount: BalanceOf<T>,
+			invoice: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Ensure the caller is the virtual account of the asset
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
+
+			Self::spend_maintenance_reserve(asset_account, servicer, amount, invoice)
+		}
+
+		/// Using the function below, an owner triggers a vote session with a proposal for an
+		/// action on the asset. Each action type has its own referendum threshold.
+		/// The origin must be an owner holding at least the minimum proposal share
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - action: action to enact if the proposal passes
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4).ref_time())]
+		pub fn launch_owners_proposal(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			action: OwnersAction<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Get asset virtual account if it exists
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
+			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			let ownership = ownership.unwrap();
+
+			// Ensure that the caller holds enough ownership tokens
+			ensure!(ownership.owners.contains(&caller), Error::<T>::NotAnOwner);
+			let supply = Assetss::Pallet::<T>::total_supply(ownership.token_id.into());
+			let tokens = Assetss::Pallet::<T>::balance(ownership.token_id.into(), &caller);
+			let supply0 = Self::assets_bal_to_u128(supply).ok_or(Error::<T>::NoneValue)?;
+			let tokens0 = Self::assets_bal_to_u128(tokens).ok_or(Error::<T>::NoneValue)?;
+			ensure!(
+				tokens0 >= T::MinimumProposalShare::get() * supply0,
+				Error::<T>::NotEnoughShares
+			);
+
+			let virtual_account = ownership.virtual_account;
+
+			// Check the action parameters
+			match &action {
+				OwnersAction::Renovation { servicer, amount, .. } => {
+					ensure!(
+						Roles::Pallet::<T>::servicers(servicer).is_some(),
+						Error::<T>::NotAServicer
+					);
+					ensure!(
+						Self::maintenance_reserve(&virtual_account) >= *amount,
+						Error::<T>::NotEnoughMaintenanceFunds
+					);
+				},
+				OwnersAction::LeaseLength(months) => {
+					ensure!(
+						*months > 0 && *months <= u8::MAX as u32,
+						Error::<T>::InvalidLeaseLength
+					);
+				},
+				OwnersAction::Sell(_) => {
+					ensure!(
+						Self::asset_sales(collection_id, asset_id).is_none(),
+						Error::<T>::AssetListedForSale
+					);
+				},
+				OwnersAction::Insurance { insurer, .. } => {
+					ensure!(
+						Roles::Pallet::<T>::servicers(insurer).is_some(),
+						Error::<T>::NotAServicer
+					);
+				},
+				_ => (),
+			}
+
+			let deposit = T::MinimumDeposit::get();
+			//Ensure that the virtual account has enough funds
+			for f in ownership.owners {
+				<T as Dem::Config>::Currency::transfer(
+					&f,
+					&virtual_account,
+					deposit,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+
+			let threshold = action.threshold();
+			let call = Call::<T>::enact_owners_proposal {
+				collection: collection_id,
+				item: asset_id,
+				action: action.clone(),
+			};
+
+			//Format the call and create the proposal Hash
+			let proposal_hash = Self::create_proposal_hash_and_note(virtual_account.clone(), call);
+
+			let delay = <T as Config>::Delay::get();
+
+			let referendum_index =
+				Dem::Pallet::<T>::internal_start_referendum(proposal_hash, threshold, delay);
+
+			//Create data for proposals Log
+			ProposalRecord::<T>::new(
+				caller.clone(),
+				virtual_account.clone(),
+				virtual_account.clone(),
+				referendum_index,
+				collection_id,
+				asset_id,
+			)
+			.ok();
+			OwnersProposals::<T>::insert(referendum_index, action.clone());
+
+			//Emit Event
+			Self::deposit_event(Event::OwnersProposalStarted {
+				caller,
+				asset_account: virtual_account,
+				session_number: referendum_index,
+				action,
+			});
+
+			Ok(())
+		}
+
+		/// The function below enacts the action of an owners proposal.
+		/// This extrinsic is executed after a positive owners proposal session.
+		/// The origin must be the virtual account connected to the asset
+		/// - collection: collection_id of the asset
+		/// - item: item_id of the asset
+		/// - action: action of the proposal
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3).ref_time())]
+		pub fn enact_owners_proposal(
+			origin: OriginFor<T>,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			action: OwnersAction<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin.clone())?;
+
+			// Ensure the caller is the virtual account of the asset
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			match action.clone() {
+				OwnersAction::RentChange(rent) => AssetRents::<T>::insert(collection, item, rent),
+				OwnersAction::Renovation { servicer, amount, invoice } => {
+					Self::spend_maintenance_reserve(
+						asset_account.clone(),
+						servicer,
+						amount,
+						invoice,
+					)?
+				},
+				OwnersAction::LeaseLength(months) =>
+					AssetLeases::<T>::insert(collection, item, months),
+				OwnersAction::Insurance { insurer, premium, coverage } => {
+					//The insurer must still be an approved servicer
+					ensure!(
+						Roles::Pallet::<T>::servicers(&insurer).is_some(),
+						Error::<T>::NotAServicer
+					);
+					<T as Config>::Currency::transfer(
+						&asset_account,
+						&insurer,
+						premium,
+						ExistenceRequirement::KeepAlive,
+					)?;
+					let policy = InsurancePolicy::<T> {
+						insurer,
+						premium,
+						start: now,
+						end: now.saturating_add(coverage),
+					};
+					Insurances::<T>::insert(collection, item, policy);
+				},
+				OwnersAction::Sell(price) =>
+					Self::list_asset_for_sale(origin, collection, item, price)?,
+				OwnersAction::RentToOwn(share) => {
+					if share.deconstruct() > 0 {
+						RentToOwnShares::<T>::insert(collection, item, share)
+					} else {
+						RentToOwnShares::<T>::remove(collection, item)
+					}
+				},
+			}
+
+			Self::deposit_event(Event::OwnersProposalEnacted { asset_account, action, when: now });
+
+			Ok(())
+		}
+
+		/// The function below lists an asset for sale, and gives notice to its tenants.
+		/// This extrinsic is executed after a positive sale session.
+		/// The origin must be the virtual account connected to the asset
+		/// - collection: collection_id of the asset
+		/// - item: item_id of the asset
+		/// - price: asking price of the asset
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn list_asset_for_sale(
+			origin: OriginFor<T>,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Ensure the caller is the virtual account of the asset
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection, item)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
+			ensure!(Self::asset_sales(collection, item).is_none(), Error::<T>::AssetListedForSale);
+
+			let house =
+				Onboarding::Pallet::<T>::houses(collection, item).ok_or(Error::<T>::NotAnAsset)?;
+			let sale =
+				SaleRecord::<T>::new(collection, item, asset_account.clone(), price, house.tenants);
+
+			//Give notice to the tenants
+			for tenant in sale.tenants {
+				Self::deposit_event(Event::TenantNoticeGiven {
+					tenant,
+					asset_account: asset_account.clone(),
+					notice_end: sale.notice_end,
+				});
+			}
+
+			Self::deposit_event(Event::AssetListedForSale {
+				collection,
+				item,
+				asset_account,
+				price,
+				when: sale.listed_at,
+			});
+
+			Ok(())
+		}
+
+		/// The function below allows a buyer to purchase a listed asset, once the tenants notice
+		/// is over. The proceeds are shared between the owners according to their ownership
+		/// tokens, and the ownership tokens are burned.
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(10,10).ref_time())]
+		pub fn buy_asset(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+
+			let sale = Self::asset_sales(collection_id, asset_id)
+				.ok_or(Error::<T>::AssetNotListedForSale)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= sale.notice_end, Error::<T>::NoticePeriodNotOver);
+			let asset_account = sale.virtual_account;
+
+			//The buyer pays the asking price to the virtual account
+			<T as Config>::Currency::transfer(
+				&buyer,
+				&asset_account,
+				sale.price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			//Pay the dividends still held by the virtual account, what could not be paid and
+			//the maintenance reserve being shared with the proceeds
+			let left = Share::Pallet::<T>::settle_dividends(asset_account.clone())?;
+			let left0 = Self::share_bal_to_u128(left).ok_or(Error::<T>::NoneValue)?;
+			let reserve = Self::release_maintenance_reserve(asset_account.clone());
+			let proceeds = sale
+				.price
+				.saturating_add(reserve)
+				.saturating_add(BalanceType::<T>::convert_to_balance(left0).manage_bal);
+
+			//Share the proceeds between the owners
+			let owners = Self::distribute_sale_proceeds(asset_account.clone(), proceeds)?;
+
+			//Free the asset from its tenants and representative
+			Self::release_sold_asset(collection_id, asset_id, asset_account)?;
+
+			//Transfer the asset to the buyer
+			let origin_root: OriginFor<T> = frame_system::RawOrigin::Root.into();
+			Nft::Pallet::<T>::transfer(
+				origin_root,
+				asset_type,
+				asset_id,
+				T::Lookup::unlookup(buyer.clone()),
+			)?;
+
+			//Burn the ownership tokens and close the virtual account records
+			Share::Pallet::<T>::close_virtual(collection_id, asset_id)?;
+			AssetSales::<T>::remove(collection_id, asset_id);
+
+			Self::deposit_event(Event::SaleProceedsDistributed {
+				owners,
+				amount: proceeds,
+				when: now,
+			});
+			Self::deposit_event(Event::AssetSold {
+				buyer,
+				collection: collection_id,
+				item: asset_id,
+				price: sale.price,
+				when: now,
+			});
+
+			Ok(())
+		}
+
+		/// Designate the appraiser of an asset, or remove it.
+		/// The origin must be root, through a governance decision
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - appraiser: account of the appraiser
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		pub fn set_appraiser(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			appraiser: Option<T::AccountId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			ensure!(
+				Share::Pallet::<T>::virtual_acc(collection_id, asset_id).is_some(),
+				Error::<T>::NotAnAsset
+			);
+
+			match appraiser.clone() {
+				Some(account) => Appraisers::<T>::insert(collection_id, asset_id, account),
+				None => Appraisers::<T>::remove(collection_id, asset_id),
+			}
+
+			Self::deposit_event(Event::AppraiserSet {
+				collection: collection_id,
+				item: asset_id,
+				appraiser,
+			});
+
+			Ok(())
+		}
+
+		/// The representative of an asset sets the share of a co-tenant in the rent and in the
+		/// guaranty deposit of the asset, before the tenant vote session.
+		/// The origin must be the representative of the asset
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - tenant: an account with the tenant role, not yet linked to an asset
+		/// - share: share of the rent and guaranty payed by the tenant
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1).ref_time())]
+		pub fn set_tenant_share(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			tenant: T::AccountId,
+			share: Percent,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			let house = Onboarding::Pallet::<T>::houses(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?;
+			ensure!(house.representative == Some(caller), Error::<T>::AssetOutOfControl);
+
+			// Ensure that provided account is a tenant, not linked to an asset
+			let tenant0 = Roles::Pallet::<T>::tenants(&tenant).ok_or(Error::<T>::NotATenant)?;
+			ensure!(tenant0.asset_account.is_none(), Error::<T>::TenantAlreadyLinkedWithAsset);
+
+			// Ensure that the linked tenants and the new co-tenant share at most the whole rent
+			ensure!(share.deconstruct() > 0, Error::<T>::RentShareExceeded);
+			let shared = house.tenants.iter().fold(0u32, |total, t| {
+				total.saturating_add(Self::rent_share(t, &asset_account).deconstruct() as u32)
+			});
+			ensure!(
+				shared.saturating_add(share.deconstruct() as u32) <= 100,
+				Error::<T>::RentShareExceeded
+			);
+
+			TenantShares::<T>::insert(&tenant, (asset_account.clone(), share));
+
+			Self::deposit_event(Event::TenantShareSet { tenant, asset_account, share });
+
+			Ok(())
+		}
+
+		/// An owner of an asset consents to sell his ownership tokens to the rent-to-own tenants
+		/// of the asset, at the net asset value per token, or withdraws his consent.
+		/// The origin must be an owner of the asset
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - consent: the owner sells his tokens to the tenants
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn consent_rent_to_own(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			consent: bool,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?;
+			ensure!(ownership.owners.contains(&caller), Error::<T>::NotAnOwner);
+			let asset_account = ownership.virtual_account;
+
+			RentToOwnSellers::<T>::mutate(&asset_account, |sellers| {
+				sellers.retain(|seller| *seller != caller);
+				if consent {
+					sellers.push(caller.clone());
+				}
+			});
+
+			Self::deposit_event(Event::RentToOwnConsentSet {
+				owner: caller,
+				asset_account,
+				consent,
+			});
+
+			Ok(())
+		}
+
+		/// Using the function below, a notary or the appraiser of an asset submits a new
+		/// valuation of the asset, and triggers a vote session of the owners.
+		/// The origin must be a notary or the appraiser of the asset
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - value: new value of the asset
+		/// - report: hash of the valuation report
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,4).ref_time())]
+		pub fn submit_revaluation(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			value: BalanceOf<T>,
+			report: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
+			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			let asset_account = ownership.unwrap().virtual_account;
+
+			// Ensure that the caller is a notary or the appraiser of the asset
+			ensure!(
+				Roles::Pallet::<T>::notaries(&caller).is_some() ||
+					Self::appraisers(collection_id, asset_id) == Some(caller.clone()),
+				Error::<T>::NotAnAppraiser
+			);
+			ensure!(!value.is_zero(), Error::<T>::InvalidValuation);
+
+			// A rejected revaluation can be replaced
+			if let Some((index, _)) = Self::pending_revaluations(collection_id, asset_id) {
+				let rejected = Self::proposals(index)
+					.map(|proposal| proposal.vote_result == VoteResult::REJECTED)
+					.unwrap_or(true);
+				ensure!(rejected, Error::<T>::RevaluationPending);
+			}
+
+			// Ensure that the previous valuation is old enough
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some(last) = Self::value_history(collection_id, asset_id).last() {
+				let approved_at = last.approved_at.unwrap_or(last.submitted_at);
+				ensure!(
+					now >= approved_at.saturating_add(T::RevaluationPeriod::get()),
+					Error::<T>::RevaluationNotDue
+				);
+			}
+
+			let deposit = T::MinimumDeposit::get();
+			<T as Dem::Config>::Currency::transfer(
+				&caller,
+				&asset_account,
+				deposit,
+				ExistenceRequirement::AllowDeath,
+			)
+			.ok();
+
+			let call = Call::<T>::approve_revaluation { collection: collection_id, item: asset_id };
+			let proposal_hash = Self::create_proposal_hash_and_note(asset_account.clone(), call);
+
+			let threshold = Dem::VoteThreshold::SimpleMajority;
+			let delay = <T as Config>::Delay::get();
+			let referendum_index =
+				Dem::Pallet::<T>::internal_start_referendum(proposal_hash, threshold, delay);
+
+			// Create data for proposals Log
+			ProposalRecord::<T>::new(
+				caller.clone(),
+				asset_account.clone(),
+				caller.clone(),
+				referendum_index,
+				collection_id,
+				asset_id,
+			)
+			.ok();
+
+			let revaluation = Revaluation::<T>::new(caller.clone(), value, report);
+			PendingRevaluations::<T>::insert(
+				collection_id,
+				asset_id,
+				(referendum_index, revaluation),
+			);
+
+			Self::deposit_event(Event::RevaluationSubmitted {
+				appraiser: caller,
+				asset_account,
+				value,
+				session_number: referendum_index,
+			});
+
+			Ok(())
+		}
+
+		/// The function below approves the pending revaluation of an asset.
+		/// The valuation is added to the value history of the asset, and the net asset value
+		/// per ownership token is used as the buyback price of the ownership tokens.
+		/// The origin must be the virtual account connected to the asset, after a positive vote
+		/// of the owners, or root through a governance decision
+		/// - collection: collection_id of the asset
+		/// - item: item_id of the asset
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,4).ref_time())]
+		pub fn approve_revaluation(
+			origin: OriginFor<T>,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+		) -> DispatchResult {
+			let ownership = Share::Pallet::<T>::virtual_acc(collection, item);
+			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
+			let asset_account = ownership.unwrap().virtual_account;
+			if ensure_root(origin.clone()).is_err() {
+				// Ensure the caller is the virtual account of the asset
+				let caller = ensure_signed(origin)?;
+				ensure!(caller == asset_account, Error::<T>::NotAnAssetAccount);
+			}
+
+			let (_, mut revaluation) = Self::pending_revaluations(collection, item)
+				.ok_or(Error::<T>::NoPendingRevaluation)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			revaluation.approved_at = Some(now);
+			let value = revaluation.value;
+			ValueHistory::<T>::mutate(collection, item, |val| {
+				val.push(revaluation);
+			});
+			PendingRevaluations::<T>::remove(collection, item);
+
+			// The net asset value per token becomes the buyback price of the tokens
+			let nav_per_token = Self::nav_per_token(collection, item)?;
+			let bals = BalanceType::<T>::convert_to_balance(nav_per_token);
+			let origin_root: OriginFor<T> = frame_system::RawOrigin::Root.into();
+			Share::Pallet::<T>::set_valuation(origin_root, collection, item, bals.hfund_bal)?;
+
+			Self::deposit_event(Event::RevaluationApproved {
+				asset_account,
+				value,
+				nav_per_token: bals.manage_bal,
+				when: now,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Share::NetAssetValue<T::NftCollectionId, T::NftItemId> for Pallet<T> {
+	fn nav_per_token(collection: T::NftCollectionId, item: T::NftItemId) -> Option<u128> {
+		Self::nav_per_token(collection, item).ok()
+	}
+}
+
+impl<T: Config> Payment::OnPaymentSettled<T> for Pallet<T> {
+	fn on_payment_settled(
+		payment_id: Payment::PaymentId,
+		payment: &Payment::PaymentDetail<T>,
+		recipient_share: Percent,
+	) {
+		Self::refund_maintenance_escrow(payment_id, payment, recipient_share)
 	}
 }
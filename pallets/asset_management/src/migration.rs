@@ -0,0 +1,57 @@
+//! Storage migrations of the asset_management pallet
+pub use super::*;
+
+/// Storage layout before payment ids, where the guaranty payment details were copied here
+pub mod v0 {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub type GuarantyPayment<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Payment::migration::v0::OldPaymentDetail<T>,
+	>;
+}
+
+/// Replace the guaranty payment details by the id given to the payment by the payment
+/// migration. Guaranties accepted by the tenant are completed in `pallet_payment`, as
+/// `accept_and_pay` left them requested.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return T::DbWeight::get().reads(1)
+	}
+
+	//The payments must have their ids before the guaranties are mapped to them
+	let weight = Payment::migration::migrate_to_v1::<T>();
+
+	//The old guaranties are collected before writing, both layouts share the same prefix
+	let old_guaranties: Vec<_> = v0::GuarantyPayment::<T>::drain().collect();
+	let mut reads = old_guaranties.len() as u64;
+	let mut writes = old_guaranties.len() as u64;
+
+	for (tenant, asset_account, old) in old_guaranties {
+		//There was a single payment between a tenant and an asset account
+		let payment_id = Payment::Pallet::<T>::payments_between(&tenant, &asset_account)
+			.into_iter()
+			.next();
+		reads = reads.saturating_add(1);
+
+		if let Some(payment_id) = payment_id {
+			if old.state == Payment::PaymentState::PaymentCompleted {
+				Payment::Pallet::<T>::complete_payment_request(payment_id).ok();
+				writes = writes.saturating_add(1);
+			}
+			Pallet::<T>::insert_guaranty(&tenant, &asset_account, payment_id);
+			writes = writes.saturating_add(2);
+		}
+	}
+
+	STORAGE_VERSION.put::<Pallet<T>>();
+
+	weight.saturating_add(
+		T::DbWeight::get().reads_writes(reads.saturating_add(1), writes.saturating_add(1)),
+	)
+}
@@ -135,6 +135,7 @@ impl pallet_payment::Config for Test {
 	type DisputeResolver = MockDisputeResolver;
 	type IncentivePercentage = IncentivePercentage;
 	type FeeHandler = MockFeeHandler;
+	type OnPaymentSettled = ();
 	type MaxRemarkLength = MaxRemarkLength;
 	type CancelBufferBlockLength = CancelBufferBlockLength;
 	type MaxScheduledTaskListLength = MaxScheduledTaskListLength;
@@ -370,19 +371,28 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributor;
 	type Extra = ();
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const AssetsFees: Balance = 15000;
+	pub const MarketFee: Percent = Percent::from_percent(2);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(50);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxOwners: u32 = 50;
 }
 impl pallet_share_distributor::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = AssetManagement;
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
@@ -413,6 +423,19 @@ parameter_types! {
 	pub const ContractLength: BlockNumber = 365;
 	pub const Lease: u32 = 12;
 	pub const Maintenance:Percent = Percent::from_percent(3);
+	pub const NoticePeriod: BlockNumber = 10;
+	pub const MaintenanceSpendLimit: u64 = 100;
+	pub const MaintenanceSpendPeriod: BlockNumber = 20;
+	pub static RepresentativeFee: pallet_asset_management::ManagementFee<u64> =
+		pallet_asset_management::ManagementFee::Percent(Percent::from_percent(5));
+	pub const RepresentativeTerm: BlockNumber = 50;
+	pub const ReelectionFallback: pallet_asset_management::ReelectionFallback<BlockNumber> =
+		pallet_asset_management::ReelectionFallback::KeepIncumbent(10);
+	pub const MinimumProposalShare: Percent = Percent::from_percent(10);
+	pub const RevaluationPeriod: BlockNumber = 20;
+	pub const MaxQueuedTasks: u32 = 100;
+	pub const MaxScannedItems: u32 = 100;
+	pub const MaxIncumbentExtensions: u32 = 1;
 }
 
 impl pallet_asset_management::Config for Test {
@@ -431,6 +454,17 @@ impl pallet_asset_management::Config for Test {
 	type RoR = RoR;
 	type Lease = Lease;
 	type Maintenance = Maintenance;
+	type NoticePeriod = NoticePeriod;
+	type MaintenanceSpendLimit = MaintenanceSpendLimit;
+	type MaintenanceSpendPeriod = MaintenanceSpendPeriod;
+	type ManagementFee = RepresentativeFee;
+	type RepresentativeTerm = RepresentativeTerm;
+	type ReelectionFallback = ReelectionFallback;
+	type MinimumProposalShare = MinimumProposalShare;
+	type RevaluationPeriod = RevaluationPeriod;
+	type MaxQueuedTasks = MaxQueuedTasks;
+	type MaxScannedItems = MaxScannedItems;
+	type MaxIncumbentExtensions = MaxIncumbentExtensions;
 	type WeightInfo = ();
 }
 
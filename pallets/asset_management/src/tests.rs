@@ -1,4 +1,5 @@
 pub use super::*;
+use frame_support::traits::{GetStorageVersion, StorageVersion};
 pub use frame_support::{assert_err, assert_ok};
 use frame_system::pallet_prelude::OriginFor;
 use mock::*;
@@ -23,6 +24,12 @@ fn next_block() {
 	Scheduler::on_initialize(System::block_number());
 	Democracy::on_initialize(System::block_number());
 	AssetManagement::begin_block(System::block_number());
+	AssetManagement::finish_block(System::block_number(), Weight::from_ref_time(u64::MAX));
+}
+
+fn run_tasks() {
+	AssetManagement::begin_block(System::block_number());
+	AssetManagement::finish_block(System::block_number(), Weight::from_ref_time(u64::MAX));
 }
 
 fn fast_forward_to(n: u64) {
@@ -686,3 +693,1051 @@ fn test_integration_test() {
 		assert!(asset.unwrap().representative.is_none());
 	});
 }
+
+pub fn prep_virtual_asset() -> (u32, u32, <Test as frame_system::Config>::AccountId) {
+	let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+	let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+	let metadata2 = b"metadata2".to_vec().try_into().unwrap();
+	//put some funds in FairSquare SlashFees account
+	let fees_account = OnboardingModule::account_id();
+	<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+
+	let price1 = 40_000;
+	prep_test(price1, 30_000, metadata0, metadata1, metadata2);
+	let coll_id0 = NftColl::OFFICESTEST.value();
+	let item_id0 = pallet_nft::ItemsCount::<Test>::get()[coll_id0 as usize] - 1;
+	let origin: OriginFor<Test> = frame_system::RawOrigin::Root.into();
+
+	OnboardingModule::change_status(
+		Origin::signed(BOB),
+		NftColl::OFFICESTEST,
+		item_id0,
+		Onboarding::AssetStatus::FINALISED,
+	)
+	.ok();
+
+	//Create the virtual account, and distribute the ownership tokens to EVE and DAVE
+	assert_ok!(ShareDistributor::virtual_account(coll_id0, item_id0));
+	let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().virtual_account;
+	assert_ok!(ShareDistributor::nft_transaction(coll_id0, item_id0, virtual0.clone()));
+	let fund_op = HFund::FundOperation {
+		nft_collection_id: coll_id0,
+		nft_item_id: item_id0,
+		amount: price1,
+		block_number: 1,
+		contributions: vec![(EVE, 25_000), (DAVE, 15_000)],
+	};
+	HFund::Reservations::<Test>::insert((coll_id0, item_id0), fund_op);
+	assert_ok!(ShareDistributor::create_tokens(origin, coll_id0, item_id0, virtual0.clone()));
+	assert_ok!(ShareDistributor::distribute_tokens(virtual0.clone(), coll_id0, item_id0));
+
+	(coll_id0, item_id0, virtual0)
+}
+
+#[test]
+fn sale_proposal_should_sell_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		let token_id = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().token_id;
+		assert_eq!(Assets::balance(token_id, EVE), 625);
+		assert_eq!(Assets::balance(token_id, DAVE), 375);
+
+		//GERARD rents the asset
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+
+		//Only owners can propose a sale
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(BOB),
+				NftColl::OFFICESTEST,
+				item_id0,
+				OwnersAction::Sell(60_000)
+			),
+			Error::<Test>::NotAnOwner
+		);
+
+		//EVE proposes to sell the asset, and the owners approve
+		assert_ok!(AssetManagement::launch_owners_proposal(
+			Origin::signed(EVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			OwnersAction::Sell(60_000)
+		));
+		assert_ok!(AssetManagement::owners_vote(Origin::signed(EVE), 0, true));
+		assert_ok!(AssetManagement::owners_vote(Origin::signed(DAVE), 0, true));
+
+		let end_block_number = System::block_number()
+			.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get());
+		fast_forward_to(end_block_number.saturating_add(<Test as crate::Config>::Delay::get()));
+
+		//The asset is listed and GERARD was given notice
+		let sale = AssetManagement::asset_sales(coll_id0, item_id0).unwrap();
+		assert_eq!(sale.price, 60_000);
+		assert_eq!(sale.tenants, vec![GERARD]);
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(DAVE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				OwnersAction::Sell(50_000)
+			),
+			Error::<Test>::AssetListedForSale
+		);
+
+		//The asset cannot be bought before the end of the notice
+		assert_err!(
+			AssetManagement::buy_asset(Origin::signed(CHARLIE), NftColl::OFFICESTEST, item_id0),
+			Error::<Test>::NoticePeriodNotOver
+		);
+		fast_forward_to(sale.notice_end);
+
+		let eve_balance = Balances::free_balance(EVE);
+		let dave_balance = Balances::free_balance(DAVE);
+		assert_ok!(AssetManagement::buy_asset(
+			Origin::signed(CHARLIE),
+			NftColl::OFFICESTEST,
+			item_id0
+		));
+
+		//The proceeds were shared according to the ownership tokens
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 37_500);
+		assert_eq!(Balances::free_balance(DAVE), dave_balance + 22_500);
+
+		//The asset belongs to the buyer, and the tenant was released
+		assert_eq!(pallet_nft::Pallet::<Test>::owner(coll_id0, item_id0), Some(CHARLIE));
+		assert!(RoleModule::tenants(GERARD).unwrap().asset_account.is_none());
+		assert!(OnboardingModule::houses(coll_id0, item_id0).unwrap().tenants.is_empty());
+
+		//The ownership tokens were burned and the virtual account closed
+		assert_eq!(Assets::total_supply(token_id), 0);
+		assert!(ShareDistributor::virtual_acc(coll_id0, item_id0).is_none());
+		assert!(ShareDistributor::tokens_infos(virtual0).is_none());
+		assert!(AssetManagement::asset_sales(coll_id0, item_id0).is_none());
+	});
+}
+
+#[test]
+fn sold_asset_should_pay_reserve_and_dividends_to_owners() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 10_000u32.into());
+
+		//The virtual account holds a maintenance reserve, a pending rent,
+		//unclaimed dividends and a rent remainder
+		assert_ok!(<Test as pallet::Config>::Currency::reserve(&virtual0, 1_000));
+		MaintenanceReserves::<Test>::insert(&virtual0, 1_000);
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 1_000));
+		Share::UnclaimedDividends::<Test>::insert(&virtual0, DAVE, 100);
+		Share::RentRemainder::<Test>::insert(&virtual0, 3);
+
+		//Only the virtual account lists the asset
+		assert_err!(
+			AssetManagement::list_asset_for_sale(Origin::signed(EVE), coll_id0, item_id0, 60_001),
+			Error::<Test>::NotAnAssetAccount
+		);
+		assert_err!(
+			AssetManagement::list_asset_for_sale(
+				Origin::signed(virtual0.clone()),
+				coll_id0,
+				item_id0 + 1,
+				60_001
+			),
+			Error::<Test>::NotAnAsset
+		);
+		assert_ok!(AssetManagement::list_asset_for_sale(
+			Origin::signed(virtual0.clone()),
+			coll_id0,
+			item_id0,
+			60_001
+		));
+		let sale = AssetManagement::asset_sales(coll_id0, item_id0).unwrap();
+		fast_forward_to(sale.notice_end);
+
+		let eve_balance = Balances::free_balance(EVE);
+		let dave_balance = Balances::free_balance(DAVE);
+		assert_ok!(AssetManagement::buy_asset(
+			Origin::signed(CHARLIE),
+			NftColl::OFFICESTEST,
+			item_id0
+		));
+
+		//The pending rent is shared according to the snapshot, the unclaimed dividends are
+		//paid, and the price, the reserve and the remainder are shared without dust
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 625 + 38_128);
+		assert_eq!(Balances::free_balance(DAVE), dave_balance + 375 + 100 + 22_876);
+		assert_eq!(Balances::reserved_balance(&virtual0), 0);
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 0);
+
+		//The rent records of the virtual account were removed
+		assert_eq!(ShareDistributor::unclaimed_dividends(&virtual0, DAVE), 0);
+		assert!(ShareDistributor::pending_rent_periods(virtual0.clone()).is_empty());
+		assert_eq!(ShareDistributor::rent_period_id(&virtual0), 0);
+		assert_eq!(ShareDistributor::rent_remainder(&virtual0), 0);
+	});
+}
+
+#[test]
+fn maintenance_reserve_should_pay_servicer_invoices() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 100_000u32.into());
+
+		//FERDIE is elected as the representative of the asset, and GERARD rents it
+		assert_ok!(AssetManagement::representative_approval(
+			Origin::signed(virtual0.clone()),
+			FERDIE,
+			coll_id0,
+			item_id0
+		));
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+
+		//A rent is received: the maintenance fees are kept in the asset reserve
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 1;
+			*val = Some(val0);
+		});
+		run_tasks();
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 300);
+		assert_eq!(Balances::reserved_balance(&virtual0), 300);
+		let deposit = AssetManagement::maintenance_log(&virtual0, 0).unwrap();
+		assert_eq!(deposit.operation, MaintenanceOperation::Deposit);
+		assert_eq!(deposit.amount, 300);
+
+		let invoice = H256::from_low_u64_be(1);
+		//Invoices must be issued by a servicer
+		assert_err!(
+			AssetManagement::pay_maintenance_invoice(
+				Origin::signed(FERDIE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				HUNTER,
+				80,
+				invoice
+			),
+			Error::<Test>::NotAServicer
+		);
+
+		//Invoices below the spend limit are paid right away
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(AssetManagement::pay_maintenance_invoice(
+			Origin::signed(FERDIE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			CHARLIE,
+			80,
+			invoice
+		));
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 80);
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 220);
+		assert_eq!(Balances::reserved_balance(&virtual0), 220);
+		let spend = AssetManagement::maintenance_log(&virtual0, 1).unwrap();
+		assert_eq!(spend.operation, MaintenanceOperation::Spend);
+		assert_eq!(spend.servicer, Some(CHARLIE));
+		assert_eq!(spend.invoice, Some(invoice));
+
+		//The reserve cannot be overspent
+		assert_err!(
+			AssetManagement::pay_maintenance_invoice(
+				Origin::signed(FERDIE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				CHARLIE,
+				500,
+				invoice
+			),
+			Error::<Test>::NotEnoughMaintenanceFunds
+		);
+
+		//Invoices above the spend limit need a vote of the owners
+		assert_ok!(AssetManagement::pay_maintenance_invoice(
+			Origin::signed(FERDIE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			CHARLIE,
+			150,
+			invoice
+		));
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 220);
+		assert_ok!(AssetManagement::owners_vote(Origin::signed(EVE), 0, true));
+		assert_ok!(AssetManagement::owners_vote(Origin::signed(DAVE), 0, true));
+
+		let end_block_number = System::block_number()
+			.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get());
+		fast_forward_to(end_block_number.saturating_add(<Test as crate::Config>::Delay::get()));
+
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 230);
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 70);
+		assert_eq!(AssetManagement::maintenance_log_id(&virtual0), 3);
+
+		//Voted invoices don't count in the spend limit of the period
+		assert_ok!(AssetManagement::pay_maintenance_invoice(
+			Origin::signed(FERDIE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			CHARLIE,
+			20,
+			invoice
+		));
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 50);
+		assert_eq!(AssetManagement::maintenance_spent(&virtual0, System::block_number()), 100);
+
+		//Invoices taking the spendings of the period above the limit need a vote
+		assert_ok!(AssetManagement::pay_maintenance_invoice(
+			Origin::signed(FERDIE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			CHARLIE,
+			10,
+			invoice
+		));
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 50);
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+		assert_eq!(
+			event,
+			mock::Event::AssetManagement(crate::Event::MaintenanceSpendVoteSessionStarted {
+				representative: FERDIE,
+				servicer: CHARLIE,
+				asset_account: virtual0.clone(),
+				amount: 10,
+			})
+		);
+
+		//The limit is available again in the next period
+		let period = <Test as crate::Config>::MaintenanceSpendPeriod::get();
+		fast_forward_to((System::block_number() / period + 1) * period);
+		assert_eq!(AssetManagement::maintenance_spent(&virtual0, System::block_number()), 0);
+		assert_ok!(AssetManagement::pay_maintenance_invoice(
+			Origin::signed(FERDIE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			CHARLIE,
+			10,
+			invoice
+		));
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 40);
+	});
+}
+
+#[test]
+fn representative_fee_should_be_paid_until_demotion() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 100_000u32.into());
+
+		//FERDIE is elected as the representative of the asset, and GERARD rents it
+		assert_ok!(AssetManagement::representative_approval(
+			Origin::signed(virtual0.clone()),
+			FERDIE,
+			coll_id0,
+			item_id0
+		));
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+		let ferdie_balance = Balances::free_balance(FERDIE);
+
+		//A rent is received: FERDIE earns 5% of it
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 1;
+			*val = Some(val0);
+		});
+		run_tasks();
+		assert_eq!(Balances::free_balance(FERDIE), ferdie_balance + 500);
+		assert_eq!(AssetManagement::representative_earnings(FERDIE, &virtual0), 500);
+
+		//FERDIE is demoted, and does not earn fees anymore
+		assert_ok!(AssetManagement::demote_representative(
+			Origin::signed(virtual0.clone()),
+			FERDIE,
+			coll_id0,
+			item_id0
+		));
+		System::set_block_number(System::block_number() + 1);
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 1;
+			*val = Some(val0);
+		});
+		run_tasks();
+		assert_eq!(Balances::free_balance(FERDIE), ferdie_balance + 500);
+		assert_eq!(AssetManagement::representative_earnings(FERDIE, &virtual0), 500);
+	});
+}
+
+#[test]
+fn flat_representative_fee_should_be_paid_once_per_month() {
+	ExtBuilder::default().build().execute_with(|| {
+		RepresentativeFee::set(ManagementFee::Flat(50));
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 100_000u32.into());
+
+		assert_ok!(AssetManagement::representative_approval(
+			Origin::signed(virtual0.clone()),
+			FERDIE,
+			coll_id0,
+			item_id0
+		));
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+		let ferdie_balance = Balances::free_balance(FERDIE);
+
+		//Two rents are received in the same month: the flat fee is paid once
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 2;
+			*val = Some(val0);
+		});
+		run_tasks();
+		assert_eq!(Balances::free_balance(FERDIE), ferdie_balance + 50);
+		assert_eq!(AssetManagement::representative_earnings(FERDIE, &virtual0), 50);
+		assert_eq!(AssetManagement::flat_fee_payments(&virtual0), Some(System::block_number()));
+
+		//The fee is paid again the next month
+		let month = <Test as crate::Config>::ContractLength::get() /
+			<Test as crate::Config>::Lease::get() as u64;
+		System::set_block_number(System::block_number() + month);
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 1;
+			*val = Some(val0);
+		});
+		run_tasks();
+		assert_eq!(Balances::free_balance(FERDIE), ferdie_balance + 100);
+		assert_eq!(AssetManagement::representative_earnings(FERDIE, &virtual0), 100);
+	});
+}
+
+#[test]
+fn representative_term_should_trigger_reelection() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		let candidate = sp_core::crypto::AccountId32::new([11u8; 32]);
+		<Test as pallet::Config>::Currency::make_free_balance_be(&candidate, 150_000u32.into());
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(candidate.clone()),
+			candidate.clone(),
+			Acc::REPRESENTATIVE
+		));
+
+		//FERDIE is elected as the representative of the asset
+		assert_ok!(AssetManagement::representative_approval(
+			Origin::signed(virtual0.clone()),
+			FERDIE,
+			coll_id0,
+			item_id0
+		));
+		let term_end = AssetManagement::representative_terms(coll_id0, item_id0).unwrap();
+		assert_eq!(term_end, 1 + <Test as crate::Config>::RepresentativeTerm::get());
+
+		//The re-election starts at the end of the term
+		fast_forward_to(term_end);
+		let reelection = AssetManagement::reelections(coll_id0, item_id0).unwrap();
+		assert_eq!(reelection.candidates, vec![FERDIE]);
+		assert_err!(
+			AssetManagement::submit_candidacy(Origin::signed(DAVE), NftColl::OFFICESTEST, item_id0),
+			Error::<Test>::NotAPendingRepresentative
+		);
+		assert_ok!(AssetManagement::submit_candidacy(
+			Origin::signed(candidate.clone()),
+			NftColl::OFFICESTEST,
+			item_id0
+		));
+
+		//The owners vote, weighted by their ownership tokens
+		assert_err!(
+			AssetManagement::reelection_vote(
+				Origin::signed(BOB),
+				NftColl::OFFICESTEST,
+				item_id0,
+				FERDIE
+			),
+			Error::<Test>::NotAnOwner
+		);
+		assert_ok!(AssetManagement::reelection_vote(
+			Origin::signed(EVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			candidate.clone()
+		));
+		assert_ok!(AssetManagement::reelection_vote(
+			Origin::signed(DAVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			FERDIE
+		));
+
+		//EVE holds the majority of the tokens: the new candidate replaces FERDIE
+		fast_forward_to(reelection.end);
+		assert!(AssetManagement::reelections(coll_id0, item_id0).is_none());
+		let asset = OnboardingModule::houses(coll_id0, item_id0).unwrap();
+		assert_eq!(asset.representative, Some(candidate.clone()));
+		assert!(!Roles::AccountsRolesLog::<Test>::contains_key(FERDIE));
+		let term_end = reelection.end + <Test as crate::Config>::RepresentativeTerm::get();
+		assert_eq!(AssetManagement::representative_terms(coll_id0, item_id0), Some(term_end));
+
+		//Without votes, the fallback rule keeps the incumbent for a short period
+		fast_forward_to(term_end);
+		let reelection = AssetManagement::reelections(coll_id0, item_id0).unwrap();
+		fast_forward_to(reelection.end);
+		let asset = OnboardingModule::houses(coll_id0, item_id0).unwrap();
+		assert_eq!(asset.representative, Some(candidate.clone()));
+		assert_eq!(
+			AssetManagement::representative_terms(coll_id0, item_id0),
+			Some(reelection.end + 10)
+		);
+		assert_eq!(AssetManagement::incumbent_extensions(coll_id0, item_id0), 1);
+
+		//The incumbent cannot be kept again: the management is suspended
+		fast_forward_to(reelection.end + 10);
+		let reelection = AssetManagement::reelections(coll_id0, item_id0).unwrap();
+		fast_forward_to(reelection.end);
+		let asset = OnboardingModule::houses(coll_id0, item_id0).unwrap();
+		assert_eq!(asset.representative, None);
+		assert!(AssetManagement::representative_terms(coll_id0, item_id0).is_none());
+		assert_eq!(AssetManagement::incumbent_extensions(coll_id0, item_id0), 0);
+		System::assert_has_event(mock::Event::AssetManagement(crate::Event::ManagementSuspended {
+			representative: candidate,
+			asset_account: virtual0,
+		}));
+	});
+}
+
+fn approve_referendum(ref_index: u32, voters: Vec<<Test as frame_system::Config>::AccountId>) {
+	for voter in voters {
+		assert_ok!(AssetManagement::owners_vote(Origin::signed(voter), ref_index, true));
+	}
+	let end_block_number = System::block_number()
+		.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get());
+	fast_forward_to(end_block_number.saturating_add(<Test as crate::Config>::Delay::get()));
+}
+
+#[test]
+fn owners_proposals_should_enact_actions() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 100_000u32.into());
+
+		//Proposals need an owner holding at least 10% of the ownership tokens
+		let rent_change = OwnersAction::<Test>::RentChange(500);
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(BOB),
+				NftColl::OFFICESTEST,
+				item_id0,
+				rent_change.clone()
+			),
+			Error::<Test>::NotAnOwner
+		);
+		assert_ok!(ShareDistributor::transfer_tokens(
+			Origin::signed(DAVE),
+			coll_id0,
+			item_id0,
+			EVE,
+			300
+		));
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(DAVE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				rent_change.clone()
+			),
+			Error::<Test>::NotEnoughShares
+		);
+
+		//The owners change the rent of the asset
+		assert_ok!(AssetManagement::launch_owners_proposal(
+			Origin::signed(EVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			rent_change.clone()
+		));
+		assert_eq!(AssetManagement::owners_proposals(0), Some(rent_change));
+		approve_referendum(0, vec![EVE]);
+		assert_eq!(AssetManagement::asset_rents(coll_id0, item_id0), Some(500));
+
+		//The owners change the lease length of the asset
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(EVE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				OwnersAction::LeaseLength(0)
+			),
+			Error::<Test>::InvalidLeaseLength
+		);
+		assert_ok!(AssetManagement::launch_owners_proposal(
+			Origin::signed(EVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			OwnersAction::LeaseLength(6)
+		));
+		approve_referendum(1, vec![EVE]);
+		assert_eq!(AssetManagement::asset_leases(coll_id0, item_id0), Some(6));
+
+		//New tenants get the rent and lease length chosen by the owners
+		assert_eq!(AssetManagement::calculate_guaranty(coll_id0, item_id0), Ok(1_500));
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+		let tenant = RoleModule::tenants(GERARD).unwrap();
+		assert_eq!(tenant.rent, 500);
+		assert_eq!(tenant.remaining_payments, 6);
+		assert_eq!(tenant.remaining_rent, 3_000);
+		assert_eq!(AssetManagement::tenant_leases(GERARD), Some(6));
+
+		//The owners buy an insurance for the asset, from an approved servicer
+		assert_err!(
+			AssetManagement::launch_owners_proposal(
+				Origin::signed(EVE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				OwnersAction::Insurance { insurer: BOB, premium: 1_000, coverage: 100 }
+			),
+			Error::<Test>::NotAServicer
+		);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(AssetManagement::launch_owners_proposal(
+			Origin::signed(EVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			OwnersAction::Insurance { insurer: CHARLIE, premium: 1_000, coverage: 100 }
+		));
+		approve_referendum(2, vec![EVE, DAVE]);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 1_000);
+		let policy = AssetManagement::insurances(coll_id0, item_id0).unwrap();
+		assert_eq!(policy.insurer, CHARLIE);
+		assert_eq!(policy.end, policy.start + 100);
+	});
+}
+
+#[test]
+fn rent_and_guaranty_should_be_rounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+		let metadata2 = b"metadata2".to_vec().try_into().unwrap();
+		prep_test(40_200, 40_100, metadata0, metadata1, metadata2);
+		let coll_id0 = NftColl::OFFICESTEST.value();
+		let item_id0 = pallet_nft::ItemsCount::<Test>::get()[coll_id0 as usize] - 1;
+		let coll_id1 = NftColl::APPARTMENTSTEST.value();
+		let item_id1 = pallet_nft::ItemsCount::<Test>::get()[coll_id1 as usize] - 1;
+
+		//3% of 40_200 shared between 12 months is 100.5, rounded up
+		assert_eq!(AssetManagement::asset_rent(coll_id0, item_id0), Ok(101));
+		assert_eq!(AssetManagement::calculate_guaranty(coll_id0, item_id0), Ok(303));
+
+		//3% of 40_100 shared between 12 months is 100.25, rounded down
+		assert_eq!(AssetManagement::asset_rent(coll_id1, item_id1), Ok(100));
+		assert_eq!(AssetManagement::calculate_guaranty(coll_id1, item_id1), Ok(300));
+
+		//Assets without a price return an error
+		Onboarding::Houses::<Test>::mutate(coll_id1, item_id1, |house| {
+			let mut house0 = house.clone().unwrap();
+			house0.price = None;
+			*house = Some(house0);
+		});
+		assert_err!(
+			AssetManagement::calculate_guaranty(coll_id1, item_id1),
+			Error::<Test>::AssetPriceMissing
+		);
+		assert_err!(AssetManagement::asset_rent(coll_id1, item_id1 + 1), Error::<Test>::NotAnAsset);
+	});
+}
+
+#[test]
+fn tenant_debt_should_be_computed_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+		let tenant = RoleModule::tenants(GERARD).unwrap();
+		assert_eq!(tenant.rent, 100);
+		let start = tenant.contract_start;
+
+		//1_200 of yearly rent over 365 blocks: 100 blocks are worth 328.77, rounded down
+		System::set_block_number(start + 100);
+		run_tasks();
+		System::assert_has_event(mock::Event::AssetManagement(crate::Event::TenantDebt {
+			tenant: GERARD,
+			debt: 328,
+			when: start + 100,
+		}));
+
+		//A payed monthly rent is removed from the debt
+		Roles::TenantLog::<Test>::mutate(GERARD, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.remaining_payments = 11;
+			*val = Some(val0);
+		});
+		System::set_block_number(start + 101);
+		run_tasks();
+		System::assert_has_event(mock::Event::AssetManagement(crate::Event::TenantDebt {
+			tenant: GERARD,
+			debt: 232,
+			when: start + 101,
+		}));
+	});
+}
+
+#[test]
+fn background_tasks_should_be_executed_by_priority() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&virtual0, 100_000u32.into());
+		assert_ok!(AssetManagement::tenant_link_asset(
+			GERARD,
+			coll_id0,
+			item_id0,
+			virtual0.clone()
+		));
+
+		//A rent is received: the scan queues the distribution and the arrears check
+		assert_ok!(ShareDistributor::record_rent(virtual0.clone(), 10_000));
+		Share::Virtual::<Test>::mutate(coll_id0, item_id0, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent_nbr = 1;
+			*val = Some(val0);
+		});
+		let now = System::block_number();
+		AssetManagement::begin_block(now);
+		//Queued tasks are not duplicated
+		AssetManagement::begin_block(now);
+		assert_eq!(AssetManagement::queue_depth(), 1);
+		assert_eq!(AssetManagement::task_queue(TaskPriority::Scan).into_inner(), vec![Task::Scan]);
+		assert_eq!(AssetManagement::background_scan().unwrap().stage, ScanStage::Proposals);
+
+		//The remaining weight only allows the scan to be executed
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let max_weight = db_weight
+			.reads(1)
+			.saturating_add(db_weight.reads_writes(1, 2))
+			.saturating_add(Task::<Test>::Scan.weight());
+		AssetManagement::finish_block(now, max_weight);
+		assert!(AssetManagement::background_scan().is_none());
+		assert!(AssetManagement::task_queue(TaskPriority::Scan).is_empty());
+		assert_eq!(AssetManagement::queue_depth(), 2);
+		let distribution =
+			Task::<Test>::RentDistribution { tenant: GERARD, asset_account: virtual0.clone() };
+		assert_eq!(
+			AssetManagement::task_queue(TaskPriority::Medium).into_inner(),
+			vec![distribution.clone()]
+		);
+		assert_eq!(
+			AssetManagement::task_queue(TaskPriority::Low).into_inner(),
+			vec![Task::ArrearsCheck(GERARD)]
+		);
+		assert!(AssetManagement::queued_tasks(&distribution).is_some());
+
+		//The remaining weight only allows the distribution to be executed
+		let max_weight = db_weight
+			.reads(1)
+			.saturating_add(db_weight.reads_writes(4, 8))
+			.saturating_add(distribution.weight());
+		AssetManagement::finish_block(now, max_weight);
+		assert_eq!(AssetManagement::queue_depth(), 1);
+		assert!(AssetManagement::task_queue(TaskPriority::Medium).is_empty());
+		assert!(AssetManagement::queued_tasks(&distribution).is_none());
+		assert_eq!(AssetManagement::maintenance_reserve(&virtual0), 300);
+
+		//The arrears check is executed in a later block
+		AssetManagement::finish_block(now + 1, Weight::from_ref_time(u64::MAX));
+		assert_eq!(AssetManagement::queue_depth(), 0);
+		assert!(AssetManagement::task_queue(TaskPriority::Low).is_empty());
+	});
+}
+
+#[test]
+fn background_task_queues_should_be_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let max = <Test as crate::Config>::MaxQueuedTasks::get();
+		for i in 0..max {
+			let tenant = sp_core::crypto::AccountId32::new([(100 + i) as u8; 32]);
+			AssetManagement::enqueue_task(Task::ArrearsCheck(tenant));
+		}
+		assert_eq!(AssetManagement::queue_depth(), max);
+
+		//A full queue doesn't take more tasks
+		AssetManagement::enqueue_task(Task::ArrearsCheck(GERARD));
+		assert_eq!(AssetManagement::queue_depth(), max);
+		assert_eq!(AssetManagement::task_queue(TaskPriority::Low).len() as u32, max);
+		assert!(AssetManagement::queued_tasks(Task::<Test>::ArrearsCheck(GERARD)).is_none());
+
+		//The other priorities have their own queue
+		AssetManagement::enqueue_task(Task::ReferendumCheck(0));
+		assert_eq!(AssetManagement::queue_depth(), max + 1);
+	});
+}
+
+#[test]
+fn revaluation_should_update_value_history_and_nav() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+		let report = H256::from_low_u64_be(1);
+
+		//Without any revaluation, the NAV is derived from the purchase price
+		assert_eq!(AssetManagement::nav_per_token(coll_id0, item_id0), Ok(40));
+
+		//Only notaries and the appraiser of the asset can submit a revaluation
+		assert_err!(
+			AssetManagement::submit_revaluation(
+				Origin::signed(HUNTER),
+				NftColl::OFFICESTEST,
+				item_id0,
+				48_000,
+				report
+			),
+			Error::<Test>::NotAnAppraiser
+		);
+		assert_ok!(AssetManagement::set_appraiser(
+			Origin::root(),
+			NftColl::OFFICESTEST,
+			item_id0,
+			Some(HUNTER)
+		));
+		assert_ok!(AssetManagement::submit_revaluation(
+			Origin::signed(HUNTER),
+			NftColl::OFFICESTEST,
+			item_id0,
+			48_000,
+			report
+		));
+		assert_err!(
+			AssetManagement::submit_revaluation(
+				Origin::signed(HUNTER),
+				NftColl::OFFICESTEST,
+				item_id0,
+				50_000,
+				report
+			),
+			Error::<Test>::RevaluationPending
+		);
+
+		//The owners approve the revaluation
+		approve_referendum(0, vec![EVE, DAVE]);
+		assert!(AssetManagement::pending_revaluations(coll_id0, item_id0).is_none());
+		let history = AssetManagement::value_history(coll_id0, item_id0);
+		assert_eq!(history.len(), 1);
+		assert_eq!(history[0].value, 48_000);
+		assert_eq!(history[0].appraiser, HUNTER);
+		assert_eq!(AssetManagement::nav_per_token(coll_id0, item_id0), Ok(48));
+		assert_eq!(ShareDistributor::valuations(coll_id0, item_id0), Some(48));
+
+		//Outstanding obligations are removed from the NAV
+		Share::UnclaimedDividends::<Test>::insert(&virtual0, EVE, 8_500);
+		assert_eq!(AssetManagement::net_asset_value(coll_id0, item_id0), Ok(39_500));
+		assert_eq!(AssetManagement::nav_per_token(coll_id0, item_id0), Ok(39));
+
+		//Ownership tokens are listed on the secondary market close to their NAV
+		assert_err!(
+			ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 10, 10),
+			Share::Error::<Test>::PriceAwayFromNav
+		);
+		assert_err!(
+			ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 10, 100),
+			Share::Error::<Test>::PriceAwayFromNav
+		);
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 10, 39));
+
+		//The next revaluation is only possible after the revaluation period
+		assert_err!(
+			AssetManagement::submit_revaluation(
+				Origin::signed(HUNTER),
+				NftColl::OFFICESTEST,
+				item_id0,
+				50_000,
+				report
+			),
+			Error::<Test>::RevaluationNotDue
+		);
+
+		//Governance can approve a revaluation without the owners vote
+		let end = System::block_number() + <Test as crate::Config>::RevaluationPeriod::get();
+		fast_forward_to(end);
+		assert_ok!(AssetManagement::submit_revaluation(
+			Origin::signed(HUNTER),
+			NftColl::OFFICESTEST,
+			item_id0,
+			50_000,
+			report
+		));
+		assert_ok!(AssetManagement::approve_revaluation(Origin::root(), coll_id0, item_id0));
+		assert_eq!(AssetManagement::value_history(coll_id0, item_id0).len(), 2);
+		assert_eq!(AssetManagement::net_asset_value(coll_id0, item_id0), Ok(41_500));
+	});
+}
+
+#[test]
+fn migration_keys_guaranty_payments_by_payment_id() {
+	ExtBuilder::default().build().execute_with(|| {
+		use pallet_payment::{migration::v0 as payment_v0, PaymentState};
+
+		let asset_account = DAVE;
+		let old_payment = |state: PaymentState<Test>| payment_v0::OldPaymentDetail::<Test> {
+			amount: 1_000,
+			incentive_amount: 0,
+			state,
+			resolver_account: ALICE,
+			fee_detail: Some((ALICE, 0)),
+		};
+
+		//Both guaranties were requested from the tenants, GERARD accepted and paid his
+		payment_v0::Payment::<Test>::insert(
+			GERARD,
+			asset_account,
+			old_payment(PaymentState::PaymentRequested),
+		);
+		payment_v0::Payment::<Test>::insert(
+			HUNTER,
+			asset_account,
+			old_payment(PaymentState::PaymentRequested),
+		);
+		migration::v0::GuarantyPayment::<Test>::insert(
+			GERARD,
+			asset_account,
+			old_payment(PaymentState::PaymentCompleted),
+		);
+		migration::v0::GuarantyPayment::<Test>::insert(
+			HUNTER,
+			asset_account,
+			old_payment(PaymentState::PaymentRequested),
+		);
+		StorageVersion::new(0).put::<pallet_payment::Pallet<Test>>();
+		StorageVersion::new(0).put::<AssetManagement>();
+
+		migration::migrate_to_v1::<Test>();
+
+		assert_eq!(AssetManagement::on_chain_storage_version(), STORAGE_VERSION);
+		assert_eq!(
+			pallet_payment::Pallet::<Test>::on_chain_storage_version(),
+			pallet_payment::STORAGE_VERSION
+		);
+
+		//The guaranties are keyed by the ids given by the payment migration
+		let gerard_id = AssetManagement::guaranty_id(GERARD, asset_account).unwrap();
+		let hunter_id = AssetManagement::guaranty_id(HUNTER, asset_account).unwrap();
+		assert_eq!(AssetManagement::asset_guaranties(asset_account, GERARD), Some(gerard_id));
+		assert_eq!(AssetManagement::asset_guaranties(asset_account, HUNTER), Some(hunter_id));
+		assert_eq!(
+			pallet_payment::Pallet::<Test>::payments_between(&GERARD, &asset_account),
+			vec![gerard_id]
+		);
+		assert_eq!(
+			pallet_payment::Pallet::<Test>::payments_between(&HUNTER, &asset_account),
+			vec![hunter_id]
+		);
+
+		//The accepted guaranty is completed, the other one is still requested
+		let gerard_guaranty = AssetManagement::guaranty(GERARD, asset_account).unwrap();
+		assert_eq!(gerard_guaranty.state, PaymentState::PaymentCompleted);
+		assert_eq!(gerard_guaranty.amount, 1_000);
+		let hunter_guaranty = AssetManagement::guaranty(HUNTER, asset_account).unwrap();
+		assert_eq!(hunter_guaranty.state, PaymentState::PaymentRequested);
+
+		//The completed guaranty cannot be cancelled by the asset account anymore
+		let cancel =
+			pallet_payment::Pallet::<Test>::cancel(Origin::signed(asset_account), gerard_id);
+		assert!(cancel.is_err());
+		assert_eq!(AssetManagement::asset_obligations(asset_account), 1_000);
+	});
+}
+
+#[test]
+fn guaranty_payment_should_not_be_requested_twice() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+
+		assert_ok!(AssetManagement::guaranty_payment(
+			Origin::signed(virtual0.clone()),
+			GERARD,
+			coll_id0,
+			item_id0
+		));
+		let payment_id = AssetManagement::guaranty_id(GERARD, virtual0.clone()).unwrap();
+
+		//The pending request is not replaced by a new one
+		assert_err!(
+			AssetManagement::guaranty_payment(
+				Origin::signed(virtual0.clone()),
+				GERARD,
+				coll_id0,
+				item_id0
+			),
+			Error::<Test>::ExistingPaymentRequest
+		);
+		assert_eq!(AssetManagement::guaranty_id(GERARD, virtual0.clone()), Some(payment_id));
+		assert_eq!(AssetManagement::asset_guaranties(virtual0, GERARD), Some(payment_id));
+	});
+}
+
+#[test]
+fn tenant_linking_errors_should_be_returned() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0, virtual0) = prep_virtual_asset();
+
+		//The asset must exist, and the linked account must be a tenant
+		assert_err!(
+			AssetManagement::link_tenant_to_asset(
+				Origin::signed(virtual0.clone()),
+				GERARD,
+				coll_id0,
+				item_id0 + 1
+			),
+			Error::<Test>::NotAnAsset
+		);
+		assert_err!(
+			AssetManagement::link_tenant_to_asset(
+				Origin::signed(virtual0.clone()),
+				DAVE,
+				coll_id0,
+				item_id0
+			),
+			Error::<Test>::NotATenant
+		);
+		assert!(AssetManagement::tenant_leases(DAVE).is_none());
+
+		assert_ok!(AssetManagement::link_tenant_to_asset(
+			Origin::signed(virtual0.clone()),
+			GERARD,
+			coll_id0,
+			item_id0
+		));
+		let asset = OnboardingModule::houses(coll_id0, item_id0).unwrap();
+		assert_eq!(asset.tenants, vec![GERARD]);
+	});
+}
@@ -8,7 +8,7 @@ pub use frame_support::{
 		traits::{AccountIdConversion, Hash, One, Saturating, StaticLookup, Zero},
 		FixedU128, PerThing, Percent,
 	},
-	storage::child,
+	storage::{child, with_storage_layer, StoragePrefixedMap},
 	traits::{
 		Contains, Currency, ExistenceRequirement, Get, LockableCurrency, ReservableCurrency,
 		UnfilteredDispatchable, WithdrawReasons,
@@ -119,6 +119,315 @@ impl<T: Config> ProposalRecord<T> {
 	}
 }
 
+///Sale of an asset approved by its owners
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SaleRecord<T: Config> {
+	///Virtual account corresponding to the asset
+	pub virtual_account: T::AccountId,
+	///Asking price of the asset
+	pub price: BalanceOf<T>,
+	///Tenants who received a notice
+	pub tenants: Vec<T::AccountId>,
+	///Listing block
+	pub listed_at: BlockNumberOf<T>,
+	///Block at which the tenants notice ends, and the asset can be bought
+	pub notice_end: BlockNumberOf<T>,
+}
+
+impl<T: Config> SaleRecord<T> {
+	pub fn new(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		virtual_account: T::AccountId,
+		price: BalanceOf<T>,
+		tenants: Vec<T::AccountId>,
+	) -> Self {
+		let listed_at = <frame_system::Pallet<T>>::block_number();
+		let notice_end = listed_at.saturating_add(T::NoticePeriod::get());
+		let sale = SaleRecord::<T> { virtual_account, price, tenants, listed_at, notice_end };
+		AssetSales::<T>::insert(collection_id, item_id, sale.clone());
+		sale
+	}
+}
+
+///Management fee paid to the representative of an asset, for each rent period
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ManagementFee<Balance> {
+	///Percentage of the collected rent
+	Percent(Percent),
+	///Flat amount
+	Flat(Balance),
+}
+
+///Action of a proposal submitted by an owner of an asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum OwnersAction<T: Config> {
+	///Change the monthly rent of the asset for new tenants
+	RentChange(Roles::BalanceOf<T>),
+	///Pay a servicer for a renovation, from the maintenance reserve
+	Renovation { servicer: T::AccountId, amount: BalanceOf<T>, invoice: T::Hash },
+	///Change the lease length of the asset for new tenants, in number of months
+	LeaseLength(u32),
+	///Buy an insurance for the asset from an approved servicer, paid from the asset account
+	Insurance { insurer: T::AccountId, premium: BalanceOf<T>, coverage: BlockNumberOf<T> },
+	///Sell the asset at the given price
+	Sell(BalanceOf<T>),
+	///Convert a share of the rents of the tenants into ownership tokens of the asset.
+	///A share of zero ends the rent-to-own mode.
+	RentToOwn(Percent),
+}
+
+impl<T: Config> OwnersAction<T> {
+	///Referendum threshold needed to enact the action
+	pub fn threshold(&self) -> Dem::VoteThreshold {
+		match self {
+			OwnersAction::RentChange(_) => Dem::VoteThreshold::SimpleMajority,
+			OwnersAction::Renovation { .. } => Dem::VoteThreshold::SuperMajorityApprove,
+			OwnersAction::LeaseLength(_) => Dem::VoteThreshold::SimpleMajority,
+			OwnersAction::Insurance { .. } => Dem::VoteThreshold::SimpleMajority,
+			OwnersAction::Sell(_) => Dem::VoteThreshold::SuperMajorityApprove,
+			OwnersAction::RentToOwn(_) => Dem::VoteThreshold::SuperMajorityApprove,
+		}
+	}
+}
+
+///Insurance bought by the owners of an asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct InsurancePolicy<T: Config> {
+	///Insurer paid for the policy
+	pub insurer: T::AccountId,
+	///Premium paid to the insurer
+	pub premium: BalanceOf<T>,
+	///Start of the coverage
+	pub start: BlockNumberOf<T>,
+	///End of the coverage
+	pub end: BlockNumberOf<T>,
+}
+
+///Rule applied when no candidate wins the re-election of a representative
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ReelectionFallback<BlockNumber> {
+	///The incumbent stays in charge for the given number of blocks, until the next re-election
+	KeepIncumbent(BlockNumber),
+	///The incumbent is demoted, and the asset is left without representative
+	SuspendManagement,
+}
+
+///Re-election of the representative of an asset, at the end of its term
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Reelection<T: Config> {
+	///Virtual account corresponding to the asset
+	pub virtual_account: T::AccountId,
+	///Representative whose term expired
+	pub incumbent: T::AccountId,
+	///Candidates, starting with the incumbent
+	pub candidates: Vec<T::AccountId>,
+	///Owners votes, as (owner, candidate)
+	pub votes: Vec<(T::AccountId, T::AccountId)>,
+	///Block at which the votes are counted
+	pub end: BlockNumberOf<T>,
+}
+
+impl<T: Config> Reelection<T> {
+	pub fn new(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		virtual_account: T::AccountId,
+		incumbent: T::AccountId,
+	) -> Self {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let end = now.saturating_add(<T as Dem::Config>::VotingPeriod::get());
+		let candidates = vec![incumbent.clone()];
+		let reelection =
+			Reelection::<T> { virtual_account, incumbent, candidates, votes: Vec::new(), end };
+		Reelections::<T>::insert(collection_id, item_id, reelection.clone());
+		reelection
+	}
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum MaintenanceOperation {
+	Deposit,
+	Spend,
+	Refund,
+}
+
+///Operation on the maintenance reserve of an asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MaintenanceRecord<T: Config> {
+	///Deposit from the rent, spending on a servicer invoice, or refund of a spending
+	pub operation: MaintenanceOperation,
+	///Amount of the operation
+	pub amount: BalanceOf<T>,
+	///Servicer paid by the operation
+	pub servicer: Option<T::AccountId>,
+	///Hash of the servicer invoice
+	pub invoice: Option<T::Hash>,
+	///Operation block
+	pub when: BlockNumberOf<T>,
+}
+
+impl<T: Config> MaintenanceRecord<T> {
+	pub fn new(
+		asset_account: T::AccountId,
+		operation: MaintenanceOperation,
+		amount: BalanceOf<T>,
+		servicer: Option<T::AccountId>,
+		invoice: Option<T::Hash>,
+	) -> u32 {
+		let when = <frame_system::Pallet<T>>::block_number();
+		let record = MaintenanceRecord::<T> { operation, amount, servicer, invoice, when };
+		let id = MaintenanceLogId::<T>::get(&asset_account);
+		MaintenanceLog::<T>::insert(&asset_account, id, record);
+		MaintenanceLogId::<T>::mutate(&asset_account, |val| {
+			let val0 = *val;
+			*val = val0 + 1;
+		});
+		id
+	}
+}
+
+///Valuation of an asset submitted by a notary or by the asset appraiser
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Revaluation<T: Config> {
+	///Notary or appraiser who valued the asset
+	pub appraiser: T::AccountId,
+	///Value of the asset
+	pub value: BalanceOf<T>,
+	///Hash of the valuation report
+	pub report: T::Hash,
+	///Block at which the valuation was submitted
+	pub submitted_at: BlockNumberOf<T>,
+	///Block at which the valuation was approved
+	pub approved_at: Option<BlockNumberOf<T>>,
+}
+
+impl<T: Config> Revaluation<T> {
+	pub fn new(appraiser: T::AccountId, value: BalanceOf<T>, report: T::Hash) -> Self {
+		Revaluation::<T> {
+			appraiser,
+			value,
+			report,
+			submitted_at: <frame_system::Pallet<T>>::block_number(),
+			approved_at: None,
+		}
+	}
+}
+
+///Priority of the background tasks executed in `on_idle`
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum TaskPriority {
+	High,
+	Medium,
+	Low,
+	///The background scan, which queues the other tasks and is executed first
+	Scan,
+}
+
+///Background tasks queued in `on_initialize` and executed in `on_idle`
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Task<T: Config> {
+	///Update the vote result of a proposal when its referendum is finished
+	ReferendumCheck(Dem::ReferendumIndex),
+	///Distribute the pending rents of an asset to its owners
+	RentDistribution { tenant: T::AccountId, asset_account: T::AccountId },
+	///Check the rent arrears of a tenant
+	ArrearsCheck(T::AccountId),
+	///Start the re-election of the representative of an asset whose term ended
+	ReelectionStart { collection: T::NftCollectionId, item: T::NftItemId },
+	///Count the votes of a re-election that is over
+	ReelectionClose { collection: T::NftCollectionId, item: T::NftItemId },
+	///Scan a batch of the storages needing background tasks, from the `BackgroundScan` cursor
+	Scan,
+}
+
+impl<T: Config> Task<T> {
+	pub fn priority(&self) -> TaskPriority {
+		match self {
+			Task::ReferendumCheck(_) |
+			Task::ReelectionStart { .. } |
+			Task::ReelectionClose { .. } => TaskPriority::High,
+			Task::RentDistribution { .. } => TaskPriority::Medium,
+			Task::ArrearsCheck(_) => TaskPriority::Low,
+			Task::Scan => TaskPriority::Scan,
+		}
+	}
+
+	///Maximum weight needed to execute the task
+	pub fn weight(&self) -> Weight {
+		let weight = match self {
+			Task::ReferendumCheck(_) => T::DbWeight::get().reads_writes(2, 1),
+			Task::RentDistribution { .. } => T::DbWeight::get().reads_writes(20, 20),
+			Task::ArrearsCheck(_) => T::DbWeight::get().reads(2),
+			Task::ReelectionStart { .. } => T::DbWeight::get().reads_writes(4, 3),
+			Task::ReelectionClose { .. } => T::DbWeight::get().reads_writes(12, 12),
+			//Each scanned item is read with up to 3 other items, and can queue two tasks
+			Task::Scan => {
+				let items = T::MaxScannedItems::get() as u64;
+				T::DbWeight::get().reads_writes(
+					items.saturating_mul(7).saturating_add(1),
+					items.saturating_mul(6).saturating_add(1),
+				)
+			},
+		};
+		//The executed task also leaves the deduplication map
+		weight
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(Weight::from_ref_time(10_000))
+	}
+}
+
+///Storages scanned by the background scan, in order
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ScanStage {
+	Proposals,
+	Tenants,
+	RepresentativeTerms,
+	Reelections,
+}
+
+impl ScanStage {
+	pub fn next(&self) -> Option<ScanStage> {
+		match self {
+			ScanStage::Proposals => Some(ScanStage::Tenants),
+			ScanStage::Tenants => Some(ScanStage::RepresentativeTerms),
+			ScanStage::RepresentativeTerms => Some(ScanStage::Reelections),
+			ScanStage::Reelections => None,
+		}
+	}
+}
+
+///Position of the background scan started every `CheckPeriod`
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ScanCursor {
+	///Storage being scanned
+	pub stage: ScanStage,
+	///Raw key of the last scanned item, or prefix of the storage when its scan starts
+	pub last_key: Vec<u8>,
+	///The rent arrears of the tenants are checked by this scan
+	pub arrears_check: bool,
+}
+
 #[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -12,6 +12,8 @@
 //!
 //! * 'force_process_onboarded_asset' - extrinsic to manually launch the process of onboarded assets
 //! * 'force_process_onboarded_asset' - extrinsic to manually launch the process of finalised assets
+//! * 'force_process_fund_shares' - extrinsic to manually launch the resale of the shares held by
+//!   the housing fund
 //!
 //! #### Functions
 //! * 'process_finalised_finalised_assets' - execute the token distribution between investors for
@@ -20,6 +22,8 @@
 //!   finalised assets
 //! * 'process_onboarded_assets' - execute the workflow to associate an onboarded onboarded asset to
 //!   a list of investors and make and make
+//! * 'process_fund_shares' - resell the ownership shares bought back by the housing fund to a list
+//!   of investors
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -66,6 +70,8 @@ pub mod pallet {
 		type MinimumSharePerInvestor: Get<u64>;
 		#[pallet::constant]
 		type NewAssetScanPeriod: Get<Self::BlockNumber>;
+		///Handler of the purchases getting FINALISING status
+		type OnPurchaseFinalising: OnPurchaseFinalising<Self::NftCollectionId, Self::NftItemId>;
 	}
 
 	pub type HousingFundAccount<T> = Housing_Fund::AccountIdOf<T>;
@@ -138,6 +144,18 @@ pub mod pallet {
 			HousingFundBalance<T>,
 			Vec<UserBalance<T>>,
 		),
+
+		/// The shares held by the housing fund were resold to a list of investors
+		FundSharesResold(T::NftCollectionId, T::NftItemId, HousingFundBalance<T>, BlockNumberOf<T>),
+
+		/// The resale of the shares held by the housing fund failed
+		FundSharesResaleFailed(
+			T::NftCollectionId,
+			T::NftItemId,
+			HousingFundBalance<T>,
+			BlockNumberOf<T>,
+			Vec<UserBalance<T>>,
+		),
 	}
 
 	#[pallet::hooks]
@@ -159,11 +177,16 @@ pub mod pallet {
 		pub fn force_process_finalised_asset(_origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			Self::process_finalised_assets()
 		}
+
+		#[pallet::weight(10_000)]
+		pub fn force_process_fund_shares(_origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			Self::process_fund_shares()
+		}
 	}
 }
 
 use enum_iterator::all;
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, storage::with_storage_layer};
 
 impl<T: Config> Pallet<T> {
 	fn begin_block(now: T::BlockNumber) -> Weight {
@@ -172,6 +195,7 @@ impl<T: Config> Pallet<T> {
 		if (now % T::NewAssetScanPeriod::get()).is_zero() {
 			Self::process_onboarded_assets().ok();
 			Self::process_finalised_assets().ok();
+			Self::process_fund_shares().ok();
 		}
 
 		max_block_weight
@@ -313,6 +337,8 @@ impl<T: Config> Pallet<T> {
 						Onboarding::AssetStatus::FINALISING,
 					)
 					.ok();
+
+					T::OnPurchaseFinalising::on_purchase_finalising(collection_id, item_id);
 				},
 				Err(_e) => {
 					Self::deposit_event(Event::HouseBiddingFailed(
@@ -332,6 +358,74 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
+	/// Process the shares bought back by the housing fund to resell them to a list of investors
+	pub fn process_fund_shares() -> DispatchResultWithPostInfo {
+		let block_number = <frame_system::Pallet<T>>::block_number();
+		let assets = ShareDistributor::FundShares::<T>::iter_keys().collect::<Vec<_>>();
+
+		for (collection_id, item_id) in assets.into_iter() {
+			// The shares are resold at the approved valuation
+			let amount =
+				match ShareDistributor::Pallet::<T>::fund_shares_price(collection_id, item_id) {
+					Some(amount) => amount,
+					None => continue,
+				};
+			Self::deposit_event(Event::ProcessingAsset(collection_id, item_id, amount));
+
+			// Retrieves the investors list and their contributions
+			let investor_shares = Self::create_investor_list(amount);
+
+			// Check that the investor list creation was successful
+			if investor_shares.is_empty() {
+				Self::deposit_event(Event::FailedToAssembleInvestors(
+					collection_id,
+					item_id,
+					amount,
+					block_number,
+				));
+				continue
+			}
+
+			Self::deposit_event(Event::InvestorListCreationSuccessful(
+				collection_id,
+				item_id,
+				amount,
+				investor_shares.clone(),
+			));
+
+			// The payments and the tokens transfers are reverted together on failure
+			let result = with_storage_layer(|| {
+				ShareDistributor::Pallet::<T>::resell_fund_shares(
+					collection_id,
+					item_id,
+					investor_shares.clone(),
+				)
+			});
+
+			match result {
+				Ok(_) => {
+					Self::deposit_event(Event::FundSharesResold(
+						collection_id,
+						item_id,
+						amount,
+						block_number,
+					));
+				},
+				Err(_e) => {
+					Self::deposit_event(Event::FundSharesResaleFailed(
+						collection_id,
+						item_id,
+						amount,
+						block_number,
+						investor_shares,
+					));
+				},
+			}
+		}
+
+		Ok(().into())
+	}
+
 	/// Create the list of investor and their contribution for a given asset's price
 	/// It follows the following rules:
 	/// - the oldest contribution comes first
@@ -312,19 +312,28 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributorModule;
 	type Extra = ();
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const AssetsFees: Balance = 20000;
+	pub const MarketFee: Percent = Percent::from_percent(2);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(50);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxOwners: u32 = 50;
 }
 impl pallet_share_distributor::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = ();
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
@@ -346,6 +355,7 @@ impl pallet_bidding::Config for Test {
 	type MaximumSharePerInvestor = MaximumSharePerInvestor;
 	type MinimumSharePerInvestor = MinimumSharePerInvestor;
 	type NewAssetScanPeriod = NewAssetScanPeriod;
+	type OnPurchaseFinalising = ();
 }
 
 pub const ALICE: u64 = 1;
@@ -9,3 +9,12 @@ pub use frame_support::{
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
+
+///Notified when a purchase gets FINALISING status
+pub trait OnPurchaseFinalising<CollectionId, ItemId> {
+	fn on_purchase_finalising(collection_id: CollectionId, item_id: ItemId);
+}
+
+impl<CollectionId, ItemId> OnPurchaseFinalising<CollectionId, ItemId> for () {
+	fn on_purchase_finalising(_collection_id: CollectionId, _item_id: ItemId) {}
+}
@@ -907,3 +907,127 @@ fn process_finalised_assets_should_succeed() {
 		));
 	});
 }
+
+#[test]
+fn process_fund_shares_should_succeed() {
+	new_test_ext().execute_with(|| {
+		let mut block_number = System::block_number();
+		let amount = 100;
+
+		for account_id in 1..6 {
+			assert_ok!(RoleModule::set_role(
+				Origin::signed(account_id),
+				account_id,
+				crate::Onboarding::HousingFund::ROLES::Accounts::INVESTOR
+			));
+
+			// test contribute with sufficient contribution and free balance
+			assert_ok!(HousingFund::contribute_to_fund(Origin::signed(account_id), amount));
+
+			block_number = block_number.saturating_add(1);
+			System::set_block_number(block_number);
+		}
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(KEZIA),
+			KEZIA,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(AMANI),
+			AMANI,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+
+		let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+			b"metadata0".to_vec().try_into().unwrap();
+
+		assert_ok!(NftModule::create_collection(
+			Origin::signed(KEZIA),
+			NftCollection::OFFICESTEST,
+			metadata.clone()
+		));
+
+		assert_ok!(OnboardingModule::create_and_submit_proposal(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			Some(100),
+			metadata,
+			false
+		));
+
+		let collection_id = NftCollection::OFFICESTEST.value();
+		let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+
+		assert_ok!(OnboardingModule::change_status(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			item_id,
+			crate::Onboarding::AssetStatus::ONBOARDED
+		));
+
+		assert_ok!(BiddingModule::process_onboarded_assets());
+
+		assert_ok!(OnboardingModule::change_status(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			item_id,
+			crate::Onboarding::AssetStatus::FINALISED
+		));
+
+		let fees_account = Onboarding::Pallet::<Test>::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+
+		assert_ok!(BiddingModule::process_finalised_assets());
+
+		// The first investor sells shares to the housing fund
+		let token_id =
+			ShareDistributorModule::virtual_acc(collection_id, item_id).unwrap().token_id;
+		assert_ok!(ShareDistributorModule::set_valuation(
+			frame_system::RawOrigin::Root.into(),
+			collection_id,
+			item_id,
+			1
+		));
+		assert_ok!(ShareDistributorModule::sell_to_fund(
+			Origin::signed(1),
+			collection_id,
+			item_id,
+			100
+		));
+		assert_eq!(ShareDistributorModule::fund_shares(collection_id, item_id), 100);
+
+		let balances = (1..6)
+			.map(|account_id| pallet_assets::Pallet::<Test>::balance(token_id, account_id))
+			.collect::<Vec<_>>();
+
+		assert_ok!(BiddingModule::process_fund_shares());
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		// check that the event has been raised
+		assert_eq!(
+			event,
+			mock::Event::BiddingModule(crate::Event::FundSharesResold(
+				collection_id,
+				item_id,
+				100,
+				block_number
+			))
+		);
+
+		// The shares are shared between the assembled investors
+		for account_id in 1..6 {
+			assert_eq!(
+				pallet_assets::Pallet::<Test>::balance(token_id, account_id),
+				balances[account_id as usize - 1] + 20
+			);
+		}
+		assert_eq!(ShareDistributorModule::fund_shares(collection_id, item_id), 0);
+	});
+}
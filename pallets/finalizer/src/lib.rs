@@ -12,18 +12,46 @@
 //!
 //! ### Dispatchable Functions
 //!
-//! * 'validate_transaction_asset' - a notary validate a purchase transaction after checked
-//!   informations
-//! * 'reject_transaction_asset' - a notary reject a purchase
+//! * 'validate_transaction_asset' - an assigned notary validate a purchase transaction after
+//!   checked informations. The purchase is FINALISED once enough assigned notaries validated it
+//! * 'reject_transaction_asset' - a notary reject a purchase, with the checklist item that failed
 //! * 'reject_transaction_asset' - a house owner can cancel the purchase transaction after notary
 //!   validation
+//! * 'set_checklist_template' - root sets the closing checklist of a property type
+//! * 'complete_checklist_item' - a notary or a servicer completes a closing checklist item with
+//!   the hash of its evidence
+//!
+//! ### Notaries sign-off
+//!
+//! Each purchase getting FINALISING status is assigned the next notaries of the notaries
+//! registry. The purchase waits until enough notaries are registered to reach the required number
+//! of approvals. If the required number of approvals is not reached before the sign-off deadline,
+//! the purchase is automatically rejected and the reserved housing fund is released.
+//!
+//! ### Escrowed settlement
+//!
+//! When a purchase gets FINALISING status, the reserved housing fund is moved into a
+//! `pallet_payment` escrow from the housing fund to the seller. The escrow is released to the
+//! seller when the notaries validate the purchase. A rejection requests a refund of the escrow,
+//! which the seller can dispute: the dispute is then settled by the resolver with
+//! `resolve_payment`. The housing fund reservation is only released once the refund is settled.
+//! A seller cancellation refunds the escrow to the housing fund.
+//!
+//! ### Closing checklist
+//!
+//! Each purchase gets a closing checklist built from the template of its property type when it
+//! gets FINALISING status, every item being mandatory if the property type has no template.
+//! The purchase can only be validated once every mandatory item is completed, and the checklist
+//! is removed once the purchase is validated, rejected or cancelled.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
+pub use pallet_bidding as Bidding;
 pub use pallet_housing_fund as HousingFund;
 pub use pallet_nft as Nft;
 pub use pallet_onboarding as Onboarding;
+pub use pallet_payment as Payment;
 pub use pallet_roles as Roles;
 
 #[cfg(test)]
@@ -38,6 +66,9 @@ mod benchmarking;
 pub mod weights;
 pub use weights::WeightInfo;
 
+mod types;
+pub use types::*;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -48,6 +79,7 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -58,12 +90,144 @@ pub mod pallet {
 		+ Nft::Config
 		+ Onboarding::Config
 		+ HousingFund::Config
+		+ Payment::Config
 	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
+
+		///Number of notaries assigned to a purchase
+		#[pallet::constant]
+		type NotariesPerPurchase: Get<u32>;
+
+		///Number of assigned notaries approvals needed to validate a purchase
+		#[pallet::constant]
+		type RequiredApprovals: Get<u32>;
+
+		///Number of blocks given to the assigned notaries to validate a purchase
+		#[pallet::constant]
+		type SignOffDelay: Get<Self::BlockNumber>;
+
+		///Maximum number of purchases waiting for notaries to be registered
+		#[pallet::constant]
+		type MaxPendingSignOffs: Get<u32>;
+
+		///Maximum number of sign-off deadlines reached in the same block
+		#[pallet::constant]
+		type MaxSignOffsPerBlock: Get<u32>;
 	}
 
+	#[pallet::storage]
+	#[pallet::getter(fn sign_offs)]
+	///Notaries sign-off of the purchases in FINALISING status
+	pub type SignOffs<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		SignOff<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sign_off_deadlines)]
+	///Purchases whose sign-off deadline is reached at a given block
+	pub type SignOffDeadlines<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::NftCollectionId, T::NftItemId), T::MaxSignOffsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_sign_offs)]
+	///Purchases in FINALISING status waiting for enough notaries to be registered
+	pub type PendingSignOffs<T: Config> = StorageValue<
+		_,
+		BoundedVec<(T::NftCollectionId, T::NftItemId), T::MaxPendingSignOffs>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn notary_cursor)]
+	///Position in the notaries registry of the next notary to assign
+	pub type NotaryCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::type_value]
+	///Closing checklist of the property types without a template, with every item mandatory
+	pub fn DefaultChecklistTemplate<T: Config>() -> Vec<(ClosingItem, bool)> {
+		sp_std::vec![
+			(ClosingItem::TitleVerified, true),
+			(ClosingItem::LiensCleared, true),
+			(ClosingItem::InspectionDone, true),
+			(ClosingItem::TaxPaid, true),
+		]
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn checklist_templates)]
+	///Closing checklist items of each property type, and whether they are mandatory
+	pub type ChecklistTemplates<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Vec<(ClosingItem, bool)>,
+		ValueQuery,
+		DefaultChecklistTemplate<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn checklists)]
+	///Closing checklist of the purchases
+	pub type Checklists<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Vec<ChecklistEntry<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn failed_checklist_items)]
+	///Checklist item that made a notary reject a purchase
+	pub type FailedChecklistItems<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		(AccountIdOf<T>, ClosingItem),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn escrow_payments)]
+	///`pallet_payment` escrow opened for the purchases in FINALISING status
+	pub type EscrowPayments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		Payment::PaymentId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn escrow_refunds)]
+	///Purchases whose escrow refund is waiting for the payment to be settled
+	pub type EscrowRefunds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		Payment::PaymentId,
+		(T::NftCollectionId, T::NftItemId),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -72,6 +236,37 @@ pub mod pallet {
 		NotaryValidatedAssetTransaction(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
 		NotaryRejectedAssetTransaction(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
 		SellerCancelledAssetTransaction(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
+		/// Notaries were assigned to a purchase. [notaries, collection, item, deadline]
+		NotariesAssigned(Vec<AccountIdOf<T>>, T::NftCollectionId, T::NftItemId, T::BlockNumber),
+		/// An assigned notary approved a purchase. [notary, collection, item, approvals]
+		NotaryApprovalRecorded(AccountIdOf<T>, T::NftCollectionId, T::NftItemId, u32),
+		/// The sign-off deadline passed and the purchase was rejected. [collection, item, block]
+		SignOffExpired(T::NftCollectionId, T::NftItemId, T::BlockNumber),
+		/// The purchase fund was escrowed to the seller. [seller, collection, item, amount]
+		EscrowOpened(AccountIdOf<T>, T::NftCollectionId, T::NftItemId, Payment::BalanceOf<T>),
+		/// The escrow was released to the seller. [seller, collection, item]
+		EscrowReleased(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
+		/// A refund of the escrow was requested from the seller. [seller, collection, item]
+		EscrowRefundRequested(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
+		/// The escrow was refunded to the housing fund. [seller, collection, item]
+		EscrowRefunded(AccountIdOf<T>, T::NftCollectionId, T::NftItemId),
+		/// The closing checklist of a property type was set. [collection, items]
+		ChecklistTemplateSet(T::NftCollectionId, Vec<(ClosingItem, bool)>),
+		/// A closing checklist item was completed. [who, collection, item, checklist item, evidence]
+		ChecklistItemCompleted(
+			AccountIdOf<T>,
+			T::NftCollectionId,
+			T::NftItemId,
+			ClosingItem,
+			T::Hash,
+		),
+		/// A purchase was rejected on a checklist item. [notary, collection, item, checklist item]
+		ChecklistItemFailed(AccountIdOf<T>, T::NftCollectionId, T::NftItemId, ClosingItem),
+		/// Not enough notaries are registered, the purchase waits for them. [collection, item]
+		SignOffPending(T::NftCollectionId, T::NftItemId),
+		/// No sign-off could be opened, the purchase went back to ONBOARDED status.
+		/// [collection, item]
+		PurchasePostponed(T::NftCollectionId, T::NftItemId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -89,6 +284,36 @@ pub mod pallet {
 		HouseHasNotFinalisedStatus,
 		/// Asset must have FINALISING status
 		HouseHasNotFinalisingStatus,
+		/// The notary is not assigned to this purchase
+		NotAnAssignedNotary,
+		/// The notary already approved this purchase
+		AlreadyApproved,
+		/// The purchase amount can't be used for an escrow
+		InvalidEscrowAmount,
+		/// Must have the notary or the servicer role
+		NotANotaryOrServicer,
+		/// An item appears twice in the checklist
+		DuplicateChecklistItem,
+		/// The item is not part of the purchase checklist
+		NotInChecklist,
+		/// The checklist item is already completed
+		ChecklistItemAlreadyCompleted,
+		/// Some mandatory checklist items are not completed
+		MandatoryChecklistItemsPending,
+		/// Fewer notaries than the required approvals are registered
+		NotEnoughNotaries,
+		/// Too many sign-off deadlines are reached in the same block
+		TooManySignOffs,
+		/// The checklist has no mandatory item
+		NoMandatoryChecklistItem,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Weight: see `begin_block`
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::begin_block(n)
+		}
 	}
 
 	#[pallet::call]
@@ -118,6 +343,39 @@ pub mod pallet {
 				Error::<T>::HouseHasNotFinalisingStatus
 			);
 
+			// Ensure every mandatory item of the closing checklist is completed
+			let checklist = Self::get_or_open_checklist(collection_id, nft_item_id);
+			ensure!(
+				checklist.iter().all(|entry| !entry.mandatory || entry.is_completed()),
+				Error::<T>::MandatoryChecklistItemsPending
+			);
+
+			// Ensure the notary is assigned to the purchase and did not approve it yet
+			let mut sign_off = Self::get_or_assign_notaries(collection_id, nft_item_id)?;
+			ensure!(sign_off.notaries.contains(&who), Error::<T>::NotAnAssignedNotary);
+			ensure!(!sign_off.approvals.contains(&who), Error::<T>::AlreadyApproved);
+
+			sign_off.approvals.push(who.clone());
+			let approvals = sign_off.approvals.len() as u32;
+
+			Self::deposit_event(Event::NotaryApprovalRecorded(
+				who.clone(),
+				collection_id,
+				nft_item_id,
+				approvals,
+			));
+
+			// The purchase stays in FINALISING status until the threshold is reached
+			if approvals < sign_off.threshold {
+				SignOffs::<T>::insert(collection_id, nft_item_id, sign_off);
+				return Ok(())
+			}
+
+			Self::close_sign_off(collection_id, nft_item_id);
+
+			// The seller gets paid
+			Self::release_escrow(collection_id, nft_item_id)?;
+
 			let collection = Self::get_possible_collection(collection_id);
 
 			Onboarding::Pallet::<T>::change_status(
@@ -141,11 +399,13 @@ pub mod pallet {
 		/// The origin must be signed
 		/// - collection_id: the collection id of the nft asset
 		/// - nft_item_id: the id of the nft asset
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		/// - failed_item: the checklist item that made the purchase fail, if any
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2).ref_time())]
 		pub fn reject_transaction_asset(
 			origin: OriginFor<T>,
 			collection_id: T::NftCollectionId,
 			nft_item_id: T::NftItemId,
+			failed_item: Option<ClosingItem>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin.clone())?;
 
@@ -162,6 +422,31 @@ pub mod pallet {
 				Error::<T>::HouseHasNotFinalisingStatus
 			);
 
+			// Ensure the notary is assigned to the purchase
+			let sign_off = Self::get_or_assign_notaries(collection_id, nft_item_id)?;
+			ensure!(sign_off.notaries.contains(&who), Error::<T>::NotAnAssignedNotary);
+
+			// Record the checklist item that failed
+			if let Some(item) = failed_item {
+				let checklist = Self::get_or_open_checklist(collection_id, nft_item_id);
+				ensure!(
+					checklist.iter().any(|entry| entry.item == item),
+					Error::<T>::NotInChecklist
+				);
+				FailedChecklistItems::<T>::insert(collection_id, nft_item_id, (who.clone(), item));
+				Self::deposit_event(Event::ChecklistItemFailed(
+					who.clone(),
+					collection_id,
+					nft_item_id,
+					item,
+				));
+			}
+
+			Self::close_sign_off(collection_id, nft_item_id);
+
+			// The housing fund asks the seller for its money back
+			Self::refund_escrow(collection_id, nft_item_id)?;
+
 			let collection = Self::get_possible_collection(collection_id);
 
 			Onboarding::Pallet::<T>::change_status(
@@ -172,8 +457,6 @@ pub mod pallet {
 			)
 			.ok();
 
-			HousingFund::Pallet::<T>::cancel_house_bidding(collection_id, nft_item_id).ok();
-
 			Self::deposit_event(Event::NotaryRejectedAssetTransaction(
 				who,
 				collection_id,
@@ -184,6 +467,7 @@ pub mod pallet {
 		}
 
 		/// The seller set the house status to CANCELLED
+		/// The escrowed purchase fund goes back to the housing fund
 		/// The origin must be signed
 		/// - collection_id: the collection id of the nft asset
 		/// - nft_item_id: the id of the nft asset
@@ -207,12 +491,44 @@ pub mod pallet {
 			// Ensure the caller is the owner of the house
 			ensure!(who == owner, Error::<T>::NotTheHouseOwner);
 
-			// Ensure the house status is FINALISED
+			// Ensure the house status is FINALISING or FINALISED
+			let status = house_wrap.unwrap().status;
 			ensure!(
-				house_wrap.unwrap().status == Onboarding::AssetStatus::FINALISED,
+				status == Onboarding::AssetStatus::FINALISING ||
+					status == Onboarding::AssetStatus::FINALISED,
 				Error::<T>::HouseHasNotFinalisedStatus
 			);
 
+			Self::close_sign_off(collection_id, nft_item_id);
+
+			if let Some(seller) = HousingFund::Pallet::<T>::escrows((collection_id, nft_item_id)) {
+				let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+				let open_payment = EscrowPayments::<T>::take(collection_id, nft_item_id)
+					.filter(|payment_id| Payment::Pallet::<T>::payment(payment_id).is_some());
+				if let Some(payment_id) = open_payment {
+					// The escrow is still open and is cancelled by the seller
+					Payment::Pallet::<T>::cancel(
+						frame_system::RawOrigin::Signed(seller.clone()).into(),
+						payment_id,
+					)
+					.map_err(|e| e.error)?;
+				} else {
+					// The escrow was released, the seller pays the purchase amount back
+					let reservation =
+						HousingFund::Pallet::<T>::reservations((collection_id, nft_item_id))
+							.ok_or(HousingFund::Error::<T>::NoFundReservationFound)?;
+					let amount = Self::hfund_to_payment_balance(reservation.amount)
+						.ok_or(Error::<T>::InvalidEscrowAmount)?;
+					<T as Payment::Config>::Currency::transfer(
+						&seller,
+						&fund_account,
+						amount,
+						ExistenceRequirement::AllowDeath,
+					)?;
+				}
+				Self::deposit_event(Event::EscrowRefunded(seller, collection_id, nft_item_id));
+			}
+
 			let collection = Self::get_possible_collection(collection_id);
 
 			Onboarding::Pallet::<T>::change_status(
@@ -233,12 +549,384 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Set the closing checklist of a property type
+		/// The origin must be root
+		/// - collection_id: the collection id of the property type
+		/// - items: the checklist items, and whether they are mandatory
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn set_checklist_template(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			items: Vec<(ClosingItem, bool)>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			// A checklist without mandatory items would let any purchase be validated
+			ensure!(
+				items.iter().any(|(_, mandatory)| *mandatory),
+				Error::<T>::NoMandatoryChecklistItem
+			);
+
+			for (index, (item, _)) in items.iter().enumerate() {
+				ensure!(
+					!items[index + 1..].iter().any(|(other, _)| other == item),
+					Error::<T>::DuplicateChecklistItem
+				);
+			}
+
+			ChecklistTemplates::<T>::insert(collection_id, items.clone());
+
+			Self::deposit_event(Event::ChecklistTemplateSet(collection_id, items));
+
+			Ok(())
+		}
+
+		/// A notary or a servicer completes an item of the closing checklist of a purchase
+		/// The origin must be signed
+		/// - collection_id: the collection id of the nft asset
+		/// - nft_item_id: the id of the nft asset
+		/// - item: the checklist item
+		/// - evidence: hash of the evidence of the item completion
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1).ref_time())]
+		pub fn complete_checklist_item(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			nft_item_id: T::NftItemId,
+			item: ClosingItem,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Check that the account has the notary or the servicer role
+			ensure!(
+				Roles::Pallet::<T>::notaries(who.clone()).is_some() ||
+					Roles::Pallet::<T>::servicers(who.clone()).is_some(),
+				Error::<T>::NotANotaryOrServicer
+			);
+
+			// Check that the house exists in storage
+			let house_wrap = Onboarding::Houses::<T>::get(collection_id, nft_item_id);
+			ensure!(house_wrap.is_some(), Error::<T>::AssetDoesNotExist);
+
+			// Ensure the house status is FINALISING
+			ensure!(
+				house_wrap.unwrap().status == Onboarding::AssetStatus::FINALISING,
+				Error::<T>::HouseHasNotFinalisingStatus
+			);
+
+			let mut checklist = Self::get_or_open_checklist(collection_id, nft_item_id);
+			let entry = checklist
+				.iter_mut()
+				.find(|entry| entry.item == item)
+				.ok_or(Error::<T>::NotInChecklist)?;
+			ensure!(!entry.is_completed(), Error::<T>::ChecklistItemAlreadyCompleted);
+
+			entry.completed_by = Some(who.clone());
+			entry.evidence = Some(evidence);
+			entry.completed_at = Some(<frame_system::Pallet<T>>::block_number());
+			Checklists::<T>::insert(collection_id, nft_item_id, checklist);
+
+			Self::deposit_event(Event::ChecklistItemCompleted(
+				who,
+				collection_id,
+				nft_item_id,
+				item,
+				evidence,
+			));
+
+			Ok(())
+		}
 	}
 }
 
 use enum_iterator::all;
 pub use frame_support::inherent::Vec;
+use frame_support::{
+	sp_runtime::{traits::Saturating, PerThing, Percent},
+	traits::{Currency, ExistenceRequirement},
+	transactional,
+};
 impl<T: Config> Pallet<T> {
+	fn begin_block(now: T::BlockNumber) -> Weight {
+		let mut reads = 2;
+		let mut writes = 1;
+
+		// Purchases waiting for notaries get them assigned once enough notaries are registered
+		let pending = Self::pending_sign_offs();
+		if !pending.is_empty() {
+			let registry = Roles::Pallet::<T>::notary_accounts();
+			reads += registry.len() as u64;
+			if registry.len() as u32 >= T::RequiredApprovals::get() {
+				let mut waiting = BoundedVec::<_, T::MaxPendingSignOffs>::default();
+				for (collection_id, item_id) in pending {
+					reads += 2;
+					if SignOffs::<T>::contains_key(collection_id, item_id) ||
+						!Self::is_finalising(collection_id, item_id)
+					{
+						continue
+					}
+					reads += 2;
+					if Self::assign_notaries_from(&registry, collection_id, item_id).is_ok() {
+						writes += 3;
+					} else {
+						waiting.try_push((collection_id, item_id)).ok();
+					}
+				}
+				PendingSignOffs::<T>::put(waiting);
+				writes += 1;
+			}
+		}
+
+		// Purchases not validated before the deadline are rejected
+		for (collection_id, item_id) in SignOffDeadlines::<T>::take(now) {
+			reads += 2;
+			if SignOffs::<T>::take(collection_id, item_id).is_none() ||
+				!Self::is_finalising(collection_id, item_id)
+			{
+				continue
+			}
+			Checklists::<T>::remove(collection_id, item_id);
+
+			let collection = Self::get_possible_collection(collection_id);
+			Self::refund_escrow(collection_id, item_id).ok();
+			Onboarding::Pallet::<T>::status(collection, item_id, Onboarding::AssetStatus::REJECTED);
+			reads += 6;
+			writes += 9;
+
+			Self::deposit_event(Event::SignOffExpired(collection_id, item_id, now));
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Open the notaries sign-off, the closing checklist and the escrow of a purchase that got
+	/// FINALISING status
+	/// The purchase waits for notaries if not enough of them are registered, and goes back to
+	/// ONBOARDED status if no sign-off can be opened
+	pub fn open_purchase(collection_id: T::NftCollectionId, item_id: T::NftItemId) {
+		if Self::assign_notaries(collection_id, item_id).is_err() {
+			let queued = PendingSignOffs::<T>::try_mutate(|pending| {
+				pending.try_push((collection_id, item_id))
+			});
+			if queued.is_err() {
+				let collection = Self::get_possible_collection(collection_id);
+				HousingFund::Pallet::<T>::cancel_house_bidding(collection_id, item_id).ok();
+				Onboarding::Pallet::<T>::status(
+					collection,
+					item_id,
+					Onboarding::AssetStatus::ONBOARDED,
+				);
+				Self::deposit_event(Event::PurchasePostponed(collection_id, item_id));
+				return
+			}
+			Self::deposit_event(Event::SignOffPending(collection_id, item_id));
+		}
+
+		// The closing checklist is built from the property type template
+		Self::get_or_open_checklist(collection_id, item_id);
+
+		// The purchase fund is escrowed to the seller
+		Self::open_escrow(collection_id, item_id).ok();
+	}
+
+	/// Assign notaries from the notaries registry to a purchase and open its sign-off
+	/// Fails if fewer notaries than the required approvals are registered
+	pub fn assign_notaries(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> Result<SignOff<T>, DispatchError> {
+		let registry = Roles::Pallet::<T>::notary_accounts();
+		Self::assign_notaries_from(&registry, collection_id, item_id)
+	}
+
+	fn assign_notaries_from(
+		registry: &[T::AccountId],
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> Result<SignOff<T>, DispatchError> {
+		let required = T::RequiredApprovals::get();
+		let registered = registry.len() as u32;
+		ensure!(registered > 0 && registered >= required, Error::<T>::NotEnoughNotaries);
+
+		// Purchases get the next notaries of the registry, so that assignments rotate
+		let count = T::NotariesPerPurchase::get().max(required).min(registered);
+		let cursor = Self::notary_cursor() % registered;
+		let notaries = registry
+			.iter()
+			.cycle()
+			.skip(cursor as usize)
+			.take(count as usize)
+			.cloned()
+			.collect::<Vec<_>>();
+
+		let deadline = <frame_system::Pallet<T>>::block_number() + T::SignOffDelay::get();
+		SignOffDeadlines::<T>::try_mutate(deadline, |purchases| {
+			purchases.try_push((collection_id, item_id))
+		})
+		.map_err(|_| Error::<T>::TooManySignOffs)?;
+		NotaryCursor::<T>::put((cursor + count) % registered);
+
+		let sign_off = SignOff::<T>::new(collection_id, item_id, notaries.clone(), deadline);
+
+		Self::deposit_event(Event::NotariesAssigned(notaries, collection_id, item_id, deadline));
+
+		Ok(sign_off)
+	}
+
+	/// Remove the sign-off of a purchase, with its deadline, its place in the waiting list and
+	/// its closing checklist
+	fn close_sign_off(collection_id: T::NftCollectionId, item_id: T::NftItemId) {
+		Checklists::<T>::remove(collection_id, item_id);
+		if let Some(sign_off) = SignOffs::<T>::take(collection_id, item_id) {
+			SignOffDeadlines::<T>::mutate(sign_off.deadline, |purchases| {
+				purchases.retain(|purchase| *purchase != (collection_id, item_id))
+			});
+		}
+		PendingSignOffs::<T>::mutate(|pending| {
+			pending.retain(|purchase| *purchase != (collection_id, item_id))
+		});
+	}
+
+	fn is_finalising(collection_id: T::NftCollectionId, item_id: T::NftItemId) -> bool {
+		Onboarding::Houses::<T>::get(collection_id, item_id)
+			.map_or(false, |house| house.status == Onboarding::AssetStatus::FINALISING)
+	}
+
+	/// Open an escrow payment of the reserved housing fund to the seller of a purchase
+	#[transactional]
+	pub fn open_escrow(collection_id: T::NftCollectionId, item_id: T::NftItemId) -> DispatchResult {
+		let seller: T::AccountId =
+			Nft::Pallet::<T>::owner(collection_id, item_id).ok_or(Error::<T>::AssetDoesNotExist)?;
+		let reservation = HousingFund::Pallet::<T>::reservations((collection_id, item_id))
+			.ok_or(HousingFund::Error::<T>::NoFundReservationFound)?;
+		let amount = Self::hfund_to_payment_balance(reservation.amount)
+			.ok_or(Error::<T>::InvalidEscrowAmount)?;
+
+		HousingFund::Pallet::<T>::escrow_house_bidding(collection_id, item_id, seller.clone())
+			.map_err(|e| e.error)?;
+
+		let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+		let payment_id = Payment::Pallet::<T>::do_escrow(fund_account, seller.clone(), amount)?;
+		EscrowPayments::<T>::insert(collection_id, item_id, payment_id);
+
+		Self::deposit_event(Event::EscrowOpened(seller, collection_id, item_id, amount));
+
+		Ok(())
+	}
+
+	/// Release the escrow of a purchase to the seller, if any
+	fn release_escrow(collection_id: T::NftCollectionId, item_id: T::NftItemId) -> DispatchResult {
+		if let Some(seller) = HousingFund::Pallet::<T>::escrows((collection_id, item_id)) {
+			let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+			let payment_id = EscrowPayments::<T>::take(collection_id, item_id)
+				.ok_or(Payment::Error::<T>::InvalidPayment)?;
+			Payment::Pallet::<T>::release(
+				frame_system::RawOrigin::Signed(fund_account).into(),
+				payment_id,
+			)
+			.map_err(|e| e.error)?;
+
+			Self::deposit_event(Event::EscrowReleased(seller, collection_id, item_id));
+		}
+		Ok(())
+	}
+
+	/// Request the refund of the escrow of a purchase, or release its housing fund reservation if
+	/// it has no escrow
+	/// The seller can dispute the refund until the payment cancel buffer is over, the dispute is
+	/// then settled by the payment resolver. The housing fund reservation is kept until the
+	/// refund is settled.
+	#[transactional]
+	fn refund_escrow(collection_id: T::NftCollectionId, item_id: T::NftItemId) -> DispatchResult {
+		let seller = match HousingFund::Pallet::<T>::escrows((collection_id, item_id)) {
+			Some(seller) => seller,
+			None => {
+				HousingFund::Pallet::<T>::cancel_house_bidding(collection_id, item_id).ok();
+				return Ok(())
+			},
+		};
+
+		let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+		let payment_id = Self::escrow_payments(collection_id, item_id)
+			.ok_or(Payment::Error::<T>::InvalidPayment)?;
+		EscrowRefunds::<T>::insert(payment_id, (collection_id, item_id));
+
+		let requested = Payment::Pallet::<T>::request_refund(
+			frame_system::RawOrigin::Signed(fund_account).into(),
+			payment_id,
+		);
+
+		if requested.is_ok() {
+			Self::deposit_event(Event::EscrowRefundRequested(seller, collection_id, item_id));
+		} else {
+			// The refund can't be scheduled, the escrow is refunded right away
+			<Payment::Pallet<T> as Payment::PaymentHandler<T>>::settle_payment(
+				payment_id,
+				Percent::from_percent(0),
+			)?;
+		}
+		Ok(())
+	}
+
+	/// Close the escrow of a purchase once its refund payment is settled
+	/// The housing fund gets back the refunded amount, the rest was paid to the seller
+	fn settle_escrow_refund(
+		payment_id: Payment::PaymentId,
+		payment: &Payment::PaymentDetail<T>,
+		recipient_share: Percent,
+	) {
+		if let Some((collection_id, item_id)) = EscrowRefunds::<T>::take(payment_id) {
+			EscrowPayments::<T>::remove(collection_id, item_id);
+			let refunded = payment.amount.saturating_sub(recipient_share.mul_floor(payment.amount));
+			if let Some(refunded) = Self::payment_to_hfund_balance(refunded) {
+				HousingFund::Pallet::<T>::settle_escrow_refund(collection_id, item_id, refunded)
+					.ok();
+			}
+			Self::deposit_event(Event::EscrowRefunded(payment.to.clone(), collection_id, item_id));
+		}
+	}
+
+	fn hfund_to_payment_balance(input: HousingFund::BalanceOf<T>) -> Option<Payment::BalanceOf<T>> {
+		let amount: u128 = input.try_into().ok()?;
+		amount.try_into().ok()
+	}
+
+	fn payment_to_hfund_balance(input: Payment::BalanceOf<T>) -> Option<HousingFund::BalanceOf<T>> {
+		let amount: u128 = input.try_into().ok()?;
+		amount.try_into().ok()
+	}
+
+	/// Get the closing checklist of a purchase, building it from the property type template if
+	/// it was not opened yet
+	fn get_or_open_checklist(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> Vec<ChecklistEntry<T>> {
+		match Self::checklists(collection_id, item_id) {
+			Some(checklist) => checklist,
+			None => {
+				let checklist = Self::checklist_templates(collection_id)
+					.into_iter()
+					.map(|(item, mandatory)| ChecklistEntry::<T>::new(item, mandatory))
+					.collect::<Vec<_>>();
+				Checklists::<T>::insert(collection_id, item_id, checklist.clone());
+				checklist
+			},
+		}
+	}
+
+	/// Get the sign-off of a purchase, assigning its notaries if it was not opened yet
+	fn get_or_assign_notaries(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> Result<SignOff<T>, DispatchError> {
+		match Self::sign_offs(collection_id, item_id) {
+			Some(sign_off) => Ok(sign_off),
+			None => Self::assign_notaries(collection_id, item_id),
+		}
+	}
+
 	fn get_possible_collection(collection_id: T::NftCollectionId) -> Nft::PossibleCollections {
 		let collections = all::<Nft::PossibleCollections>().collect::<Vec<_>>();
 		let mut possible_collection = Nft::PossibleCollections::HOUSES;
@@ -252,3 +940,19 @@ impl<T: Config> Pallet<T> {
 		possible_collection
 	}
 }
+
+impl<T: Config> Bidding::OnPurchaseFinalising<T::NftCollectionId, T::NftItemId> for Pallet<T> {
+	fn on_purchase_finalising(collection_id: T::NftCollectionId, item_id: T::NftItemId) {
+		Self::open_purchase(collection_id, item_id)
+	}
+}
+
+impl<T: Config> Payment::OnPaymentSettled<T> for Pallet<T> {
+	fn on_payment_settled(
+		payment_id: Payment::PaymentId,
+		payment: &Payment::PaymentDetail<T>,
+		recipient_share: Percent,
+	) {
+		Self::settle_escrow_refund(payment_id, payment, recipient_share)
+	}
+}
@@ -51,6 +51,7 @@ frame_support::construct_runtime!(
 		Assets: pallet_assets::{Pallet, Storage, Config<T>, Event<T>},
 		ShareDistributor: pallet_share_distributor::{Pallet, Call, Storage, Event<T>},
 		BiddingModule: pallet_bidding::{Pallet, Call, Storage, Event<T>},
+		PaymentModule: pallet_payment::{Pallet, Call, Storage, Event<T>},
 		FinalizerModule: pallet_finalizer::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -308,19 +309,28 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributor;
 	type Extra = ();
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const AssetsFees: Balance = 15000;
+	pub const MarketFee: Percent = Percent::from_percent(2);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(50);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxOwners: u32 = 50;
 }
 impl pallet_share_distributor::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = ();
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
@@ -342,11 +352,64 @@ impl pallet_bidding::Config for Test {
 	type MaximumSharePerInvestor = MaximumSharePerInvestor;
 	type MinimumSharePerInvestor = MinimumSharePerInvestor;
 	type NewAssetScanPeriod = NewAssetScanPeriod;
+	type OnPurchaseFinalising = FinalizerModule;
+}
+
+pub struct MockDisputeResolver;
+impl pallet_payment::DisputeResolver<AccountId> for MockDisputeResolver {
+	fn get_resolver_account() -> AccountId {
+		RESOLVER
+	}
+}
+
+pub struct MockFeeHandler;
+impl pallet_payment::FeeHandler<Test> for MockFeeHandler {
+	fn apply_fees(
+		_from: &AccountId,
+		_to: &AccountId,
+		_detail: &pallet_payment::PaymentDetail<Test>,
+		_remark: Option<&[u8]>,
+	) -> (AccountId, Percent) {
+		(RESOLVER, Percent::from_percent(0))
+	}
+}
+
+parameter_types! {
+	pub const IncentivePercentage: Percent = Percent::from_percent(10);
+	pub const MaxRemarkLength: u32 = 50;
+	pub const CancelBufferBlockLength: u64 = 5;
+	pub const MaxScheduledTaskListLength : u32 = 5;
+}
+
+impl pallet_payment::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type DisputeResolver = MockDisputeResolver;
+	type IncentivePercentage = IncentivePercentage;
+	type FeeHandler = MockFeeHandler;
+	type OnPaymentSettled = FinalizerModule;
+	type MaxRemarkLength = MaxRemarkLength;
+	type CancelBufferBlockLength = CancelBufferBlockLength;
+	type MaxScheduledTaskListLength = MaxScheduledTaskListLength;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const NotariesPerPurchase: u32 = 3;
+	pub static RequiredApprovals: u32 = 1;
+	pub const SignOffDelay: BlockNumber = 10;
+	pub const MaxPendingSignOffs: u32 = 10;
+	pub const MaxSignOffsPerBlock: u32 = 10;
 }
 
 impl pallet_finalizer::Config for Test {
 	type Event = Event;
 	type WeightInfo = ();
+	type NotariesPerPurchase = NotariesPerPurchase;
+	type RequiredApprovals = RequiredApprovals;
+	type SignOffDelay = SignOffDelay;
+	type MaxPendingSignOffs = MaxPendingSignOffs;
+	type MaxSignOffsPerBlock = MaxSignOffsPerBlock;
 }
 
 pub const ALICE: u64 = 1;
@@ -357,6 +420,7 @@ pub const EVE: u64 = 5;
 pub const AMANI: u64 = 6;
 pub const KEZIA: u64 = 7;
 pub const DAN: u64 = 8;
+pub const RESOLVER: u64 = 9;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
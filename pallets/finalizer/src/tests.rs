@@ -1,6 +1,6 @@
 use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight, BoundedVec};
 
 #[test]
 fn validate_transaction_asset_no_notary_role_should_fail() {
@@ -182,6 +182,8 @@ fn validate_transaction_asset_should_succeed() {
 			crate::Onboarding::AssetStatus::FINALISING
 		));
 
+		complete_checklist(collection_id, item_id, DAN);
+
 		assert_ok!(FinalizerModule::validate_transaction_asset(
 			Origin::signed(DAN),
 			collection_id,
@@ -250,6 +252,7 @@ fn reject_transaction_asset_no_notary_role_should_fail() {
 				Origin::signed(AMANI),
 				collection_id,
 				item_id,
+				None,
 			),
 			Error::<Test>::NotANotary
 		);
@@ -276,7 +279,7 @@ fn reject_transaction_asset_no_existing_house_should_fail() {
 		let collection_id = NftCollection::OFFICESTEST.value();
 
 		assert_noop!(
-			FinalizerModule::reject_transaction_asset(Origin::signed(DAN), collection_id, 1,),
+			FinalizerModule::reject_transaction_asset(Origin::signed(DAN), collection_id, 1, None),
 			Error::<Test>::AssetDoesNotExist
 		);
 	});
@@ -327,7 +330,12 @@ fn reject_transaction_asset_no_finalising_status_should_fail() {
 		let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
 
 		assert_noop!(
-			FinalizerModule::reject_transaction_asset(Origin::signed(DAN), collection_id, item_id,),
+			FinalizerModule::reject_transaction_asset(
+				Origin::signed(DAN),
+				collection_id,
+				item_id,
+				None,
+			),
 			Error::<Test>::HouseHasNotFinalisingStatus
 		);
 	});
@@ -415,6 +423,7 @@ fn reject_transaction_asset_should_succeed() {
 			Origin::signed(DAN),
 			collection_id,
 			item_id,
+			None,
 		));
 
 		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
@@ -639,6 +648,8 @@ fn cancel_transaction_asset_should_succeed() {
 		));
 		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), DAN));
 
+		complete_checklist(collection_id, item_id, DAN);
+
 		assert_ok!(FinalizerModule::validate_transaction_asset(
 			Origin::signed(DAN),
 			collection_id,
@@ -679,3 +690,915 @@ fn cancel_transaction_asset_should_succeed() {
 		);
 	});
 }
+
+#[test]
+fn validate_transaction_asset_threshold_should_succeed() {
+	new_test_ext().execute_with(|| {
+		RequiredApprovals::set(2);
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(KEZIA),
+			KEZIA,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+
+		let notaries = vec![DAN, BOB, CHARLIE, EVE];
+		for notary in notaries.clone() {
+			assert_ok!(RoleModule::set_role(
+				Origin::signed(notary),
+				notary,
+				crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+			));
+			assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), notary));
+		}
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(AMANI),
+			AMANI,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+
+		let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+			b"metadata0".to_vec().try_into().unwrap();
+
+		assert_ok!(NftModule::create_collection(
+			Origin::signed(KEZIA),
+			NftCollection::OFFICESTEST,
+			metadata.clone()
+		));
+
+		assert_ok!(OnboardingModule::create_and_submit_proposal(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			Some(100),
+			metadata,
+			false
+		));
+
+		let collection_id = NftCollection::OFFICESTEST.value();
+		let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+
+		assert_ok!(OnboardingModule::change_status(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			item_id,
+			crate::Onboarding::AssetStatus::FINALISING
+		));
+
+		// Notaries are assigned when the purchase gets FINALISING status
+		FinalizerModule::open_purchase(collection_id, item_id);
+
+		let sign_off = FinalizerModule::sign_offs(collection_id, item_id).unwrap();
+		assert_eq!(sign_off.notaries.len(), 3);
+		assert_eq!(sign_off.threshold, 2);
+		assert_eq!(sign_off.deadline, System::block_number() + 10);
+
+		let outsider = *notaries.iter().find(|n| !sign_off.notaries.contains(n)).unwrap();
+		let first = sign_off.notaries[0];
+		let second = sign_off.notaries[1];
+
+		complete_checklist(collection_id, item_id, first);
+
+		// A notary not assigned to the purchase can't validate it
+		assert_noop!(
+			FinalizerModule::validate_transaction_asset(
+				Origin::signed(outsider),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::NotAnAssignedNotary
+		);
+
+		// The first approval is not enough
+		assert_ok!(FinalizerModule::validate_transaction_asset(
+			Origin::signed(first),
+			collection_id,
+			item_id
+		));
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISING);
+		assert_eq!(
+			FinalizerModule::sign_offs(collection_id, item_id).unwrap().approvals,
+			vec![first]
+		);
+
+		assert_noop!(
+			FinalizerModule::validate_transaction_asset(
+				Origin::signed(first),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::AlreadyApproved
+		);
+
+		// The second approval validates the purchase
+		assert_ok!(FinalizerModule::validate_transaction_asset(
+			Origin::signed(second),
+			collection_id,
+			item_id
+		));
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISED);
+		assert!(FinalizerModule::sign_offs(collection_id, item_id).is_none());
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::FinalizerModule(crate::Event::NotaryValidatedAssetTransaction(
+				second,
+				collection_id,
+				item_id
+			))
+		);
+	});
+}
+
+#[test]
+fn sign_off_deadline_should_reject_asset() {
+	new_test_ext().execute_with(|| {
+		let mut block_number = System::block_number();
+		let amount = 100;
+
+		for account_id in 1..6 {
+			assert_ok!(RoleModule::set_role(
+				Origin::signed(account_id),
+				account_id,
+				crate::Onboarding::HousingFund::ROLES::Accounts::INVESTOR
+			));
+			assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(account_id), amount));
+
+			block_number = block_number.saturating_add(1);
+			System::set_block_number(block_number);
+		}
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(KEZIA),
+			KEZIA,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(AMANI),
+			AMANI,
+			crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+
+		let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+			b"metadata0".to_vec().try_into().unwrap();
+
+		assert_ok!(NftModule::create_collection(
+			Origin::signed(KEZIA),
+			NftCollection::OFFICESTEST,
+			metadata.clone()
+		));
+
+		assert_ok!(OnboardingModule::create_and_submit_proposal(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			Some(100),
+			metadata,
+			false
+		));
+
+		let collection_id = NftCollection::OFFICESTEST.value();
+		let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+
+		assert_ok!(OnboardingModule::change_status(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			item_id,
+			crate::Onboarding::AssetStatus::ONBOARDED
+		));
+
+		assert_ok!(BiddingModule::process_onboarded_assets());
+
+		// No notary is registered yet, so the purchase waits for one
+		FinalizerModule::on_initialize(block_number);
+		assert!(FinalizerModule::sign_offs(collection_id, item_id).is_none());
+		assert_eq!(
+			FinalizerModule::pending_sign_offs().into_inner(),
+			vec![(collection_id, item_id)]
+		);
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(DAN),
+			DAN,
+			crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), DAN));
+
+		FinalizerModule::on_initialize(block_number);
+		let sign_off = FinalizerModule::sign_offs(collection_id, item_id).unwrap();
+		assert_eq!(sign_off.notaries, vec![DAN]);
+		assert_eq!(sign_off.threshold, 1);
+		assert!(FinalizerModule::pending_sign_offs().is_empty());
+		assert_eq!(
+			FinalizerModule::sign_off_deadlines(sign_off.deadline).into_inner(),
+			vec![(collection_id, item_id)]
+		);
+
+		// Nothing happens before the deadline
+		System::set_block_number(sign_off.deadline - 1);
+		FinalizerModule::on_initialize(sign_off.deadline - 1);
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISING);
+
+		// The purchase is rejected once the deadline is reached
+		System::set_block_number(sign_off.deadline);
+		FinalizerModule::on_initialize(sign_off.deadline);
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::REJECTED);
+		assert!(FinalizerModule::sign_offs(collection_id, item_id).is_none());
+		assert!(FinalizerModule::sign_off_deadlines(sign_off.deadline).is_empty());
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::FinalizerModule(crate::Event::SignOffExpired(
+				collection_id,
+				item_id,
+				sign_off.deadline
+			))
+		);
+
+		// The reservation is released once the escrow refund is executed
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			crate::HousingFund::FundInfo {
+				total: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(400).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(100).unwrap(),
+			}
+		);
+
+		let refund_block = sign_off.deadline + 5;
+		System::set_block_number(refund_block);
+		PaymentModule::on_idle(refund_block, Weight::MAX);
+
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			crate::HousingFund::FundInfo {
+				total: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+		assert!(HousingFundModule::reservations((collection_id, item_id)).is_none());
+	});
+}
+
+fn prep_escrowed_purchase() -> (u32, u32) {
+	let mut block_number = System::block_number();
+
+	for account_id in 1..6 {
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(account_id),
+			account_id,
+			crate::Onboarding::HousingFund::ROLES::Accounts::INVESTOR
+		));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(account_id), 100));
+
+		block_number = block_number.saturating_add(1);
+		System::set_block_number(block_number);
+	}
+
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(KEZIA),
+		KEZIA,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(AMANI),
+		AMANI,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(DAN),
+		DAN,
+		crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), DAN));
+
+	let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+		b"metadata0".to_vec().try_into().unwrap();
+
+	assert_ok!(NftModule::create_collection(
+		Origin::signed(KEZIA),
+		NftCollection::OFFICESTEST,
+		metadata.clone()
+	));
+
+	assert_ok!(OnboardingModule::create_and_submit_proposal(
+		Origin::signed(AMANI),
+		NftCollection::OFFICESTEST,
+		Some(100),
+		metadata,
+		false
+	));
+
+	let collection_id = NftCollection::OFFICESTEST.value();
+	let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+
+	assert_ok!(OnboardingModule::change_status(
+		Origin::signed(AMANI),
+		NftCollection::OFFICESTEST,
+		item_id,
+		crate::Onboarding::AssetStatus::ONBOARDED
+	));
+
+	// The escrow is opened when the purchase gets FINALISING status
+	assert_ok!(BiddingModule::process_onboarded_assets());
+
+	(collection_id, item_id)
+}
+
+#[test]
+fn escrow_should_be_released_on_validation() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_escrowed_purchase();
+		let fund_account = HousingFundModule::fund_account_id();
+		let seller_total = Balances::total_balance(&AMANI);
+
+		assert_eq!(HousingFundModule::escrows((collection_id, item_id)), Some(AMANI));
+		let payment_id = FinalizerModule::escrow_payments(collection_id, item_id).unwrap();
+		let payment = PaymentModule::payment(payment_id).unwrap();
+		assert_eq!((payment.from, payment.to), (fund_account, AMANI));
+		assert_eq!(payment.amount, 100);
+		assert_eq!(payment.state, pallet_payment::PaymentState::Created);
+		// The escrowed amount is held on the seller account
+		assert!(Balances::reserved_balance(&AMANI) >= 100);
+
+		complete_checklist(collection_id, item_id, DAN);
+
+		assert_ok!(FinalizerModule::validate_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id
+		));
+		assert!(PaymentModule::payment(payment_id).is_none());
+		assert!(FinalizerModule::escrow_payments(collection_id, item_id).is_none());
+		// The housing fund paid the purchase amount only
+		assert_eq!(Balances::free_balance(&fund_account), 400);
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISED);
+
+		// The sale doesn't pay the seller a second time
+		assert_ok!(BiddingModule::process_finalised_assets());
+		assert_eq!(Balances::free_balance(&fund_account), 400);
+		assert_eq!(Balances::total_balance(&AMANI), seller_total);
+		assert!(HousingFundModule::escrows((collection_id, item_id)).is_none());
+	});
+}
+
+#[test]
+fn escrow_refund_dispute_should_be_resolved() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_escrowed_purchase();
+		let fund_account = HousingFundModule::fund_account_id();
+
+		let payment_id = FinalizerModule::escrow_payments(collection_id, item_id).unwrap();
+
+		// 100 escrowed, without incentive nor fee
+		assert_eq!(Balances::free_balance(&fund_account), 400);
+		let payment = PaymentModule::payment(payment_id).unwrap();
+		assert_eq!(payment.incentive_amount, 0);
+		assert_eq!(payment.fee_detail, None);
+
+		assert_ok!(FinalizerModule::reject_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id,
+			None
+		));
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::REJECTED);
+
+		// The reservation is kept until the refund is settled
+		assert_eq!(HousingFundModule::escrows((collection_id, item_id)), Some(AMANI));
+		assert_eq!(FinalizerModule::escrow_refunds(payment_id), Some((collection_id, item_id)));
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			crate::HousingFund::FundInfo {
+				total: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(400).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(100).unwrap(),
+			}
+		);
+
+		// The seller disputes the refund
+		assert!(matches!(
+			PaymentModule::payment(payment_id).unwrap().state,
+			pallet_payment::PaymentState::RefundRequested { .. }
+		));
+		assert_ok!(PaymentModule::dispute_refund(Origin::signed(AMANI), payment_id));
+		assert_eq!(
+			PaymentModule::payment(payment_id).unwrap().state,
+			pallet_payment::PaymentState::NeedsReview
+		);
+
+		// The resolver settles the dispute in favor of the housing fund
+		assert_ok!(PaymentModule::resolve_payment(
+			Origin::signed(RESOLVER),
+			payment_id,
+			sp_runtime::Percent::from_percent(0)
+		));
+		assert!(PaymentModule::payment(payment_id).is_none());
+		assert_eq!(Balances::free_balance(&fund_account), 500);
+		assert!(HousingFundModule::escrows((collection_id, item_id)).is_none());
+		assert!(HousingFundModule::reservations((collection_id, item_id)).is_none());
+		assert!(FinalizerModule::escrow_payments(collection_id, item_id).is_none());
+		assert!(FinalizerModule::escrow_refunds(payment_id).is_none());
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			crate::HousingFund::FundInfo {
+				total: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(500).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+	});
+}
+
+#[test]
+fn escrow_partial_refund_should_use_the_paid_part() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_escrowed_purchase();
+		let fund_account = HousingFundModule::fund_account_id();
+		let payment_id = FinalizerModule::escrow_payments(collection_id, item_id).unwrap();
+		let reservation = HousingFundModule::reservations((collection_id, item_id)).unwrap();
+
+		assert_ok!(FinalizerModule::reject_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id,
+			None
+		));
+		assert_ok!(PaymentModule::dispute_refund(Origin::signed(AMANI), payment_id));
+
+		// The resolver gives 40% of the escrow to the seller
+		assert_ok!(PaymentModule::resolve_payment(
+			Origin::signed(RESOLVER),
+			payment_id,
+			sp_runtime::Percent::from_percent(40)
+		));
+		assert_eq!(Balances::free_balance(&fund_account), 460);
+		assert!(HousingFundModule::reservations((collection_id, item_id)).is_none());
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			crate::HousingFund::FundInfo {
+				total: HousingFundModule::u64_to_balance_option(460).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(460).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+
+		// The paid part is used from the contributions of the reservation
+		let mut used = 0;
+		for (account_id, _) in reservation.contributions {
+			let contribution = HousingFundModule::contributions(account_id).unwrap();
+			assert_eq!(contribution.reserved_balance, 0);
+			used += contribution.contributed_balance;
+		}
+		assert_eq!(used, 40);
+	});
+}
+
+#[test]
+fn seller_cancellation_should_refund_escrow() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_escrowed_purchase();
+		let fund_account = HousingFundModule::fund_account_id();
+		let payment_id = FinalizerModule::escrow_payments(collection_id, item_id).unwrap();
+
+		assert_ok!(FinalizerModule::cancel_transaction_asset(
+			Origin::signed(AMANI),
+			collection_id,
+			item_id
+		));
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::CANCELLED);
+		assert!(FinalizerModule::sign_offs(collection_id, item_id).is_none());
+		assert!(HousingFundModule::escrows((collection_id, item_id)).is_none());
+		assert!(PaymentModule::payment(payment_id).is_none());
+		assert!(FinalizerModule::escrow_payments(collection_id, item_id).is_none());
+		assert_eq!(Balances::free_balance(&fund_account), 500);
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::FinalizerModule(crate::Event::SellerCancelledAssetTransaction(
+				AMANI,
+				collection_id,
+				item_id
+			))
+		);
+	});
+}
+
+fn complete_checklist(collection_id: u32, item_id: u32, who: u64) {
+	for (item, _) in FinalizerModule::checklist_templates(collection_id) {
+		assert_ok!(FinalizerModule::complete_checklist_item(
+			Origin::signed(who),
+			collection_id,
+			item_id,
+			item,
+			sp_core::H256::repeat_byte(1)
+		));
+	}
+}
+
+fn prep_finalising_purchase() -> (u32, u32) {
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(KEZIA),
+		KEZIA,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(DAN),
+		DAN,
+		crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), DAN));
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(AMANI),
+		AMANI,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+
+	let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+		b"metadata0".to_vec().try_into().unwrap();
+
+	assert_ok!(NftModule::create_collection(
+		Origin::signed(KEZIA),
+		NftCollection::OFFICESTEST,
+		metadata.clone()
+	));
+
+	assert_ok!(OnboardingModule::create_and_submit_proposal(
+		Origin::signed(AMANI),
+		NftCollection::OFFICESTEST,
+		Some(100),
+		metadata,
+		false
+	));
+
+	let collection_id = NftCollection::OFFICESTEST.value();
+	let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+
+	// The closing checklist of the offices
+	assert_noop!(
+		FinalizerModule::set_checklist_template(
+			Origin::signed(ALICE),
+			collection_id,
+			vec![(crate::ClosingItem::TitleVerified, true)]
+		),
+		sp_runtime::DispatchError::BadOrigin
+	);
+	assert_noop!(
+		FinalizerModule::set_checklist_template(
+			Origin::root(),
+			collection_id,
+			vec![
+				(crate::ClosingItem::TitleVerified, true),
+				(crate::ClosingItem::TitleVerified, false)
+			]
+		),
+		Error::<Test>::DuplicateChecklistItem
+	);
+	assert_noop!(
+		FinalizerModule::set_checklist_template(
+			Origin::root(),
+			collection_id,
+			vec![(crate::ClosingItem::TaxPaid, false)]
+		),
+		Error::<Test>::NoMandatoryChecklistItem
+	);
+	assert_noop!(
+		FinalizerModule::set_checklist_template(Origin::root(), collection_id, vec![]),
+		Error::<Test>::NoMandatoryChecklistItem
+	);
+	assert_ok!(FinalizerModule::set_checklist_template(
+		Origin::root(),
+		collection_id,
+		vec![
+			(crate::ClosingItem::TitleVerified, true),
+			(crate::ClosingItem::LiensCleared, true),
+			(crate::ClosingItem::TaxPaid, false)
+		]
+	));
+
+	assert_ok!(OnboardingModule::change_status(
+		Origin::signed(AMANI),
+		NftCollection::OFFICESTEST,
+		item_id,
+		crate::Onboarding::AssetStatus::FINALISING
+	));
+
+	// The checklist is opened when the purchase gets FINALISING status
+	FinalizerModule::open_purchase(collection_id, item_id);
+
+	(collection_id, item_id)
+}
+
+#[test]
+fn closing_checklist_should_gate_validation() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_finalising_purchase();
+		let evidence = sp_core::H256::repeat_byte(1);
+
+		let checklist = FinalizerModule::checklists(collection_id, item_id).unwrap();
+		assert_eq!(checklist.len(), 3);
+		assert!(checklist.iter().all(|entry| !entry.is_completed()));
+
+		assert_noop!(
+			FinalizerModule::validate_transaction_asset(
+				Origin::signed(DAN),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::MandatoryChecklistItemsPending
+		);
+
+		// Only notaries and servicers complete the checklist
+		assert_noop!(
+			FinalizerModule::complete_checklist_item(
+				Origin::signed(AMANI),
+				collection_id,
+				item_id,
+				crate::ClosingItem::TitleVerified,
+				evidence
+			),
+			Error::<Test>::NotANotaryOrServicer
+		);
+		assert_noop!(
+			FinalizerModule::complete_checklist_item(
+				Origin::signed(KEZIA),
+				collection_id,
+				item_id,
+				crate::ClosingItem::InspectionDone,
+				evidence
+			),
+			Error::<Test>::NotInChecklist
+		);
+
+		assert_ok!(FinalizerModule::complete_checklist_item(
+			Origin::signed(KEZIA),
+			collection_id,
+			item_id,
+			crate::ClosingItem::TitleVerified,
+			evidence
+		));
+		assert_noop!(
+			FinalizerModule::complete_checklist_item(
+				Origin::signed(DAN),
+				collection_id,
+				item_id,
+				crate::ClosingItem::TitleVerified,
+				evidence
+			),
+			Error::<Test>::ChecklistItemAlreadyCompleted
+		);
+
+		let entry = FinalizerModule::checklists(collection_id, item_id).unwrap()[0].clone();
+		assert_eq!(entry.item, crate::ClosingItem::TitleVerified);
+		assert_eq!(entry.completed_by, Some(KEZIA));
+		assert_eq!(entry.evidence, Some(evidence));
+		assert_eq!(entry.completed_at, Some(System::block_number()));
+
+		// A mandatory item is still pending
+		assert_noop!(
+			FinalizerModule::validate_transaction_asset(
+				Origin::signed(DAN),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::MandatoryChecklistItemsPending
+		);
+
+		assert_ok!(FinalizerModule::complete_checklist_item(
+			Origin::signed(DAN),
+			collection_id,
+			item_id,
+			crate::ClosingItem::LiensCleared,
+			evidence
+		));
+
+		// The optional item doesn't block the validation
+		assert_ok!(FinalizerModule::validate_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id
+		));
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISED);
+		// The checklist is removed with the completed purchase
+		assert!(FinalizerModule::checklists(collection_id, item_id).is_none());
+	});
+}
+
+#[test]
+fn closing_checklist_should_default_to_every_item_mandatory() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_finalising_purchase();
+		let apartments = NftCollection::APPARTMENTSTEST.value();
+
+		// A property type without template gets every checklist item, all mandatory
+		let template = FinalizerModule::checklist_templates(apartments);
+		assert_eq!(template.len(), 4);
+		assert!(template.iter().all(|(_, mandatory)| *mandatory));
+
+		assert_eq!(FinalizerModule::checklist_templates(collection_id).len(), 3);
+		assert_ok!(FinalizerModule::cancel_transaction_asset(
+			Origin::signed(AMANI),
+			collection_id,
+			item_id
+		));
+		assert!(FinalizerModule::checklists(collection_id, item_id).is_none());
+	});
+}
+
+#[test]
+fn reject_transaction_asset_should_record_failed_item() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, item_id) = prep_finalising_purchase();
+
+		assert_noop!(
+			FinalizerModule::reject_transaction_asset(
+				Origin::signed(DAN),
+				collection_id,
+				item_id,
+				Some(crate::ClosingItem::InspectionDone)
+			),
+			Error::<Test>::NotInChecklist
+		);
+
+		assert_ok!(FinalizerModule::reject_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id,
+			Some(crate::ClosingItem::LiensCleared)
+		));
+
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::REJECTED);
+		assert_eq!(
+			FinalizerModule::failed_checklist_items(collection_id, item_id),
+			Some((DAN, crate::ClosingItem::LiensCleared))
+		);
+		assert!(FinalizerModule::checklists(collection_id, item_id).is_none());
+	});
+}
+
+fn prep_finalising_houses(count: u32) -> (u32, Vec<u32>) {
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(KEZIA),
+		KEZIA,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SERVICER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), KEZIA));
+	assert_ok!(RoleModule::set_role(
+		Origin::signed(AMANI),
+		AMANI,
+		crate::Onboarding::HousingFund::ROLES::Accounts::SELLER
+	));
+	assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), AMANI));
+
+	let metadata: BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit> =
+		b"metadata0".to_vec().try_into().unwrap();
+
+	assert_ok!(NftModule::create_collection(
+		Origin::signed(KEZIA),
+		NftCollection::OFFICESTEST,
+		metadata.clone()
+	));
+
+	let collection_id = NftCollection::OFFICESTEST.value();
+	let mut items = Vec::new();
+	for _ in 0..count {
+		assert_ok!(OnboardingModule::create_and_submit_proposal(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			Some(100),
+			metadata.clone(),
+			false
+		));
+		let item_id = pallet_nft::ItemsCount::<Test>::get()[collection_id as usize] - 1;
+		assert_ok!(OnboardingModule::change_status(
+			Origin::signed(AMANI),
+			NftCollection::OFFICESTEST,
+			item_id,
+			crate::Onboarding::AssetStatus::FINALISING
+		));
+		items.push(item_id);
+	}
+
+	(collection_id, items)
+}
+
+#[test]
+fn sign_off_should_wait_for_required_notaries() {
+	new_test_ext().execute_with(|| {
+		RequiredApprovals::set(2);
+		let (collection_id, items) = prep_finalising_houses(1);
+		let item_id = items[0];
+
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(DAN),
+			DAN,
+			crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), DAN));
+
+		// A single notary can't reach the required approvals
+		FinalizerModule::open_purchase(collection_id, item_id);
+		assert!(FinalizerModule::sign_offs(collection_id, item_id).is_none());
+		assert_eq!(
+			FinalizerModule::pending_sign_offs().into_inner(),
+			vec![(collection_id, item_id)]
+		);
+
+		complete_checklist(collection_id, item_id, DAN);
+
+		assert_noop!(
+			FinalizerModule::validate_transaction_asset(
+				Origin::signed(DAN),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::NotEnoughNotaries
+		);
+
+		// The sign-off is opened once a second notary is registered
+		assert_ok!(RoleModule::set_role(
+			Origin::signed(BOB),
+			BOB,
+			crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+		));
+		assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), BOB));
+
+		FinalizerModule::on_initialize(System::block_number());
+		let sign_off = FinalizerModule::sign_offs(collection_id, item_id).unwrap();
+		assert_eq!(sign_off.notaries.len(), 2);
+		assert_eq!(sign_off.threshold, 2);
+		assert!(FinalizerModule::pending_sign_offs().is_empty());
+
+		// One approval doesn't validate the purchase
+		assert_ok!(FinalizerModule::validate_transaction_asset(
+			Origin::signed(DAN),
+			collection_id,
+			item_id
+		));
+		let house = OnboardingModule::houses(collection_id, item_id).unwrap();
+		assert_eq!(house.status, crate::Onboarding::AssetStatus::FINALISING);
+	});
+}
+
+#[test]
+fn notaries_assignment_should_rotate() {
+	new_test_ext().execute_with(|| {
+		let (collection_id, items) = prep_finalising_houses(2);
+
+		for notary in [DAN, BOB, CHARLIE, EVE] {
+			assert_ok!(RoleModule::set_role(
+				Origin::signed(notary),
+				notary,
+				crate::Onboarding::HousingFund::ROLES::Accounts::NOTARY
+			));
+			assert_ok!(RoleModule::account_approval(Origin::signed(ALICE), notary));
+		}
+		let registry = RoleModule::notary_accounts();
+
+		FinalizerModule::open_purchase(collection_id, items[0]);
+		FinalizerModule::open_purchase(collection_id, items[1]);
+
+		let first = FinalizerModule::sign_offs(collection_id, items[0]).unwrap();
+		let second = FinalizerModule::sign_offs(collection_id, items[1]).unwrap();
+		assert_eq!(first.notaries, registry[0..3].to_vec());
+		assert_eq!(second.notaries, vec![registry[3], registry[0], registry[1]]);
+		assert_eq!(FinalizerModule::notary_cursor(), 2);
+	});
+}
@@ -0,0 +1,85 @@
+pub use super::*;
+pub use frame_support::{
+	codec::{Decode, Encode},
+	inherent::Vec,
+	pallet_prelude::*,
+};
+pub use scale_info::TypeInfo;
+
+pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
+
+///Notaries sign-off of a purchase in FINALISING status
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SignOff<T: Config> {
+	///Notaries assigned to the purchase
+	pub notaries: Vec<T::AccountId>,
+	///Assigned notaries who validated the purchase
+	pub approvals: Vec<T::AccountId>,
+	///Number of approvals needed to validate the purchase
+	pub threshold: u32,
+	///Block after which the purchase is automatically rejected
+	pub deadline: BlockNumberOf<T>,
+}
+
+impl<T: Config> SignOff<T> {
+	pub fn new(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		notaries: Vec<T::AccountId>,
+		deadline: BlockNumberOf<T>,
+	) -> Self {
+		let sign_off = SignOff::<T> {
+			notaries,
+			approvals: Vec::new(),
+			threshold: T::RequiredApprovals::get(),
+			deadline,
+		};
+		SignOffs::<T>::insert(collection_id, item_id, sign_off.clone());
+		sign_off
+	}
+}
+
+///Items of the closing checklist of a purchase
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ClosingItem {
+	TitleVerified,
+	LiensCleared,
+	InspectionDone,
+	TaxPaid,
+}
+
+///Closing checklist item of a purchase in FINALISING status
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ChecklistEntry<T: Config> {
+	///Checklist item
+	pub item: ClosingItem,
+	///Validation of the purchase needs the item to be completed
+	pub mandatory: bool,
+	///Notary or servicer who completed the item
+	pub completed_by: Option<T::AccountId>,
+	///Hash of the evidence provided for the item
+	pub evidence: Option<T::Hash>,
+	///Block at which the item was completed
+	pub completed_at: Option<BlockNumberOf<T>>,
+}
+
+impl<T: Config> ChecklistEntry<T> {
+	pub fn new(item: ClosingItem, mandatory: bool) -> Self {
+		ChecklistEntry::<T> {
+			item,
+			mandatory,
+			completed_by: None,
+			evidence: None,
+			completed_at: None,
+		}
+	}
+
+	pub fn is_completed(&self) -> bool {
+		self.completed_by.is_some()
+	}
+}
@@ -1,4 +1,5 @@
 pub use crate::structs::*;
+use frame_support::sp_runtime::traits::SaturatedConversion;
 
 impl<T: Config> Pallet<T> {
 	// Conversion of u64 to BalanxceOf<T>
@@ -142,7 +143,10 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let mut fund = FundBalance::<T>::get();
-		T::LocalCurrency::unreserve(&Self::fund_account_id(), reservation.amount);
+		// An escrowed amount is refunded by the payment, not held in the fund reserve
+		if Escrows::<T>::take((nft_collection_id, nft_item_id)).is_none() {
+			T::LocalCurrency::unreserve(&Self::fund_account_id(), reservation.amount);
+		}
 		fund.unreserve(reservation.amount);
 
 		Reservations::<T>::remove((nft_collection_id, nft_item_id));
@@ -166,6 +170,96 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
+	/// Move the reserved amount of a house bid out of the fund reserve, so it can be held in an
+	/// escrow payment to the seller
+	/// - seller : account of the house seller
+	/// Emits FundEscrowedForPurchase when successful
+	pub fn escrow_house_bidding(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		seller: AccountIdOf<T>,
+	) -> DispatchResultWithPostInfo {
+		let reservation_wrap = Reservations::<T>::get((nft_collection_id, nft_item_id));
+
+		ensure!(reservation_wrap.is_some(), Error::<T>::NoFundReservationFound);
+		ensure!(
+			!Escrows::<T>::contains_key((nft_collection_id, nft_item_id)),
+			Error::<T>::FundAlreadyEscrowed
+		);
+
+		let reservation = reservation_wrap.unwrap();
+
+		// The amount is unreserved in the currency pallet, the escrow reserves it again
+		T::LocalCurrency::unreserve(&Self::fund_account_id(), reservation.amount);
+		Escrows::<T>::insert((nft_collection_id, nft_item_id), seller.clone());
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundEscrowedForPurchase(
+			seller,
+			nft_collection_id,
+			nft_item_id,
+			reservation.amount,
+			block_number,
+		));
+
+		Ok(().into())
+	}
+
+	/// Close the escrow of a house bid once its refund is settled
+	/// The refunded amount goes back to the contributions, the rest was paid to the seller and is
+	/// used as for a purchase, in proportion of the contributions
+	/// - refunded : amount refunded to the fund by the escrow payment
+	/// Emits FundEscrowRefundSettled when successful
+	pub fn settle_escrow_refund(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		refunded: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		let reservation = Reservations::<T>::get((nft_collection_id, nft_item_id))
+			.ok_or(Error::<T>::NoFundReservationFound)?;
+		let refunded = refunded.min(reservation.amount);
+		let paid = reservation.amount - refunded;
+
+		let (parts, _) = Self::share_by_weight(reservation.contributions.clone(), paid, true);
+		for (account_id, balance) in reservation.contributions.into_iter() {
+			let used = parts
+				.iter()
+				.find(|(part_account, _)| *part_account == account_id)
+				.map_or_else(Zero::zero, |(_, part)| *part);
+			Contributions::<T>::mutate(account_id, |val| {
+				if let Some(contribution) = val {
+					contribution.use_reserved_amount(used);
+					contribution.unreserve_amount(balance - used);
+				}
+			});
+		}
+
+		let mut fund = FundBalance::<T>::get();
+		// The paid part is tagged as used, the refunded part is back in the pot
+		fund.use_reserved(paid);
+		fund.unreserve(refunded);
+		FundBalance::<T>::put(fund);
+
+		Reservations::<T>::remove((nft_collection_id, nft_item_id));
+		Escrows::<T>::remove((nft_collection_id, nft_item_id));
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundEscrowRefundSettled(
+			nft_collection_id,
+			nft_item_id,
+			refunded,
+			block_number,
+		));
+
+		Ok(().into())
+	}
+
 	/// Unreserved the amount of the house in the Housing fund
 	pub fn unreserve_house_bidding_amount(
 		nft_collection_id: NftCollectionId<T>,
@@ -224,6 +318,7 @@ impl<T: Config> Pallet<T> {
 
 		// Delete from reservation
 		Reservations::<T>::remove((nft_collection_id, nft_item_id));
+		Escrows::<T>::remove((nft_collection_id, nft_item_id));
 		// Add to purchased operations
 		Purchases::<T>::insert((nft_collection_id, nft_item_id), reservation.clone());
 
@@ -245,4 +340,275 @@ impl<T: Config> Pallet<T> {
 
 		Ok(().into())
 	}
+
+	/// Buy back ownership shares of an asset from one of its owners
+	/// The amount is taken from the transferable part of the fund, and the
+	/// contributors available balances are used proportionally
+	/// - nft_collection_id : id of a collection of house type
+	/// - nft_item_id : id of the house in the collection
+	/// - seller : account of the owner selling its shares
+	/// - amount : amount paid for the shares
+	/// Emits FundBuybackExecuted when successful
+	pub fn buyback_shares(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		seller: AccountIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		// Check that the fund can afford the buyback
+		let mut fund = FundBalance::<T>::get();
+		ensure!(fund.can_take_off(amount), Error::<T>::NotEnoughFundForBuyback);
+
+		// Each contributor pays its part of the shares, without exceeding its available balance
+		let available = Self::get_contributions()
+			.into_iter()
+			.map(|(account_id, contribution)| (account_id, contribution.available_balance))
+			.collect::<Vec<_>>();
+		let (parts, left) = Self::share_by_weight(available, amount, true);
+		ensure!(left.is_zero(), Error::<T>::NotEnoughFundForBuyback);
+		debug_assert!(
+			parts.iter().fold(BalanceOf::<T>::zero(), |total, (_, part)| total + *part) == amount
+		);
+
+		T::LocalCurrency::transfer(
+			&Self::fund_account_id(),
+			&seller,
+			amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		for (account_id, part) in parts.into_iter() {
+			Contributions::<T>::mutate(account_id, |val| {
+				let mut unwrap_val = val.clone().unwrap();
+				unwrap_val.available_balance = unwrap_val.available_balance.saturating_sub(part);
+				unwrap_val.contributed_balance += part;
+				let contribution = unwrap_val.clone();
+				*val = Some(contribution);
+			});
+		}
+
+		fund.withdraw_transferable(amount);
+
+		// The amount is removed from the pot
+		FundBalance::<T>::mutate(|val| {
+			*val = fund.clone();
+		});
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundBuybackExecuted(
+			seller,
+			nft_collection_id,
+			nft_item_id,
+			amount,
+			block_number,
+		));
+
+		Ok(().into())
+	}
+
+	/// Record the sale of ownership shares held by the fund
+	/// The proceeds, already transferred to the fund account, are added to the transferable
+	/// part of the fund, and given back to the contributors in proportion of their
+	/// contributed balances
+	/// - nft_collection_id : id of a collection of house type
+	/// - nft_item_id : id of the house in the collection
+	/// - buyer : account buying the shares
+	/// - amount : amount paid for the shares
+	/// Emits FundSharesSold when successful
+	pub fn record_shares_sale(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		buyer: AccountIdOf<T>,
+		amount: BalanceOf<T>,
+	) {
+		// Each contributor gets back its part of the shares
+		Self::credit_contributors(amount, true);
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundSharesSold(
+			buyer,
+			nft_collection_id,
+			nft_item_id,
+			amount,
+			block_number,
+		));
+	}
+
+	/// Resell ownership shares held by the fund to a list of investors
+	/// The investors pay the shares from their available balances, and the amount is given
+	/// back to the contributors in proportion of their contributed balances
+	/// - nft_collection_id : id of a collection of house type
+	/// - nft_item_id : id of the house in the collection
+	/// - contributions : list of investors contributions
+	/// Emits FundSharesResold when successful
+	pub fn resell_shares(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		contributions: Vec<(AccountIdOf<T>, BalanceOf<T>)>,
+	) -> DispatchResultWithPostInfo {
+		// Check the number of investors
+		ensure!(
+			contributions.len() <= T::MaxInvestorPerHouse::get().try_into().unwrap(),
+			Error::<T>::NotMoreThanMaxInvestorPerHouse
+		);
+
+		// Checks that each contribution is possible
+		let mut amount = BalanceOf::<T>::zero();
+		for (account_id, balance) in contributions.iter() {
+			let entry = Contributions::<T>::get(account_id).ok_or(Error::<T>::NotAContributor)?;
+			ensure!(entry.can_reserve(*balance), Error::<T>::NotEnoughAvailableBalance);
+			amount += *balance;
+		}
+
+		// Each contributor gets back its part of the shares
+		Self::credit_contributors(amount, true);
+
+		// The investors pay the shares with their available balances
+		for (account_id, balance) in contributions.into_iter() {
+			Contributions::<T>::mutate(account_id, |val| {
+				let mut unwrap_val = val.clone().unwrap();
+				unwrap_val.available_balance -= balance;
+				unwrap_val.contributed_balance += balance;
+				let contribution = unwrap_val.clone();
+				*val = Some(contribution);
+			});
+		}
+
+		// The amount stays in the pot, but is no longer transferable
+		let mut fund = FundBalance::<T>::get();
+		fund.withdraw_transferable(amount);
+		FundBalance::<T>::mutate(|val| {
+			*val = fund.clone();
+		});
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundSharesResold(
+			nft_collection_id,
+			nft_item_id,
+			amount,
+			block_number,
+		));
+
+		Ok(().into())
+	}
+
+	/// Record the rent dividends received by the fund for the ownership shares it holds
+	/// The dividends, already transferred to the fund account, are added to the transferable
+	/// part of the fund, and given to the contributors in proportion of their contributed
+	/// balances
+	/// - nft_collection_id : id of a collection of house type
+	/// - nft_item_id : id of the house in the collection
+	/// - amount : amount of the dividends
+	/// Emits FundDividendsReceived when successful
+	pub fn record_dividends(
+		nft_collection_id: NftCollectionId<T>,
+		nft_item_id: NftItemId<T>,
+		amount: BalanceOf<T>,
+	) {
+		Self::credit_contributors(amount, false);
+
+		// Get the block number for timestamp
+		let block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Emit an event.
+		Self::deposit_event(Event::FundDividendsReceived(
+			nft_collection_id,
+			nft_item_id,
+			amount,
+			block_number,
+		));
+	}
+
+	/// Add an amount received by the fund account to the transferable fund, and to the
+	/// available balances of the contributors, in proportion of their contributed balances.
+	/// When `release` is set, the parts are also taken off the contributed balances.
+	fn credit_contributors(amount: BalanceOf<T>, release: bool) {
+		let mut fund = FundBalance::<T>::get();
+
+		let contributed = Self::get_contributions()
+			.into_iter()
+			.map(|(account_id, contribution)| (account_id, contribution.contributed_balance))
+			.collect::<Vec<_>>();
+		let (parts, left) = Self::share_by_weight(contributed.clone(), amount, release);
+		Self::credit_parts(parts, release);
+		// The gains above the contributed balances are only added to the available balances
+		if release && !left.is_zero() {
+			let (gains, _) = Self::share_by_weight(contributed, left, false);
+			Self::credit_parts(gains, false);
+		}
+
+		fund.contribute_transferable(amount);
+
+		// The amount is added to the pot
+		FundBalance::<T>::mutate(|val| {
+			*val = fund.clone();
+		});
+	}
+
+	fn credit_parts(parts: Vec<(AccountIdOf<T>, BalanceOf<T>)>, release: bool) {
+		for (account_id, part) in parts.into_iter() {
+			Contributions::<T>::mutate(account_id, |val| {
+				let mut unwrap_val = val.clone().unwrap();
+				if release {
+					unwrap_val.contributed_balance =
+						unwrap_val.contributed_balance.saturating_sub(part);
+				}
+				unwrap_val.available_balance += part;
+				let contribution = unwrap_val.clone();
+				*val = Some(contribution);
+			});
+		}
+	}
+
+	/// Share an amount between accounts in proportion of their weights
+	/// The parts are rounded down, and the rounding remainder is added to the parts of the
+	/// largest weights, so that the parts add up to the amount. When `capped` is set, no part
+	/// exceeds the weight of its account.
+	/// Returns the non-zero parts, and the amount that could not be shared
+	pub fn share_by_weight(
+		weights: Vec<(AccountIdOf<T>, BalanceOf<T>)>,
+		amount: BalanceOf<T>,
+		capped: bool,
+	) -> (Vec<(AccountIdOf<T>, BalanceOf<T>)>, BalanceOf<T>) {
+		let total = weights
+			.iter()
+			.fold(0u128, |total, (_, weight)| total.saturating_add(weight.saturated_into()));
+		if total == 0 {
+			return (Vec::new(), amount)
+		}
+		let amount0 = amount.saturated_into::<u128>();
+
+		let mut parts = weights
+			.into_iter()
+			.map(|(account_id, weight)| {
+				let weight0 = weight.saturated_into::<u128>();
+				(account_id, weight0, amount0.saturating_mul(weight0) / total)
+			})
+			.collect::<Vec<_>>();
+		parts.sort_by(|(_, a, _), (_, b, _)| b.cmp(a));
+
+		let shared = parts.iter().fold(0u128, |total, (_, _, part)| total.saturating_add(*part));
+		let mut left = amount0.saturating_sub(shared);
+		for (_, weight, part) in parts.iter_mut() {
+			let extra = if capped { left.min(weight.saturating_sub(*part)) } else { left };
+			*part = part.saturating_add(extra);
+			left = left.saturating_sub(extra);
+		}
+
+		let parts = parts
+			.into_iter()
+			.filter(|(_, _, part)| *part > 0)
+			.map(|(account_id, _, part)| (account_id, part.saturated_into()))
+			.collect();
+		(parts, left.saturated_into())
+	}
 }
@@ -54,8 +54,8 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + NFT::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type LocalCurrency: frame_support::traits::Currency<Self::AccountId>
-			+ frame_support::traits::ReservableCurrency<Self::AccountId>;
+		type LocalCurrency: frame_support::traits::Currency<Self::AccountId> +
+			frame_support::traits::ReservableCurrency<Self::AccountId>;
 		type MinContribution: Get<BalanceOf<Self>>;
 		type FundThreshold: Get<BalanceOf<Self>>;
 		type MaxFundContribution: Get<BalanceOf<Self>>;
@@ -112,6 +112,17 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn escrows)]
+	// Reservations moved into an escrow payment to the house seller
+	pub type Escrows<T> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(NftCollectionId<T>, NftItemId<T>),
+		AccountIdOf<T>,
+		OptionQuery,
+	>;
+
 	// Pallets use events to inform users when important changes are made.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -130,6 +141,36 @@ pub mod pallet {
 		FundReservationCancelled(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
 		PurchaseFundValidated(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
 		FundUnreservedForPurchase(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
+		/// The reserved fund of a purchase was moved into an escrow to the seller
+		FundEscrowedForPurchase(
+			AccountIdOf<T>,
+			T::NftCollectionId,
+			T::NftItemId,
+			BalanceOf<T>,
+			BlockNumberOf<T>,
+		),
+		/// Ownership shares were bought back by the fund
+		FundBuybackExecuted(
+			AccountIdOf<T>,
+			T::NftCollectionId,
+			T::NftItemId,
+			BalanceOf<T>,
+			BlockNumberOf<T>,
+		),
+		/// Ownership shares held by the fund were sold
+		FundSharesSold(
+			AccountIdOf<T>,
+			T::NftCollectionId,
+			T::NftItemId,
+			BalanceOf<T>,
+			BlockNumberOf<T>,
+		),
+		/// Rent dividends were received for the ownership shares held by the fund
+		FundDividendsReceived(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
+		/// The escrow refund of a purchase was settled, with the refunded amount
+		FundEscrowRefundSettled(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
+		/// Ownership shares held by the fund were resold to a list of investors
+		FundSharesResold(T::NftCollectionId, T::NftItemId, BalanceOf<T>, BlockNumberOf<T>),
 	}
 
 	// Errors inform users that something went wrong.
@@ -159,6 +200,10 @@ pub mod pallet {
 		NotMoreThanMaxInvestorPerHouse,
 		/// The reservation doesn't exist in the storage
 		NoFundReservationFound,
+		/// Not enough in the fund to buy back the shares
+		NotEnoughFundForBuyback,
+		/// The reservation is already held in an escrow
+		FundAlreadyEscrowed,
 	}
 
 	#[pallet::call]
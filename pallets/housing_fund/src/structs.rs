@@ -5,7 +5,10 @@ pub use frame_support::{
 	dispatch::DispatchResult,
 	inherent::Vec,
 	pallet_prelude::*,
-	sp_runtime::traits::{AccountIdConversion, Zero},
+	sp_runtime::{
+		traits::{AccountIdConversion, Saturating, Zero},
+		Perbill,
+	},
 	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
 	transactional, PalletId,
 };
@@ -980,6 +980,90 @@ fn unreserve_house_bidding_amount_with_valid_values_should_succeed() {
 	});
 }
 
+#[test]
+fn escrow_house_bidding_should_succeed() {
+	new_test_ext().execute_with(|| {
+		let fund_account_id = HousingFundModule::fund_account_id();
+
+		// Try to escrow a bidding that doesn't exist
+		assert_noop!(
+			HousingFundModule::escrow_house_bidding(1, 1, 3),
+			Error::<Test>::NoFundReservationFound
+		);
+
+		// Give the investor role to the accounts
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(RoleModule::set_role(Origin::signed(2), 2, crate::ROLES::Accounts::INVESTOR));
+
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(2), 40));
+
+		assert_ok!(HousingFundModule::house_bidding(1, 1, 60, vec![(1, 30), (2, 30)]));
+		assert_eq!(Balances::reserved_balance(&fund_account_id), 60);
+
+		assert_ok!(HousingFundModule::escrow_house_bidding(1, 1, 3));
+		assert_eq!(HousingFundModule::escrows((1, 1)), Some(3));
+		assert_eq!(Balances::reserved_balance(&fund_account_id), 0);
+
+		assert_noop!(
+			HousingFundModule::escrow_house_bidding(1, 1, 3),
+			Error::<Test>::FundAlreadyEscrowed
+		);
+
+		// Cancelling the bidding clears the escrow without touching the fund reserve
+		assert_ok!(HousingFundModule::cancel_house_bidding(1, 1));
+		assert_eq!(HousingFundModule::escrows((1, 1)), None);
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			FundInfo {
+				total: HousingFundModule::u64_to_balance_option(80).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(80).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+	});
+}
+
+#[test]
+fn settle_escrow_refund_should_use_the_paid_part() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			HousingFundModule::settle_escrow_refund(1, 1, 20),
+			Error::<Test>::NoFundReservationFound
+		);
+
+		// Give the investor role to the accounts
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(RoleModule::set_role(Origin::signed(2), 2, crate::ROLES::Accounts::INVESTOR));
+
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(2), 40));
+
+		assert_ok!(HousingFundModule::house_bidding(1, 1, 60, vec![(1, 30), (2, 30)]));
+		assert_ok!(HousingFundModule::escrow_house_bidding(1, 1, 3));
+
+		// 20 of the 60 escrowed are refunded, the seller kept 40
+		assert_ok!(HousingFundModule::settle_escrow_refund(1, 1, 20));
+		assert_eq!(HousingFundModule::escrows((1, 1)), None);
+		assert_eq!(HousingFundModule::reservations((1, 1)), None);
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			FundInfo {
+				total: HousingFundModule::u64_to_balance_option(40).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(40).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+
+		for account_id in [1, 2] {
+			let contribution = HousingFundModule::contributions(account_id).unwrap();
+			assert_eq!(contribution.available_balance, 20);
+			assert_eq!(contribution.reserved_balance, 0);
+			assert_eq!(contribution.contributed_balance, 20);
+		}
+	});
+}
+
 #[test]
 fn validate_house_bidding_with_invalid_values_should_fail() {
 	new_test_ext().execute_with(|| {
@@ -1064,3 +1148,158 @@ fn validate_house_bidding_with_valid_values_should_succeed() {
 		);
 	});
 }
+
+#[test]
+fn buyback_shares_without_enough_in_fund_should_fail() {
+	new_test_ext().execute_with(|| {
+		// Give the investor role to the account
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+
+		// The fund threshold must stay in the fund
+		assert_noop!(
+			HousingFundModule::buyback_shares(1, 1, 3, 39),
+			Error::<Test>::NotEnoughFundForBuyback
+		);
+	});
+}
+
+#[test]
+fn buyback_shares_with_valid_values_should_succeed() {
+	new_test_ext().execute_with(|| {
+		let fund_account_id = HousingFundModule::fund_account_id();
+
+		// Give the investor role to the accounts
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(RoleModule::set_role(Origin::signed(2), 2, crate::ROLES::Accounts::INVESTOR));
+
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(2), 40));
+
+		assert_ok!(HousingFundModule::buyback_shares(1, 1, 3, 20));
+
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			FundInfo {
+				total: HousingFundModule::u64_to_balance_option(60).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(60).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+
+		// Each contributor paid half of the shares
+		let contribution = HousingFundModule::contributions(1).unwrap();
+		assert_eq!(contribution.available_balance, 30);
+		assert_eq!(contribution.contributed_balance, 10);
+		let contribution = HousingFundModule::contributions(2).unwrap();
+		assert_eq!(contribution.available_balance, 30);
+		assert_eq!(contribution.contributed_balance, 10);
+
+		assert_eq!(Balances::free_balance(&3), 20);
+		assert_eq!(Balances::free_balance(&fund_account_id), 70);
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::HousingFundModule(crate::Event::FundBuybackExecuted(3, 1, 1, 20, 1))
+		);
+	});
+}
+
+#[test]
+fn resell_shares_with_valid_values_should_succeed() {
+	new_test_ext().execute_with(|| {
+		// Give the investor role to the accounts
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(RoleModule::set_role(Origin::signed(2), 2, crate::ROLES::Accounts::INVESTOR));
+
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(2), 40));
+		assert_ok!(HousingFundModule::buyback_shares(1, 1, 3, 20));
+
+		// The investors must be contributors with enough available balance
+		assert_noop!(
+			HousingFundModule::resell_shares(1, 1, vec![(1, 5), (3, 15)]),
+			Error::<Test>::NotAContributor
+		);
+		assert_noop!(
+			HousingFundModule::resell_shares(1, 1, vec![(1, 5), (2, 31)]),
+			Error::<Test>::NotEnoughAvailableBalance
+		);
+
+		assert_ok!(HousingFundModule::resell_shares(1, 1, vec![(1, 5), (2, 15)]));
+
+		// The contributors got back the shares they paid for, and the investors paid the new ones
+		let contribution = HousingFundModule::contributions(1).unwrap();
+		assert_eq!(contribution.available_balance, 35);
+		assert_eq!(contribution.contributed_balance, 5);
+		let contribution = HousingFundModule::contributions(2).unwrap();
+		assert_eq!(contribution.available_balance, 25);
+		assert_eq!(contribution.contributed_balance, 15);
+
+		assert_eq!(
+			HousingFundModule::fund_balance(),
+			FundInfo {
+				total: HousingFundModule::u64_to_balance_option(60).unwrap(),
+				transferable: HousingFundModule::u64_to_balance_option(60).unwrap(),
+				reserved: HousingFundModule::u64_to_balance_option(0).unwrap(),
+			}
+		);
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::HousingFundModule(crate::Event::FundSharesResold(1, 1, 20, 1))
+		);
+	});
+}
+
+#[test]
+fn buyback_shares_parts_should_add_up_to_the_amount() {
+	new_test_ext().execute_with(|| {
+		// Give the investor role to the accounts
+		assert_ok!(RoleModule::set_role(Origin::signed(1), 1, crate::ROLES::Accounts::INVESTOR));
+		assert_ok!(RoleModule::set_role(Origin::signed(2), 2, crate::ROLES::Accounts::INVESTOR));
+
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(1), 40));
+		assert_ok!(HousingFundModule::contribute_to_fund(Origin::signed(2), 30));
+
+		// The rounding remainder is paid by the largest contributor
+		assert_ok!(HousingFundModule::buyback_shares(1, 1, 3, 25));
+		let contribution1 = HousingFundModule::contributions(1).unwrap();
+		let contribution2 = HousingFundModule::contributions(2).unwrap();
+		assert_eq!(contribution1.contributed_balance, 15);
+		assert_eq!(contribution2.contributed_balance, 10);
+		assert_eq!(contribution1.contributed_balance + contribution2.contributed_balance, 25);
+		assert_eq!(contribution1.available_balance + contribution2.available_balance, 45);
+		assert_eq!(HousingFundModule::fund_balance().transferable, 45);
+
+		// The dividends of the shares are given to the contributors
+		HousingFundModule::record_dividends(1, 1, 7);
+		let contribution1 = HousingFundModule::contributions(1).unwrap();
+		let contribution2 = HousingFundModule::contributions(2).unwrap();
+		assert_eq!(contribution1.available_balance, 30);
+		assert_eq!(contribution2.available_balance, 22);
+		assert_eq!(contribution1.contributed_balance, 15);
+		assert_eq!(HousingFundModule::fund_balance().transferable, 52);
+		assert_eq!(HousingFundModule::fund_balance().total, 52);
+
+		let event = <frame_system::Pallet<Test>>::events()
+			.pop()
+			.expect("Expected at least one EventRecord to be found")
+			.event;
+
+		assert_eq!(
+			event,
+			mock::Event::HousingFundModule(crate::Event::FundDividendsReceived(1, 1, 7, 1))
+		);
+	});
+}
@@ -104,19 +104,23 @@ impl<T: Config> Pallet<T> {
 		let balance = <T as Config>::Currency::reserved_balance(&owner);
 		let _returned = <T as Config>::Currency::unreserve(&owner, balance);
 
-		// The reserved funds in Housing Fund from the house bidding are unreserved for the transfer
-		// transaction
-		HousingFund::Pallet::<T>::unreserve_house_bidding_amount(collection_id, item_id).ok();
-
-		//Transfer funds from HousingFund to owner
 		let price = Prices::<T>::get(collection_id, item_id).unwrap();
-		let fund_id = T::PalletId::get().into_account_truncating();
-		<T as Config>::Currency::transfer(
-			&fund_id,
-			&owner,
-			price,
-			ExistenceRequirement::KeepAlive,
-		)?;
+
+		// A purchase settled through the finalizer escrow was already paid to the owner
+		if HousingFund::Pallet::<T>::escrows((collection_id, item_id)).is_none() {
+			// The reserved funds in Housing Fund from the house bidding are unreserved for the
+			// transfer transaction
+			HousingFund::Pallet::<T>::unreserve_house_bidding_amount(collection_id, item_id).ok();
+
+			//Transfer funds from HousingFund to owner
+			let fund_id = T::PalletId::get().into_account_truncating();
+			<T as Config>::Currency::transfer(
+				&fund_id,
+				&owner,
+				price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+		}
 		let to = T::Lookup::unlookup(buyer.clone());
 		Nft::Pallet::<T>::transfer(origin_root, collection, item_id, to)?;
 		Self::deposit_event(Event::TokenSold {
@@ -7,6 +7,171 @@ impl<T: Config> Pallet<T> {
 	pub fn u128_to_balance_option(input: u128) -> Option<BalanceOf<T>> {
 		input.try_into().ok()
 	}
+
+	/// Create a payment from `from` to `recipient` and reserve its amount.
+	/// Returns the payment_id of the new payment.
+	pub fn do_pay(
+		from: T::AccountId,
+		recipient: T::AccountId,
+		amount: BalanceOf<T>,
+		remark: Option<BoundedDataOf<T>>,
+	) -> Result<PaymentId, sp_runtime::DispatchError> {
+		// create PaymentDetail and add to storage
+		let (payment_id, payment_detail) = <Self as PaymentHandler<T>>::create_payment(
+			&from,
+			&recipient,
+			amount,
+			PaymentState::Created,
+			T::IncentivePercentage::get(),
+			remark.as_ref().map(|x| x.as_slice()),
+		)?;
+		// reserve funds for payment
+		<Self as PaymentHandler<T>>::reserve_payment_amount(payment_detail)?;
+		// emit paymentcreated event
+		Self::deposit_event(Event::PaymentCreated { payment_id, from, amount, remark });
+		Ok(payment_id)
+	}
+
+	/// Create an escrow payment from `from` to `recipient` and reserve its amount, without
+	/// incentive nor fee.
+	/// Returns the payment_id of the new payment.
+	pub fn do_escrow(
+		from: T::AccountId,
+		recipient: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<PaymentId, sp_runtime::DispatchError> {
+		let (payment_id, mut payment_detail) = <Self as PaymentHandler<T>>::create_payment(
+			&from,
+			&recipient,
+			amount,
+			PaymentState::Created,
+			Percent::from_percent(0),
+			None,
+		)?;
+		// the escrow is not charged the marketplace fee
+		payment_detail.fee_detail = None;
+		Self::insert_payment(payment_id, &payment_detail);
+		// reserve funds for payment
+		<Self as PaymentHandler<T>>::reserve_payment_amount(payment_detail)?;
+		Self::deposit_event(Event::PaymentCreated { payment_id, from, amount, remark: None });
+		Ok(payment_id)
+	}
+
+	/// Create a payment request from `to` to `from`, to be completed with `accept_and_pay`.
+	/// Returns the payment_id of the new request.
+	pub fn do_request_payment(
+		from: T::AccountId,
+		to: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<PaymentId, sp_runtime::DispatchError> {
+		// create PaymentDetail and add to storage
+		let (payment_id, _) = <Self as PaymentHandler<T>>::create_payment(
+			&from,
+			&to,
+			amount,
+			PaymentState::PaymentRequested,
+			Percent::from_percent(0),
+			None,
+		)?;
+
+		Self::deposit_event(Event::PaymentRequestCreated { payment_id, from, to });
+
+		Ok(payment_id)
+	}
+
+	/// Release part of a payment to the recipient, the rest of the payment staying reserved.
+	/// Returns the released amount.
+	pub fn release_partial_amount(
+		payment_id: PaymentId,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, sp_runtime::DispatchError> {
+		Payment::<T>::try_mutate(
+			payment_id,
+			|maybe_payment| -> Result<BalanceOf<T>, sp_runtime::DispatchError> {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				let released = amount.min(payment.amount);
+				T::Currency::unreserve(&payment.to, released);
+				payment.amount = payment.amount.saturating_sub(released);
+				Ok(released)
+			},
+		)
+	}
+
+	/// Refund part of a payment to the sender, the rest of the payment staying reserved.
+	/// Returns the refunded amount.
+	pub fn refund_partial_amount(
+		payment_id: PaymentId,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, sp_runtime::DispatchError> {
+		Payment::<T>::try_mutate(
+			payment_id,
+			|maybe_payment| -> Result<BalanceOf<T>, sp_runtime::DispatchError> {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				let refunded = amount.min(payment.amount);
+				T::Currency::repatriate_reserved(
+					&payment.to,
+					&payment.from,
+					refunded,
+					BalanceStatus::Free,
+				)?;
+				payment.amount = payment.amount.saturating_sub(refunded);
+				Ok(refunded)
+			},
+		)
+	}
+
+	/// Send a reserved payment to the assigned resolver, who settles it with `resolve_payment`
+	pub fn request_review(payment_id: PaymentId) -> DispatchResult {
+		Payment::<T>::try_mutate(payment_id, |maybe_payment| -> DispatchResult {
+			let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.state != PaymentState::NeedsReview, Error::<T>::InvalidAction);
+			payment.state = PaymentState::NeedsReview;
+			Ok(())
+		})
+	}
+
+	/// Mark a payment request as completed, for requests accepted before `accept_and_pay`
+	/// completed them. Used by the storage migrations of the pallets requesting payments.
+	pub fn complete_payment_request(payment_id: PaymentId) -> DispatchResult {
+		Payment::<T>::try_mutate(payment_id, |maybe_payment| -> DispatchResult {
+			let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::PaymentRequested, Error::<T>::InvalidAction);
+			payment.state = PaymentState::PaymentCompleted;
+			Ok(())
+		})
+	}
+
+	/// Identifiers of the payments created by `creator`
+	pub fn payments_from(creator: &T::AccountId) -> Vec<PaymentId> {
+		CreatorPayments::<T>::iter_key_prefix(creator).collect()
+	}
+
+	/// Identifiers of the payments received by `recipient`
+	pub fn payments_to(recipient: &T::AccountId) -> Vec<PaymentId> {
+		RecipientPayments::<T>::iter_key_prefix(recipient).collect()
+	}
+
+	/// Identifiers of the payments created by `creator` for `recipient`
+	pub fn payments_between(creator: &T::AccountId, recipient: &T::AccountId) -> Vec<PaymentId> {
+		CreatorPayments::<T>::iter_prefix(creator)
+			.filter(|(_, to)| to == recipient)
+			.map(|(payment_id, _)| payment_id)
+			.collect()
+	}
+
+	/// Store a payment under `payment_id`, and index it by creator and recipient
+	pub(super) fn insert_payment(payment_id: PaymentId, payment: &PaymentDetail<T>) {
+		CreatorPayments::<T>::insert(&payment.from, payment_id, &payment.to);
+		RecipientPayments::<T>::insert(&payment.to, payment_id, &payment.from);
+		Payment::<T>::insert(payment_id, payment);
+	}
+
+	/// Remove a payment and its index entries from storage
+	pub(super) fn remove_payment(payment_id: PaymentId, payment: &PaymentDetail<T>) {
+		CreatorPayments::<T>::remove(&payment.from, payment_id);
+		RecipientPayments::<T>::remove(&payment.to, payment_id);
+		Payment::<T>::remove(payment_id);
+	}
 }
 
 impl<T: Config> PaymentHandler<T> for Pallet<T> {
@@ -21,63 +186,56 @@ impl<T: Config> PaymentHandler<T> for Pallet<T> {
 		payment_state: PaymentState<T>,
 		incentive_percentage: Percent,
 		remark: Option<&[u8]>,
-	) -> Result<PaymentDetail<T>, sp_runtime::DispatchError> {
-		Payment::<T>::try_mutate(
-			from,
-			recipient,
-			|maybe_payment| -> Result<PaymentDetail<T>, sp_runtime::DispatchError> {
-				// only payment requests can be overwritten
-				if let Some(payment) = maybe_payment {
-					ensure!(
-						payment.state == PaymentState::PaymentRequested,
-						Error::<T>::PaymentAlreadyInProcess
-					);
-				}
+	) -> Result<(PaymentId, PaymentDetail<T>), sp_runtime::DispatchError> {
+		let payment_id = NextPaymentId::<T>::get();
+		let next_id = payment_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 
-				// Calculate incentive amount - this is to insentivise the user to release
-				// the funds once a transaction has been completed
-				let incentive_amount = incentive_percentage.mul_floor(amount);
+		// Calculate incentive amount - this is to insentivise the user to release
+		// the funds once a transaction has been completed
+		let incentive_amount = incentive_percentage.mul_floor(amount);
 
-				let mut new_payment = PaymentDetail {
-					amount,
-					incentive_amount,
-					state: payment_state,
-					resolver_account: T::DisputeResolver::get_resolver_account(),
-					fee_detail: None,
-				};
+		let mut new_payment = PaymentDetail {
+			from: from.clone(),
+			to: recipient.clone(),
+			amount,
+			incentive_amount,
+			state: payment_state,
+			resolver_account: T::DisputeResolver::get_resolver_account(),
+			fee_detail: None,
+		};
 
-				// Calculate fee amount - this will be implemented based on the custom
-				// implementation of the fee provider
-				let (fee_recipient, fee_percent) =
-					T::FeeHandler::apply_fees(from, recipient, &new_payment, remark);
-				let fee_amount = fee_percent.mul_floor(amount);
-				new_payment.fee_detail = Some((fee_recipient, fee_amount));
+		// Calculate fee amount - this will be implemented based on the custom
+		// implementation of the fee provider
+		let (fee_recipient, fee_percent) =
+			T::FeeHandler::apply_fees(from, recipient, &new_payment, remark);
+		let fee_amount = fee_percent.mul_floor(amount);
+		new_payment.fee_detail = Some((fee_recipient, fee_amount));
 
-				*maybe_payment = Some(new_payment.clone());
+		Self::insert_payment(payment_id, &new_payment);
+		NextPaymentId::<T>::put(next_id);
 
-				Ok(new_payment)
-			},
-		)
+		Ok((payment_id, new_payment))
 	}
 
 	/// The function will reserve the fees+transfer amount from the `from`
 	/// account. After reserving the payment.amount will be transferred to
 	/// the recipient but will stay in Reserve state.
 	#[require_transactional]
-	fn reserve_payment_amount(
-		from: &T::AccountId,
-		to: &T::AccountId,
-		payment: PaymentDetail<T>,
-	) -> DispatchResult {
+	fn reserve_payment_amount(payment: PaymentDetail<T>) -> DispatchResult {
 		let fee_amount = payment.fee_detail.map(|(_, f)| f).unwrap_or_else(|| 0u32.into());
 
 		let total_fee_amount = payment.incentive_amount.saturating_add(fee_amount);
 		let total_amount = total_fee_amount.saturating_add(payment.amount);
 
 		// reserve the total amount from payment creator
-		T::Currency::reserve(from, total_amount)?;
+		T::Currency::reserve(&payment.from, total_amount)?;
 		// transfer payment amount to recipient -- keeping reserve status
-		T::Currency::repatriate_reserved(from, to, payment.amount, BalanceStatus::Reserved)?;
+		T::Currency::repatriate_reserved(
+			&payment.from,
+			&payment.to,
+			payment.amount,
+			BalanceStatus::Reserved,
+		)?;
 		Ok(())
 	}
 
@@ -88,48 +246,44 @@ impl<T: Config> PaymentHandler<T> for Pallet<T> {
 	/// fee_recipient For cancelling a payment, recipient_share = 0
 	/// For releasing a payment, recipient_share = 100
 	/// In other cases, the custom recipient_share can be specified
-	fn settle_payment(
-		from: &T::AccountId,
-		to: &T::AccountId,
-		recipient_share: Percent,
-	) -> DispatchResult {
-		Payment::<T>::try_mutate(from, to, |maybe_payment| -> DispatchResult {
-			let payment = maybe_payment.take().ok_or(Error::<T>::InvalidPayment)?;
-
-			// unreserve the incentive amount and fees from the owner account
-			match payment.fee_detail {
-				Some((fee_recipient, fee_amount)) => {
-					T::Currency::unreserve(from, payment.incentive_amount + fee_amount);
-					// transfer fee to marketplace if operation is not cancel
-					if recipient_share != Percent::zero() {
-						T::Currency::transfer(
-							from,           // fee is paid by payment creator
-							&fee_recipient, // account of fee recipient
-							fee_amount,     // amount of fee
-							AllowDeath,
-						)?;
-					}
-				},
-				None => {
-					T::Currency::unreserve(from, payment.incentive_amount);
-				},
-			};
+	fn settle_payment(payment_id: PaymentId, recipient_share: Percent) -> DispatchResult {
+		let payment = Payment::<T>::get(payment_id).ok_or(Error::<T>::InvalidPayment)?;
+		let (from, to) = (&payment.from, &payment.to);
+
+		// unreserve the incentive amount and fees from the owner account
+		match payment.fee_detail.clone() {
+			Some((fee_recipient, fee_amount)) => {
+				T::Currency::unreserve(from, payment.incentive_amount + fee_amount);
+				// transfer fee to marketplace if operation is not cancel
+				if recipient_share != Percent::zero() {
+					T::Currency::transfer(
+						from,           // fee is paid by payment creator
+						&fee_recipient, // account of fee recipient
+						fee_amount,     // amount of fee
+						AllowDeath,
+					)?;
+				}
+			},
+			None => {
+				T::Currency::unreserve(from, payment.incentive_amount);
+			},
+		};
 
-			// Unreserve the transfer amount
-			T::Currency::unreserve(to, payment.amount);
+		// Unreserve the transfer amount
+		T::Currency::unreserve(to, payment.amount);
 
-			let amount_to_recipient = recipient_share.mul_floor(payment.amount);
-			let amount_to_sender = payment.amount.saturating_sub(amount_to_recipient);
-			// send share to recipient
-			T::Currency::transfer(to, from, amount_to_sender, AllowDeath)?;
+		let amount_to_recipient = recipient_share.mul_floor(payment.amount);
+		let amount_to_sender = payment.amount.saturating_sub(amount_to_recipient);
+		// send share to recipient
+		T::Currency::transfer(to, from, amount_to_sender, AllowDeath)?;
 
-			Ok(())
-		})?;
+		Self::remove_payment(payment_id, &payment);
+		T::OnPaymentSettled::on_payment_settled(payment_id, &payment, recipient_share);
 		Ok(())
 	}
 
-	fn get_payment_details(from: &T::AccountId, to: &T::AccountId) -> Option<PaymentDetail<T>> {
-		Payment::<T>::get(from, to)
+	fn get_payment_details(payment_id: PaymentId) -> Option<PaymentDetail<T>> {
+		Payment::<T>::get(payment_id)
 	}
 }
 
@@ -162,15 +316,15 @@ impl<T: Config> Pallet<T> {
 			task_list.sort_by(|(_, t), (_, x)| x.when.cmp(&t.when));
 
 			while !task_list.is_empty() && remaining_weight >= cancel_weight {
-				if let Some((account_pair, _)) = task_list.pop() {
+				if let Some((payment_id, _)) = task_list.pop() {
 					remaining_weight.saturating_sub(cancel_weight);
 					// remove the task form the tasks storage
-					tasks.remove(&account_pair);
+					tasks.remove(&payment_id);
 
 					// process the cancel payment
+					let payment = Payment::<T>::get(payment_id);
 					if <Self as PaymentHandler<T>>::settle_payment(
-						&account_pair.0,
-						&account_pair.1,
+						payment_id,
 						Percent::from_percent(0),
 					)
 					.is_err()
@@ -180,11 +334,12 @@ impl<T: Config> Pallet<T> {
 							target: "runtime::payments",
 							"Warning: Unable to process payment refund!"
 						);
-					} else {
+					} else if let Some(payment) = payment {
 						// emit the cancel event if the refund was successful
 						Self::deposit_event(Event::PaymentCancelled {
-							from: account_pair.0,
-							to: account_pair.1,
+							payment_id,
+							from: payment.from,
+							to: payment.to,
 						});
 					}
 				}
@@ -12,6 +12,7 @@ mod mock;
 mod tests;
 
 mod functions;
+pub mod migration;
 mod types;
 pub use crate::types::*;
 pub use functions::*;
@@ -23,8 +24,12 @@ pub use weights::WeightInfo;
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -37,6 +42,8 @@ pub mod pallet {
 		type DisputeResolver: DisputeResolver<Self::AccountId>;
 		/// Fee handler trait
 		type FeeHandler: FeeHandler<Self>;
+		/// Handler of the settled payments
+		type OnPaymentSettled: OnPaymentSettled<Self>;
 		/// Incentive percentage - amount witheld from sender
 		#[pallet::constant]
 		type IncentivePercentage: Get<Percent>;
@@ -57,19 +64,40 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn payment)]
-	/// Payments created by a user, this method of storageDoubleMap is chosen
-	/// since there is no usecase for listing payments by provider/currency. The
-	/// payment will only be referenced by the creator in any transaction of
-	/// interest. The storage map keys are the creator and the recipient, this
-	/// also ensures that for any (sender,recipient) combo, only a single
-	/// payment is active. The history of payment is not stored.
-	pub(super) type Payment<T: Config> = StorageDoubleMap<
+	/// Payments created by a user, keyed by a unique payment_id. Several payments
+	/// can be active between the same creator and recipient, they are listed
+	/// through the `CreatorPayments` and `RecipientPayments` indexes. The
+	/// history of payment is not stored.
+	pub(super) type Payment<T: Config> =
+		StorageMap<_, Blake2_128Concat, PaymentId, PaymentDetail<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_payment_id)]
+	/// Identifier given to the next created payment
+	pub(super) type NextPaymentId<T> = StorageValue<_, PaymentId, ValueQuery>;
+
+	#[pallet::storage]
+	/// Payments created by an account, with their recipient
+	pub(super) type CreatorPayments<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId, // payment creator
 		Blake2_128Concat,
+		PaymentId,
 		T::AccountId, // payment recipient
-		PaymentDetail<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Payments received by an account, with their creator
+	pub(super) type RecipientPayments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // payment recipient
+		Blake2_128Concat,
+		PaymentId,
+		T::AccountId, // payment creator
+		OptionQuery,
 	>;
 
 	#[pallet::storage]
@@ -87,28 +115,35 @@ pub mod pallet {
 		SomethingStored(u32, T::AccountId),
 		/// A new payment has been created
 		PaymentCreated {
+			payment_id: PaymentId,
 			from: T::AccountId,
 			amount: BalanceOf<T>,
 			remark: Option<BoundedDataOf<T>>,
 		},
 		/// Payment amount released to the recipient
-		PaymentReleased { from: T::AccountId, to: T::AccountId },
+		PaymentReleased { payment_id: PaymentId, from: T::AccountId, to: T::AccountId },
 		/// Payment has been cancelled by the creator
-		PaymentCancelled { from: T::AccountId, to: T::AccountId },
+		PaymentCancelled { payment_id: PaymentId, from: T::AccountId, to: T::AccountId },
 		/// A payment that NeedsReview has been resolved by Judge
-		PaymentResolved { from: T::AccountId, to: T::AccountId, recipient_share: Percent },
+		PaymentResolved {
+			payment_id: PaymentId,
+			from: T::AccountId,
+			to: T::AccountId,
+			recipient_share: Percent,
+		},
 		/// the payment creator has created a refund request
 		PaymentCreatorRequestedRefund {
+			payment_id: PaymentId,
 			from: T::AccountId,
 			to: T::AccountId,
 			expiry: T::BlockNumber,
 		},
 		/// the refund request from creator was disputed by recipient
-		PaymentRefundDisputed { from: T::AccountId, to: T::AccountId },
+		PaymentRefundDisputed { payment_id: PaymentId, from: T::AccountId, to: T::AccountId },
 		/// Payment request was created by recipient
-		PaymentRequestCreated { from: T::AccountId, to: T::AccountId },
+		PaymentRequestCreated { payment_id: PaymentId, from: T::AccountId, to: T::AccountId },
 		/// Payment request was completed by sender
-		PaymentRequestCompleted { from: T::AccountId, to: T::AccountId },
+		PaymentRequestCompleted { payment_id: PaymentId, from: T::AccountId, to: T::AccountId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -146,6 +181,11 @@ pub mod pallet {
 		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
 			Self::check_task(now, remaining_weight)
 		}
+
+		/// Move the payments stored under (creator, recipient) to payment ids
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -167,59 +207,54 @@ pub mod pallet {
 			remark: Option<BoundedDataOf<T>>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-
-			// create PaymentDetail and add to storage
-			let payment_detail = <Self as PaymentHandler<T>>::create_payment(
-				&who,
-				&recipient,
-				amount,
-				PaymentState::Created,
-				T::IncentivePercentage::get(),
-				remark.as_ref().map(|x| x.as_slice()),
-			)?;
-			// reserve funds for payment
-			<Self as PaymentHandler<T>>::reserve_payment_amount(&who, &recipient, payment_detail)?;
-			// emit paymentcreated event
-			Self::deposit_event(Event::PaymentCreated { from: who, amount, remark });
+			Self::do_pay(who, recipient, amount, remark)?;
 			Ok(().into())
 		}
 
 		/// Release any created payment, this will transfer the reserved amount
 		/// from the creator of the payment to the assigned recipient
+		/// The origin must be the creator of the payment
+		/// - payment_id: identifier of the payment to release
 		#[pallet::weight(T::WeightInfo::release())]
-		pub fn release(origin: OriginFor<T>, to: T::AccountId) -> DispatchResultWithPostInfo {
+		pub fn release(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 
 			// ensure the payment is in Created state
-			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			let payment = Payment::<T>::get(payment_id).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.from == from, Error::<T>::InvalidAction);
 			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
 
 			// release is a settle_payment with 100% recipient_share
-			<Self as PaymentHandler<T>>::settle_payment(&from, &to, Percent::from_percent(100))?;
+			<Self as PaymentHandler<T>>::settle_payment(payment_id, Percent::from_percent(100))?;
 
-			Self::deposit_event(Event::PaymentReleased { from, to });
+			Self::deposit_event(Event::PaymentReleased { payment_id, from, to: payment.to });
 			Ok(().into())
 		}
 
 		/// Cancel a payment in created state, this will release the reserved
 		/// back to creator of the payment. This extrinsic can only be called by
 		/// the recipient of the payment
+		/// - payment_id: identifier of the payment to cancel
 		#[pallet::weight(T::WeightInfo::cancel())]
-		pub fn cancel(origin: OriginFor<T>, creator: T::AccountId) -> DispatchResultWithPostInfo {
+		pub fn cancel(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			if let Some(payment) = Payment::<T>::get(&creator, &who) {
+			if let Some(payment) = Payment::<T>::get(payment_id) {
+				ensure!(payment.to == who, Error::<T>::InvalidAction);
 				match payment.state {
 					// call settle payment with recipient_share=0, this refunds the sender
 					PaymentState::Created => {
 						<Self as PaymentHandler<T>>::settle_payment(
-							&creator,
-							&who,
+							payment_id,
 							Percent::from_percent(0),
 						)?;
-						Self::deposit_event(Event::PaymentCancelled { from: creator, to: who });
+						Self::deposit_event(Event::PaymentCancelled {
+							payment_id,
+							from: payment.from,
+							to: who,
+						});
 					},
 					// if the payment is in state PaymentRequested, remove from storage
-					PaymentState::PaymentRequested => Payment::<T>::remove(&creator, &who),
+					PaymentState::PaymentRequested => Self::remove_payment(payment_id, &payment),
 					_ => fail!(Error::<T>::InvalidAction),
 				}
 			}
@@ -230,34 +265,30 @@ pub mod pallet {
 		/// recipient of the payment.
 		/// This extrinsic allows the assigned judge to
 		/// cancel/release/partial_release the payment.
+		/// - payment_id: identifier of the payment to resolve
+		/// - recipient_share: share of the payment amount sent to the recipient
 		#[pallet::weight(T::WeightInfo::resolve_payment())]
 		pub fn resolve_payment(
 			origin: OriginFor<T>,
-			from: T::AccountId,
-			recipient: T::AccountId,
+			payment_id: PaymentId,
 			recipient_share: Percent,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let account_pair = (from, recipient);
+			let payment = Payment::<T>::get(payment_id).ok_or(Error::<T>::InvalidPayment)?;
 			// ensure the caller is the assigned resolver
-			if let Some(payment) = Payment::<T>::get(&account_pair.0, &account_pair.1) {
-				ensure!(who == payment.resolver_account, Error::<T>::InvalidAction);
-				ensure!(payment.state != PaymentState::PaymentRequested, Error::<T>::InvalidAction);
-				if matches!(payment.state, PaymentState::RefundRequested { .. }) {
-					ScheduledTasks::<T>::mutate(|tasks| {
-						tasks.remove(&account_pair);
-					})
-				}
+			ensure!(who == payment.resolver_account, Error::<T>::InvalidAction);
+			ensure!(payment.state != PaymentState::PaymentRequested, Error::<T>::InvalidAction);
+			if matches!(payment.state, PaymentState::RefundRequested { .. }) {
+				ScheduledTasks::<T>::mutate(|tasks| {
+					tasks.remove(&payment_id);
+				})
 			}
 			// try to update the payment to new state
-			<Self as PaymentHandler<T>>::settle_payment(
-				&account_pair.0,
-				&account_pair.1,
-				recipient_share,
-			)?;
+			<Self as PaymentHandler<T>>::settle_payment(payment_id, recipient_share)?;
 			Self::deposit_event(Event::PaymentResolved {
-				from: account_pair.0,
-				to: account_pair.1,
+				payment_id,
+				from: payment.from,
+				to: payment.to,
 				recipient_share,
 			});
 			Ok(().into())
@@ -266,49 +297,49 @@ pub mod pallet {
 		/// Allow the creator of a payment to initiate a refund that will return
 		/// the funds after a configured amount of time that the reveiver has to
 		/// react and opose the request
+		/// - payment_id: identifier of the payment to refund
 		#[pallet::weight(T::WeightInfo::request_refund())]
 		pub fn request_refund(
 			origin: OriginFor<T>,
-			recipient: T::AccountId,
+			payment_id: PaymentId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
-			Payment::<T>::try_mutate(
-				who.clone(),
-				recipient.clone(),
-				|maybe_payment| -> DispatchResult {
-					// ensure the payment exists
-					let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
-					// refunds only possible for payments in created state
-					ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
-
-					// set the payment to requested refund
-					let current_block = frame_system::Pallet::<T>::block_number();
-					let cancel_block = current_block
-						.checked_add(&T::CancelBufferBlockLength::get())
-						.ok_or(Error::<T>::MathError)?;
-
-					ScheduledTasks::<T>::try_mutate(|task_list| -> DispatchResult {
-						task_list
-							.try_insert(
-								(who.clone(), recipient.clone()),
-								ScheduledTask { task: Task::Cancel, when: cancel_block },
-							)
-							.map_err(|_| Error::<T>::RefundQueueFull)?;
-						Ok(())
-					})?;
-
-					payment.state = PaymentState::RefundRequested { cancel_block };
-
-					Self::deposit_event(Event::PaymentCreatorRequestedRefund {
-						from: who,
-						to: recipient,
-						expiry: cancel_block,
-					});
-
+			Payment::<T>::try_mutate(payment_id, |maybe_payment| -> DispatchResult {
+				// ensure the payment exists
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				// refunds are requested by the payment creator
+				ensure!(payment.from == who, Error::<T>::InvalidAction);
+				// refunds only possible for payments in created state
+				ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+
+				// set the payment to requested refund
+				let current_block = frame_system::Pallet::<T>::block_number();
+				let cancel_block = current_block
+					.checked_add(&T::CancelBufferBlockLength::get())
+					.ok_or(Error::<T>::MathError)?;
+
+				ScheduledTasks::<T>::try_mutate(|task_list| -> DispatchResult {
+					task_list
+						.try_insert(
+							payment_id,
+							ScheduledTask { task: Task::Cancel, when: cancel_block },
+						)
+						.map_err(|_| Error::<T>::RefundQueueFull)?;
 					Ok(())
-				},
-			)?;
+				})?;
+
+				payment.state = PaymentState::RefundRequested { cancel_block };
+
+				Self::deposit_event(Event::PaymentCreatorRequestedRefund {
+					payment_id,
+					from: who,
+					to: payment.to.clone(),
+					expiry: cancel_block,
+				});
+
+				Ok(())
+			})?;
 
 			Ok(().into())
 		}
@@ -317,49 +348,47 @@ pub mod pallet {
 		/// payment creator This does not cancel the request, instead sends the
 		/// payment to a NeedsReview state The assigned resolver account can
 		/// then change the state of the payment after review.
+		/// - payment_id: identifier of the payment to dispute
 		#[pallet::weight(T::WeightInfo::dispute_refund())]
 		pub fn dispute_refund(
 			origin: OriginFor<T>,
-			creator: T::AccountId,
+			payment_id: PaymentId,
 		) -> DispatchResultWithPostInfo {
 			use PaymentState::*;
 			let who = ensure_signed(origin)?;
 
-			Payment::<T>::try_mutate(
-				creator.clone(),
-				who.clone(), // should be called by the payment recipient
-				|maybe_payment| -> DispatchResult {
-					// ensure the payment exists
-					let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
-					// ensure the payment is in Requested Refund state
-					match payment.state {
-						RefundRequested { cancel_block } => {
-							ensure!(
-								cancel_block > frame_system::Pallet::<T>::block_number(),
-								Error::<T>::InvalidAction
-							);
-
-							payment.state = PaymentState::NeedsReview;
-
-							// remove the payment from scheduled tasks
-							ScheduledTasks::<T>::try_mutate(|task_list| -> DispatchResult {
-								task_list
-									.remove(&(creator.clone(), who.clone()))
-									.ok_or(Error::<T>::InvalidAction)?;
-								Ok(())
-							})?;
-
-							Self::deposit_event(Event::PaymentRefundDisputed {
-								from: creator,
-								to: who,
-							});
-						},
-						_ => fail!(Error::<T>::InvalidAction),
-					}
+			Payment::<T>::try_mutate(payment_id, |maybe_payment| -> DispatchResult {
+				// ensure the payment exists
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				// should be called by the payment recipient
+				ensure!(payment.to == who, Error::<T>::InvalidAction);
+				// ensure the payment is in Requested Refund state
+				match payment.state {
+					RefundRequested { cancel_block } => {
+						ensure!(
+							cancel_block > frame_system::Pallet::<T>::block_number(),
+							Error::<T>::InvalidAction
+						);
+
+						payment.state = PaymentState::NeedsReview;
+
+						// remove the payment from scheduled tasks
+						ScheduledTasks::<T>::try_mutate(|task_list| -> DispatchResult {
+							task_list.remove(&payment_id).ok_or(Error::<T>::InvalidAction)?;
+							Ok(())
+						})?;
+
+						Self::deposit_event(Event::PaymentRefundDisputed {
+							payment_id,
+							from: payment.from.clone(),
+							to: who,
+						});
+					},
+					_ => fail!(Error::<T>::InvalidAction),
+				}
 
-					Ok(())
-				},
-			)?;
+				Ok(())
+			})?;
 
 			Ok(().into())
 		}
@@ -376,43 +405,36 @@ pub mod pallet {
 			#[pallet::compact] amount: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let to = ensure_signed(origin)?;
-
-			// create PaymentDetail and add to storage
-			<Self as PaymentHandler<T>>::create_payment(
-				&from,
-				&to,
-				amount,
-				PaymentState::PaymentRequested,
-				Percent::from_percent(0),
-				None,
-			)?;
-
-			Self::deposit_event(Event::PaymentRequestCreated { from, to });
-
+			Self::do_request_payment(from, to, amount)?;
 			Ok(().into())
 		}
 
 		// This extrinsic allows the sender to fulfill a payment request created by a
-		// recipient. The amount will be transferred to the recipient and payment
-		// removed from storage
+		// recipient. The amount is reserved in the recipient account and the payment
+		// is kept in storage in PaymentCompleted state, until it is settled. A completed
+		// payment cannot be cancelled by the recipient while the amount is reserved.
 		#[pallet::weight(T::WeightInfo::accept_and_pay())]
 		pub fn accept_and_pay(
 			origin: OriginFor<T>,
-			to: T::AccountId,
+			payment_id: PaymentId,
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 
-			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			let payment = Payment::<T>::get(payment_id).ok_or(Error::<T>::InvalidPayment)?;
 
+			ensure!(payment.from == from, Error::<T>::InvalidAction);
 			ensure!(payment.state == PaymentState::PaymentRequested, Error::<T>::InvalidAction);
 
 			// reserve all the fees from the sender
-			<Self as PaymentHandler<T>>::reserve_payment_amount(&from, &to, payment)?;
-
-			// release the payment and delete the payment from storage
-			//<Self as PaymentHandler<T>>::settle_payment(&from, &to, Percent::from_percent(100))?;
+			let to = payment.to.clone();
+			<Self as PaymentHandler<T>>::reserve_payment_amount(payment)?;
+			Payment::<T>::mutate(payment_id, |maybe_payment| {
+				if let Some(payment) = maybe_payment {
+					payment.state = PaymentState::PaymentCompleted;
+				}
+			});
 
-			Self::deposit_event(Event::PaymentRequestCompleted { from, to });
+			Self::deposit_event(Event::PaymentRequestCompleted { payment_id, from, to });
 
 			Ok(().into())
 		}
@@ -0,0 +1,86 @@
+//! Storage migrations of the payment pallet
+pub use super::*;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Storage layout before payment ids, where a single payment was stored per
+/// (creator, recipient) pair
+pub mod v0 {
+	use super::*;
+
+	/// Payment details, without the creator and recipient accounts
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct OldPaymentDetail<T: Config> {
+		#[codec(compact)]
+		pub amount: BalanceOf<T>,
+		#[codec(compact)]
+		pub incentive_amount: BalanceOf<T>,
+		pub state: PaymentState<T>,
+		pub resolver_account: T::AccountId,
+		pub fee_detail: Option<(T::AccountId, BalanceOf<T>)>,
+	}
+
+	pub type OldScheduledTaskList<T> = BoundedBTreeMap<
+		(AccountIdOf<T>, AccountIdOf<T>),
+		ScheduledTaskOf<T>,
+		<T as Config>::MaxRemarkLength,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type Payment<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		OldPaymentDetail<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledTasks<T: Config> =
+		StorageValue<Pallet<T>, OldScheduledTaskList<T>, ValueQuery>;
+}
+
+/// Give a payment id to every payment stored by (creator, recipient), index them by creator
+/// and recipient, and key the scheduled tasks by payment id
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return T::DbWeight::get().reads(1)
+	}
+
+	//The old payments are collected before writing, both layouts share the same prefix
+	let old_payments: Vec<_> = v0::Payment::<T>::drain().collect();
+	let count = old_payments.len() as u64;
+	let mut payment_id = NextPaymentId::<T>::get();
+	let mut ids = BTreeMap::new();
+
+	for (from, to, old) in old_payments {
+		let payment = PaymentDetail {
+			from: from.clone(),
+			to: to.clone(),
+			amount: old.amount,
+			incentive_amount: old.incentive_amount,
+			state: old.state,
+			resolver_account: old.resolver_account,
+			fee_detail: old.fee_detail,
+		};
+		Pallet::<T>::insert_payment(payment_id, &payment);
+		ids.insert((from, to), payment_id);
+		payment_id = payment_id.saturating_add(1);
+	}
+	NextPaymentId::<T>::put(payment_id);
+
+	let mut tasks = ScheduledTaskList::<T>::default();
+	for (account_pair, task) in v0::ScheduledTasks::<T>::take() {
+		if let Some(id) = ids.get(&account_pair) {
+			tasks.try_insert(*id, task).ok();
+		}
+	}
+	ScheduledTasks::<T>::put(tasks);
+
+	STORAGE_VERSION.put::<Pallet<T>>();
+
+	T::DbWeight::get()
+		.reads_writes(count.saturating_add(3), count.saturating_mul(4).saturating_add(3))
+}
@@ -1,9 +1,14 @@
 use crate::{
+	migration,
 	mock::*,
 	types::{PaymentDetail, PaymentState},
-	Payment as PaymentStore, PaymentHandler, ScheduledTask, ScheduledTasks, Task,
+	Payment as PaymentStore, PaymentHandler, PaymentId, ScheduledTask, ScheduledTasks, Task,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::with_transaction,
+	traits::{GetStorageVersion, StorageVersion},
 };
-use frame_support::{assert_noop, assert_ok, storage::with_transaction};
 use sp_runtime::{Percent, TransactionOutcome};
 type Error = crate::Error<Test>;
 
@@ -11,6 +16,11 @@ fn last_event() -> Event {
 	System::events().pop().expect("Event expected").event
 }
 
+fn sorted(mut payment_ids: Vec<PaymentId>) -> Vec<PaymentId> {
+	payment_ids.sort();
+	payment_ids
+}
+
 #[test]
 fn test_pay_works() {
 	new_test_ext().execute_with(|| {
@@ -32,6 +42,7 @@ fn test_pay_works() {
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentCreated {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				amount: payment_amount,
 				remark: None
@@ -40,8 +51,10 @@ fn test_pay_works() {
 		);
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -69,20 +82,24 @@ fn test_pay_works() {
 			payment_amount.saturating_add(Balances::free_balance(&PAYMENT_RECIPENT))
 		);
 
-		// the payment should not be overwritten
-		assert_noop!(
-			PaymentModule::pay(
-				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_RECIPENT,
-				payment_amount,
-				None
-			),
-			crate::Error::<Test>::PaymentAlreadyInProcess
+		// another payment to the same recipient gets a new payment_id
+		assert_ok!(PaymentModule::pay(
+			Origin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			payment_amount,
+			None
+		));
+		assert_eq!(
+			sorted(PaymentModule::payments_between(&PAYMENT_CREATOR, &PAYMENT_RECIPENT)),
+			[0, 1]
 		);
 
+		// the first payment should not be overwritten
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: 200,
 				state: PaymentState::Created,
@@ -109,8 +126,10 @@ fn test_cancel_works() {
 		));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -126,18 +145,22 @@ fn test_cancel_works() {
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT), 1);
 
 		// cancel should succeed when caller is the recipent
-		assert_ok!(PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT), PAYMENT_CREATOR));
+		assert_ok!(PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT), 0));
 		assert_eq!(
 			last_event(),
-			crate::Event::<Test>::PaymentCancelled { from: PAYMENT_CREATOR, to: PAYMENT_RECIPENT }
-				.into()
+			crate::Event::<Test>::PaymentCancelled {
+				payment_id: 0,
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT
+			}
+			.into()
 		);
 		// the payment amount should be released back to creator
 		assert_eq!(Balances::free_balance(&PAYMENT_CREATOR), creator_initial_balance);
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT), 1);
 
 		// should be released from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -157,8 +180,10 @@ fn test_release_works() {
 			None
 		));
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -174,11 +199,15 @@ fn test_release_works() {
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT), 1);
 
 		// should succeed for valid payment
-		assert_ok!(PaymentModule::release(Origin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT));
+		assert_ok!(PaymentModule::release(Origin::signed(PAYMENT_CREATOR), 0));
 		assert_eq!(
 			last_event(),
-			crate::Event::<Test>::PaymentReleased { from: PAYMENT_CREATOR, to: PAYMENT_RECIPENT }
-				.into()
+			crate::Event::<Test>::PaymentReleased {
+				payment_id: 0,
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT
+			}
+			.into()
 		);
 		// the payment amount should be transferred
 		assert_eq!(
@@ -191,7 +220,7 @@ fn test_release_works() {
 		);
 
 		// should be deleted from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 
 		// should be able to create another payment since previous is released
 		assert_ok!(PaymentModule::pay(
@@ -231,8 +260,7 @@ fn test_resolve_payment_works() {
 		assert_noop!(
 			PaymentModule::resolve_payment(
 				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_CREATOR,
-				PAYMENT_RECIPENT,
+				0,
 				Percent::from_percent(100)
 			),
 			Error::InvalidAction
@@ -241,13 +269,13 @@ fn test_resolve_payment_works() {
 		// should be able to release a payment
 		assert_ok!(PaymentModule::resolve_payment(
 			Origin::signed(RESOLVER_ACCOUNT),
-			PAYMENT_CREATOR,
-			PAYMENT_RECIPENT,
+			0,
 			Percent::from_percent(100)
 		));
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentResolved {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 				recipient_share: Percent::from_percent(100)
@@ -266,7 +294,7 @@ fn test_resolve_payment_works() {
 		);
 
 		// should be removed from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 
 		assert_ok!(PaymentModule::pay(
 			Origin::signed(PAYMENT_CREATOR),
@@ -278,13 +306,13 @@ fn test_resolve_payment_works() {
 		// should be able to cancel a payment
 		assert_ok!(PaymentModule::resolve_payment(
 			Origin::signed(RESOLVER_ACCOUNT),
-			PAYMENT_CREATOR,
-			PAYMENT_RECIPENT,
+			1,
 			Percent::from_percent(0)
 		));
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentResolved {
+				payment_id: 1,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 				recipient_share: Percent::from_percent(0)
@@ -303,7 +331,7 @@ fn test_resolve_payment_works() {
 		);
 
 		// should be released from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(1), None);
 	});
 }
 
@@ -325,8 +353,10 @@ fn test_charging_fee_payment_works() {
 			None
 		));
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT_FEE_CHARGED,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -347,10 +377,7 @@ fn test_charging_fee_payment_works() {
 		);
 
 		// should succeed for valid payment
-		assert_ok!(PaymentModule::release(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT_FEE_CHARGED
-		));
+		assert_ok!(PaymentModule::release(Origin::signed(PAYMENT_CREATOR), 0));
 		// the payment amount should be transferred
 		assert_eq!(
 			Balances::free_balance(&PAYMENT_CREATOR),
@@ -387,8 +414,10 @@ fn test_charging_fee_payment_works_when_canceled() {
 			None
 		));
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT_FEE_CHARGED,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -406,10 +435,7 @@ fn test_charging_fee_payment_works_when_canceled() {
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT_FEE_CHARGED), 1);
 
 		// should succeed for valid payment
-		assert_ok!(PaymentModule::cancel(
-			Origin::signed(PAYMENT_RECIPENT_FEE_CHARGED),
-			PAYMENT_CREATOR
-		));
+		assert_ok!(PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT_FEE_CHARGED), 0));
 		// the payment amount should be transferred
 		assert_eq!(Balances::free_balance(&PAYMENT_CREATOR), creator_initial_balance);
 		assert_eq!(
@@ -437,8 +463,10 @@ fn test_pay_with_remark_works() {
 			Some(vec![1u8; 10].try_into().unwrap())
 		));
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -466,20 +494,10 @@ fn test_pay_with_remark_works() {
 			Balances::free_balance(&PAYMENT_RECIPENT).saturating_add(payment_amount)
 		);
 
-		// the payment should not be overwritten
-		assert_noop!(
-			PaymentModule::pay(
-				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_RECIPENT,
-				payment_amount,
-				None
-			),
-			crate::Error::<Test>::PaymentAlreadyInProcess
-		);
-
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentCreated {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				amount: payment_amount,
 				remark: Some(vec![1u8; 10].try_into().unwrap())
@@ -490,7 +508,7 @@ fn test_pay_with_remark_works() {
 }
 
 #[test]
-fn test_do_not_overwrite_logic_works() {
+fn test_payments_between_same_accounts_are_kept_apart() {
 	new_test_ext().execute_with(|| {
 		let payment_amount = 40;
 		let expected_incentive_amount = payment_amount / INCENTIVE_PERCENTAGE as u64;
@@ -501,40 +519,47 @@ fn test_do_not_overwrite_logic_works() {
 			payment_amount,
 			None
 		));
+		assert_ok!(PaymentModule::pay(
+			Origin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			payment_amount,
+			None
+		));
+		assert_ok!(PaymentModule::request_payment(
+			Origin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT_TWO,
+			payment_amount,
+		));
+
+		// payments are listed by creator and by recipient
+		assert_eq!(sorted(PaymentModule::payments_from(&PAYMENT_CREATOR)), [0, 1]);
+		assert_eq!(sorted(PaymentModule::payments_to(&PAYMENT_RECIPENT)), [0, 1]);
+		assert_eq!(PaymentModule::payments_from(&PAYMENT_RECIPENT_TWO), [2]);
+		assert_eq!(PaymentModule::payments_to(&PAYMENT_CREATOR), [2]);
 
+		// only the creator can release a payment
 		assert_noop!(
-			PaymentModule::pay(
-				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_RECIPENT,
-				payment_amount,
-				None
-			),
-			crate::Error::<Test>::PaymentAlreadyInProcess
+			PaymentModule::release(Origin::signed(PAYMENT_RECIPENT), 1),
+			Error::InvalidAction
 		);
 
-		// set payment state to NeedsReview
-		PaymentStore::<Test>::insert(
-			PAYMENT_CREATOR,
-			PAYMENT_RECIPENT,
-			PaymentDetail {
+		// releasing the second payment leaves the first one in place
+		assert_ok!(PaymentModule::release(Origin::signed(PAYMENT_CREATOR), 1));
+		assert_eq!(PaymentStore::<Test>::get(1), None);
+		assert_eq!(
+			PaymentStore::<Test>::get(0),
+			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
-				state: PaymentState::NeedsReview,
+				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
-			},
-		);
-
-		// the payment should not be overwritten
-		assert_noop!(
-			PaymentModule::pay(
-				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_RECIPENT,
-				payment_amount,
-				None
-			),
-			crate::Error::<Test>::PaymentAlreadyInProcess
+			})
 		);
+		assert_eq!(PaymentModule::payments_between(&PAYMENT_CREATOR, &PAYMENT_RECIPENT), [0]);
+		assert_eq!(PaymentModule::payments_to(&PAYMENT_RECIPENT), [0]);
 	});
 }
 
@@ -552,25 +577,13 @@ fn test_request_refund() {
 			None
 		));
 
-		assert_ok!(PaymentModule::request_refund(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT
-		));
-
-		// do not overwrite payment
-		assert_noop!(
-			PaymentModule::pay(
-				Origin::signed(PAYMENT_CREATOR),
-				PAYMENT_RECIPENT,
-				payment_amount,
-				None
-			),
-			crate::Error::<Test>::PaymentAlreadyInProcess
-		);
+		assert_ok!(PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), 0));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::RefundRequested { cancel_block: expected_cancel_block },
@@ -582,6 +595,7 @@ fn test_request_refund() {
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentCreatorRequestedRefund {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 				expiry: expected_cancel_block
@@ -607,30 +621,26 @@ fn test_dispute_refund() {
 
 		// cannot dispute if refund is not requested
 		assert_noop!(
-			PaymentModule::dispute_refund(Origin::signed(PAYMENT_RECIPENT), PAYMENT_CREATOR),
+			PaymentModule::dispute_refund(Origin::signed(PAYMENT_RECIPENT), 0),
 			Error::InvalidAction
 		);
 		// creator requests a refund
-		assert_ok!(PaymentModule::request_refund(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT
-		));
+		assert_ok!(PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), 0));
 		// ensure the request is added to the refund queue
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
 		assert_eq!(
-			scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)).unwrap(),
+			scheduled_tasks_list.get(&0).unwrap(),
 			&ScheduledTask { task: Task::Cancel, when: expected_cancel_block }
 		);
 
 		// recipient disputes the refund request
-		assert_ok!(PaymentModule::dispute_refund(
-			Origin::signed(PAYMENT_RECIPENT),
-			PAYMENT_CREATOR
-		));
+		assert_ok!(PaymentModule::dispute_refund(Origin::signed(PAYMENT_RECIPENT), 0));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::NeedsReview,
@@ -642,6 +652,7 @@ fn test_dispute_refund() {
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentRefundDisputed {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 			}
@@ -650,7 +661,7 @@ fn test_dispute_refund() {
 
 		// ensure the request is removed from the refund queue
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
-		assert_eq!(scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)), None);
+		assert_eq!(scheduled_tasks_list.get(&0), None);
 	});
 }
 
@@ -667,13 +678,15 @@ fn test_request_payment() {
 		));
 
 		assert_noop!(
-			PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), 0),
 			crate::Error::<Test>::InvalidAction
 		);
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::PaymentRequested,
@@ -685,6 +698,7 @@ fn test_request_payment() {
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentRequestCreated {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 			}
@@ -706,7 +720,7 @@ fn test_requested_payment_cannot_be_released() {
 
 		// requested payment cannot be released
 		assert_noop!(
-			PaymentModule::release(Origin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			PaymentModule::release(Origin::signed(PAYMENT_CREATOR), 0),
 			Error::InvalidAction
 		);
 	});
@@ -723,10 +737,10 @@ fn test_requested_payment_can_be_cancelled_by_requestor() {
 			payment_amount,
 		));
 
-		assert_ok!(PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT), PAYMENT_CREATOR));
+		assert_ok!(PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT), 0));
 
 		// the request should be removed from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -745,8 +759,10 @@ fn test_accept_and_pay() {
 		));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::PaymentRequested,
@@ -755,10 +771,7 @@ fn test_accept_and_pay() {
 			})
 		);
 
-		assert_ok!(PaymentModule::accept_and_pay(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT,
-		));
+		assert_ok!(PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), 0));
 
 		// the payment amount should be transferred
 		assert_eq!(
@@ -768,11 +781,12 @@ fn test_accept_and_pay() {
 		assert_eq!(Balances::reserved_balance(&PAYMENT_RECIPENT), payment_amount);
 
 		// should be deleted from storage
-		//assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		//assert_eq!(PaymentStore::<Test>::get(0), None);
 
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentRequestCompleted {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT,
 			}
@@ -787,7 +801,7 @@ fn test_accept_and_pay_should_fail_for_non_payment_requested() {
 		assert_ok!(PaymentModule::pay(Origin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT, 20, None));
 
 		assert_noop!(
-			PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT,),
+			PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), 0),
 			Error::InvalidAction
 		);
 	});
@@ -809,8 +823,10 @@ fn test_accept_and_pay_should_charge_fee_correctly() {
 		));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT_FEE_CHARGED,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::PaymentRequested,
@@ -819,10 +835,7 @@ fn test_accept_and_pay_should_charge_fee_correctly() {
 			})
 		);
 
-		assert_ok!(PaymentModule::accept_and_pay(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT_FEE_CHARGED,
-		));
+		assert_ok!(PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), 0));
 
 		// the payment amount should be transferred
 		assert_eq!(
@@ -837,13 +850,14 @@ fn test_accept_and_pay_should_charge_fee_correctly() {
 
 		// should be deleted from storage
 		/*assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			PaymentStore::<Test>::get(0),
 			None
 		);*/
 
 		assert_eq!(
 			last_event(),
 			crate::Event::<Test>::PaymentRequestCompleted {
+				payment_id: 0,
 				from: PAYMENT_CREATOR,
 				to: PAYMENT_RECIPENT_FEE_CHARGED,
 			}
@@ -852,6 +866,36 @@ fn test_accept_and_pay_should_charge_fee_correctly() {
 	});
 }
 
+#[test]
+fn test_accepted_payment_request_should_be_completed() {
+	new_test_ext().execute_with(|| {
+		let payment_amount = 20;
+
+		assert_ok!(PaymentModule::request_payment(
+			Origin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			payment_amount,
+		));
+		assert_ok!(PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), 0));
+
+		// the accepted request is kept as a completed payment
+		assert_eq!(PaymentStore::<Test>::get(0).unwrap().state, PaymentState::PaymentCompleted);
+
+		// the request cannot be accepted twice
+		assert_noop!(
+			PaymentModule::accept_and_pay(Origin::signed(PAYMENT_CREATOR), 0),
+			Error::InvalidAction
+		);
+
+		// the recipient cannot cancel it while the amount is reserved
+		assert_noop!(
+			PaymentModule::cancel(Origin::signed(PAYMENT_RECIPENT), 0),
+			Error::InvalidAction
+		);
+		assert_eq!(Balances::reserved_balance(&PAYMENT_RECIPENT), payment_amount);
+	});
+}
+
 #[test]
 fn test_create_payment_works() {
 	new_test_ext().execute_with(|| {
@@ -878,8 +922,10 @@ fn test_create_payment_works() {
 		})));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -888,8 +934,8 @@ fn test_create_payment_works() {
 			})
 		);
 
-		// the payment should not be overwritten
-		assert_noop!(
+		// another payment to the same recipient gets a new payment_id
+		assert_eq!(
 			with_transaction(|| TransactionOutcome::Commit({
 				<PaymentModule as PaymentHandler<Test>>::create_payment(
 					&PAYMENT_CREATOR,
@@ -899,13 +945,18 @@ fn test_create_payment_works() {
 					Percent::from_percent(INCENTIVE_PERCENTAGE),
 					Some(&[1u8; 10]),
 				)
-			})),
-			Error::PaymentAlreadyInProcess
+			}))
+			.map(|(payment_id, _)| payment_id),
+			Ok(1)
 		);
 
+		// the first payment should not be overwritten
+
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -942,8 +993,10 @@ fn test_reserve_payment_amount_works() {
 		})));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -954,9 +1007,7 @@ fn test_reserve_payment_amount_works() {
 
 		assert_ok!(with_transaction(|| TransactionOutcome::Commit({
 			<PaymentModule as PaymentHandler<Test>>::reserve_payment_amount(
-				&PAYMENT_CREATOR,
-				&PAYMENT_RECIPENT,
-				PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT).unwrap(),
+				PaymentStore::<Test>::get(0).unwrap(),
 			)
 		})));
 		// the payment amount should be reserved correctly
@@ -979,8 +1030,8 @@ fn test_reserve_payment_amount_works() {
 			payment_amount.saturating_add(Balances::free_balance(&PAYMENT_RECIPENT))
 		);
 
-		// the payment should not be overwritten
-		assert_noop!(
+		// another payment to the same recipient gets a new payment_id
+		assert_eq!(
 			with_transaction(|| TransactionOutcome::Commit({
 				<PaymentModule as PaymentHandler<Test>>::create_payment(
 					&PAYMENT_CREATOR,
@@ -990,13 +1041,18 @@ fn test_reserve_payment_amount_works() {
 					Percent::from_percent(INCENTIVE_PERCENTAGE),
 					Some(&[1u8; 10]),
 				)
-			})),
-			Error::PaymentAlreadyInProcess
+			}))
+			.map(|(payment_id, _)| payment_id),
+			Ok(1)
 		);
 
+		// the first payment should not be overwritten
+
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::Created,
@@ -1028,11 +1084,7 @@ fn test_settle_payment_works_for_cancel() {
 		));
 
 		assert_ok!(with_transaction(|| TransactionOutcome::Commit({
-			<PaymentModule as PaymentHandler<Test>>::settle_payment(
-				&PAYMENT_CREATOR,
-				&PAYMENT_RECIPENT,
-				Percent::from_percent(0),
-			)
+			<PaymentModule as PaymentHandler<Test>>::settle_payment(0, Percent::from_percent(0))
 		})));
 
 		// the payment amount should be released back to creator
@@ -1040,7 +1092,7 @@ fn test_settle_payment_works_for_cancel() {
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT), recipient_initial_balance);
 
 		// should be released from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -1065,11 +1117,7 @@ fn test_settle_payment_works_for_release() {
 		));
 
 		assert_ok!(with_transaction(|| TransactionOutcome::Commit({
-			<PaymentModule as PaymentHandler<Test>>::settle_payment(
-				&PAYMENT_CREATOR,
-				&PAYMENT_RECIPENT,
-				Percent::from_percent(100),
-			)
+			<PaymentModule as PaymentHandler<Test>>::settle_payment(0, Percent::from_percent(100))
 		})));
 
 		// the payment amount should be transferred
@@ -1083,7 +1131,7 @@ fn test_settle_payment_works_for_release() {
 		);
 
 		// should be deleted from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -1109,11 +1157,7 @@ fn test_settle_payment_works_for_70_30() {
 		));
 
 		assert_ok!(with_transaction(|| TransactionOutcome::Commit({
-			<PaymentModule as PaymentHandler<Test>>::settle_payment(
-				&PAYMENT_CREATOR,
-				&PAYMENT_RECIPENT_FEE_CHARGED,
-				Percent::from_percent(70),
-			)
+			<PaymentModule as PaymentHandler<Test>>::settle_payment(0, Percent::from_percent(70))
 		})));
 
 		let expected_amount_for_creator =
@@ -1133,7 +1177,7 @@ fn test_settle_payment_works_for_70_30() {
 		);
 
 		// should be deleted from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -1159,11 +1203,7 @@ fn test_settle_payment_works_for_50_50() {
 		));
 
 		assert_ok!(with_transaction(|| TransactionOutcome::Commit({
-			<PaymentModule as PaymentHandler<Test>>::settle_payment(
-				&PAYMENT_CREATOR,
-				&PAYMENT_RECIPENT_FEE_CHARGED,
-				Percent::from_percent(50),
-			)
+			<PaymentModule as PaymentHandler<Test>>::settle_payment(0, Percent::from_percent(50))
 		})));
 
 		let expected_amount_for_creator =
@@ -1183,7 +1223,7 @@ fn test_settle_payment_works_for_50_50() {
 		);
 
 		// should be deleted from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 	});
 }
 
@@ -1203,14 +1243,13 @@ fn test_automatic_refund_works() {
 			None
 		));
 
-		assert_ok!(PaymentModule::request_refund(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT
-		));
+		assert_ok!(PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), 0));
 
 		assert_eq!(
-			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			PaymentStore::<Test>::get(0),
 			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
 				amount: payment_amount,
 				incentive_amount: expected_incentive_amount,
 				state: PaymentState::RefundRequested { cancel_block: CANCEL_BLOCK },
@@ -1221,7 +1260,7 @@ fn test_automatic_refund_works() {
 
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
 		assert_eq!(
-			scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)).unwrap(),
+			scheduled_tasks_list.get(&0).unwrap(),
 			&ScheduledTask { task: Task::Cancel, when: CANCEL_BLOCK }
 		);
 
@@ -1229,29 +1268,33 @@ fn test_automatic_refund_works() {
 		assert_eq!(run_n_blocks(CANCEL_PERIOD - 1), 600);
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
 		assert_eq!(
-			scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)).unwrap(),
+			scheduled_tasks_list.get(&0).unwrap(),
 			&ScheduledTask { task: Task::Cancel, when: CANCEL_BLOCK }
 		);
 
 		// run to after cancel block but odd blocks are busy
 		assert_eq!(run_n_blocks(1), 601);
 		// the payment is still not processed since the block was busy
-		assert!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT).is_some());
+		assert!(PaymentStore::<Test>::get(0).is_some());
 
 		// next block has spare weight to process the payment
 		assert_eq!(run_n_blocks(1), 602);
 		// the payment should be removed from storage
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
 
 		// the scheduled storage should be cleared
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
-		assert_eq!(scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)), None);
+		assert_eq!(scheduled_tasks_list.get(&0), None);
 
 		// test that the refund happened correctly
 		assert_eq!(
 			last_event(),
-			crate::Event::<Test>::PaymentCancelled { from: PAYMENT_CREATOR, to: PAYMENT_RECIPENT }
-				.into()
+			crate::Event::<Test>::PaymentCancelled {
+				payment_id: 0,
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT
+			}
+			.into()
 		);
 		// the payment amount should be released back to creator
 		assert_eq!(Balances::free_balance(&PAYMENT_CREATOR), creator_initial_balance);
@@ -1273,30 +1316,24 @@ fn test_automatic_refund_works_for_multiple_payments() {
 			None
 		));
 
-		assert_ok!(PaymentModule::request_refund(
-			Origin::signed(PAYMENT_CREATOR),
-			PAYMENT_RECIPENT
-		));
+		assert_ok!(PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR), 0));
 		run_n_blocks(1);
-		assert_ok!(PaymentModule::request_refund(
-			Origin::signed(PAYMENT_CREATOR_TWO),
-			PAYMENT_RECIPENT_TWO
-		));
+		assert_ok!(PaymentModule::request_refund(Origin::signed(PAYMENT_CREATOR_TWO), 1));
 
 		assert_eq!(run_n_blocks(CANCEL_PERIOD - 1), 601);
 
 		// Odd block 601 was busy so we still haven't processed the first payment
-		assert_ok!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT).ok_or(()));
+		assert_ok!(PaymentStore::<Test>::get(0).ok_or(()));
 
 		// Even block 602 has enough room to process both pending payments
 		assert_eq!(run_n_blocks(1), 602);
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
-		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR_TWO, PAYMENT_RECIPENT_TWO), None);
+		assert_eq!(PaymentStore::<Test>::get(0), None);
+		assert_eq!(PaymentStore::<Test>::get(1), None);
 
 		// the scheduled storage should be cleared
 		let scheduled_tasks_list = ScheduledTasks::<Test>::get();
-		assert_eq!(scheduled_tasks_list.get(&(PAYMENT_CREATOR, PAYMENT_RECIPENT)), None);
-		assert_eq!(scheduled_tasks_list.get(&(PAYMENT_CREATOR_TWO, PAYMENT_RECIPENT_TWO)), None);
+		assert_eq!(scheduled_tasks_list.get(&0), None);
+		assert_eq!(scheduled_tasks_list.get(&1), None);
 
 		// test that the refund happened correctly
 		assert_eq!(Balances::free_balance(&PAYMENT_CREATOR), 100_000_000_000);
@@ -1306,3 +1343,91 @@ fn test_automatic_refund_works_for_multiple_payments() {
 		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT_TWO), 1);
 	});
 }
+
+#[test]
+fn test_migration_gives_ids_to_stored_payments() {
+	new_test_ext().execute_with(|| {
+		let old_payment = |state: PaymentState<Test>| migration::v0::OldPaymentDetail::<Test> {
+			amount: 20,
+			incentive_amount: 2,
+			state,
+			resolver_account: RESOLVER_ACCOUNT,
+			fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+		};
+		let task = ScheduledTask { task: Task::Cancel, when: CANCEL_BLOCK_BUFFER + 1 };
+
+		// payments and tasks stored by (creator, recipient)
+		migration::v0::Payment::<Test>::insert(
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			old_payment(PaymentState::RefundRequested { cancel_block: task.when }),
+		);
+		migration::v0::Payment::<Test>::insert(
+			PAYMENT_CREATOR_TWO,
+			PAYMENT_RECIPENT_TWO,
+			old_payment(PaymentState::Created),
+		);
+		let mut tasks = migration::v0::OldScheduledTaskList::<Test>::default();
+		assert_ok!(tasks.try_insert((PAYMENT_CREATOR, PAYMENT_RECIPENT), task.clone()));
+		migration::v0::ScheduledTasks::<Test>::put(tasks);
+		StorageVersion::new(0).put::<PaymentModule>();
+
+		migration::migrate_to_v1::<Test>();
+
+		assert_eq!(PaymentModule::on_chain_storage_version(), crate::STORAGE_VERSION);
+		assert_eq!(PaymentModule::next_payment_id(), 2);
+
+		let payment_ids = PaymentModule::payments_between(&PAYMENT_CREATOR, &PAYMENT_RECIPENT);
+		assert_eq!(payment_ids.len(), 1);
+		let payment_id = payment_ids[0];
+		assert_eq!(
+			PaymentStore::<Test>::get(payment_id),
+			Some(PaymentDetail {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				amount: 20,
+				incentive_amount: 2,
+				state: PaymentState::RefundRequested { cancel_block: task.when },
+				resolver_account: RESOLVER_ACCOUNT,
+				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+			})
+		);
+		assert_eq!(PaymentModule::payments_to(&PAYMENT_RECIPENT_TWO).len(), 1);
+
+		// the scheduled refund follows the payment
+		assert_eq!(ScheduledTasks::<Test>::get().get(&payment_id), Some(&task));
+	});
+}
+
+#[test]
+fn test_escrow_is_not_charged_incentive_nor_fee() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100_000_000_000;
+		let payment_amount = 2000;
+
+		let payment_id = with_transaction(|| {
+			TransactionOutcome::Commit(PaymentModule::do_escrow(
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT_FEE_CHARGED,
+				payment_amount,
+			))
+		})
+		.unwrap();
+
+		let payment = PaymentStore::<Test>::get(payment_id).unwrap();
+		assert_eq!(payment.incentive_amount, 0);
+		assert_eq!(payment.fee_detail, None);
+		assert_eq!(payment.state, PaymentState::Created);
+		// only the payment amount is taken from the creator
+		assert_eq!(
+			Balances::free_balance(&PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount
+		);
+		assert_eq!(Balances::reserved_balance(&PAYMENT_CREATOR), 0);
+
+		// releasing the escrow pays the full amount without fee
+		assert_ok!(PaymentModule::release(Origin::signed(PAYMENT_CREATOR), payment_id));
+		assert_eq!(Balances::free_balance(&PAYMENT_RECIPENT_FEE_CHARGED), payment_amount + 1);
+		assert_eq!(Balances::free_balance(&FEE_RECIPIENT_ACCOUNT), 1);
+	});
+}
@@ -35,12 +35,11 @@ pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
 pub type HashOf<T> = <T as frame_system::Config>::Hash;
 pub type BoundedDataOf<T> = BoundedVec<u8, <T as Config>::MaxRemarkLength>;
 pub type ScheduledTaskOf<T> = ScheduledTask<<T as frame_system::Config>::BlockNumber>;
+/// Unique identifier of a payment
+pub type PaymentId = u32;
 /// list of ScheduledTasks, stored as a BoundedBTreeMap
-pub type ScheduledTaskList<T> = BoundedBTreeMap<
-	(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::AccountId),
-	ScheduledTaskOf<T>,
-	<T as Config>::MaxRemarkLength,
->;
+pub type ScheduledTaskList<T> =
+	BoundedBTreeMap<PaymentId, ScheduledTaskOf<T>, <T as Config>::MaxRemarkLength>;
 
 /// The PaymentDetail struct stores information about the payment/escrow
 /// A "payment" in virto network is similar to an escrow, it is used to
@@ -52,6 +51,10 @@ pub type ScheduledTaskList<T> = BoundedBTreeMap<
 #[codec(mel_bound(T: pallet::Config))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PaymentDetail<T: pallet::Config> {
+	/// account that created the payment, and pays the amount
+	pub from: T::AccountId,
+	/// account receiving the payment
+	pub to: T::AccountId,
 	/// amount used for payment
 	#[codec(compact)]
 	pub amount: BalanceOf<T>,
@@ -90,9 +93,9 @@ pub enum PaymentState<T: pallet::Config> {
 /// trait that defines how to create/release payments for users
 pub trait PaymentHandler<T: pallet::Config> {
 	/// Create a PaymentDetail from the given payment details
-	/// Calculate the fee amount and store PaymentDetail in storage
+	/// Calculate the fee amount and store PaymentDetail in storage under a new payment_id
 	/// Possible reasons for failure include:
-	/// - Payment already exists and cannot be overwritten
+	/// - The payment_id counter overflows
 	fn create_payment(
 		from: &T::AccountId,
 		to: &T::AccountId,
@@ -100,34 +103,26 @@ pub trait PaymentHandler<T: pallet::Config> {
 		payment_state: PaymentState<T>,
 		incentive_percentage: Percent,
 		remark: Option<&[u8]>,
-	) -> Result<PaymentDetail<T>, sp_runtime::DispatchError>;
+	) -> Result<(PaymentId, PaymentDetail<T>), sp_runtime::DispatchError>;
 
-	/// Attempt to reserve the amount from the caller
+	/// Attempt to reserve the amount from the payment creator
 	/// If not possible then return Error. Possible reasons for failure include:
 	/// - User does not have enough balance.
-	fn reserve_payment_amount(
-		from: &T::AccountId,
-		to: &T::AccountId,
-		payment: PaymentDetail<T>,
-	) -> DispatchResult;
+	fn reserve_payment_amount(payment: PaymentDetail<T>) -> DispatchResult;
 
-	// Settle a payment of `from` to `to`. To release a payment, the
+	// Settle the payment `payment_id`. To release a payment, the
 	// recipient_share=100, to cancel a payment recipient_share=0
 	// Possible reasonse for failure include
 	///
 	/// - The payment does not exist
 	/// - The unreserve operation fails
 	/// - The transfer operation fails
-	fn settle_payment(
-		from: &T::AccountId,
-		to: &T::AccountId,
-		recipient_share: Percent,
-	) -> DispatchResult;
+	fn settle_payment(payment_id: PaymentId, recipient_share: Percent) -> DispatchResult;
 
 	/// Attempt to fetch the details of a payment from the given payment_id
 	/// Possible reasons for failure include:
 	/// - The payment does not exist
-	fn get_payment_details(from: &T::AccountId, to: &T::AccountId) -> Option<PaymentDetail<T>>;
+	fn get_payment_details(payment_id: PaymentId) -> Option<PaymentDetail<T>>;
 }
 
 /// DisputeResolver trait defines how to create/assign judges for solving
@@ -149,10 +144,42 @@ pub trait FeeHandler<T: pallet::Config> {
 	) -> (T::AccountId, Percent);
 }
 
+/// Handler notified when a payment is settled, with the share of the payment amount sent to the
+/// recipient
+pub trait OnPaymentSettled<T: pallet::Config> {
+	fn on_payment_settled(
+		payment_id: PaymentId,
+		payment: &PaymentDetail<T>,
+		recipient_share: Percent,
+	);
+}
+
+impl<T: pallet::Config> OnPaymentSettled<T> for () {
+	fn on_payment_settled(
+		_payment_id: PaymentId,
+		_payment: &PaymentDetail<T>,
+		_recipient_share: Percent,
+	) {
+	}
+}
+
+impl<T: pallet::Config, A: OnPaymentSettled<T>, B: OnPaymentSettled<T>> OnPaymentSettled<T>
+	for (A, B)
+{
+	fn on_payment_settled(
+		payment_id: PaymentId,
+		payment: &PaymentDetail<T>,
+		recipient_share: Percent,
+	) {
+		A::on_payment_settled(payment_id, payment, recipient_share);
+		B::on_payment_settled(payment_id, payment, recipient_share);
+	}
+}
+
 /// Types of Tasks that can be scheduled in the pallet
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, TypeInfo, MaxEncodedLen)]
 pub enum Task {
-	// payment `from` to `to` has to be cancelled
+	// the payment has to be cancelled
 	Cancel,
 }
 
@@ -73,6 +73,11 @@ impl<T: Config> Pallet<T> {
 		exist
 	}
 
+	// Accounts of the registered notaries
+	pub fn notary_accounts() -> Vec<T::AccountId> {
+		NotaryLog::<T>::iter_keys().collect()
+	}
+
 	//Helper function for account creation approval by admin only
 	pub fn approve_account(sender: T::AccountId, who: T::AccountId) -> DispatchResult {
 		let role = Self::get_requested_role(who.clone());
@@ -4,9 +4,14 @@
 //4) transfer tokens to owners
 use super::*;
 use enum_iterator::all;
-#[allow(unused_imports)]
-use num_traits::float::FloatCore;
-use sp_runtime::{traits::SaturatedConversion, FixedPointNumber, FixedU128};
+use sp_runtime::{
+	traits::{CheckedMul, SaturatedConversion},
+	Perbill,
+};
+
+//Set while the pallet moves ownership tokens. It only lives in memory for the duration of
+//the move, so the restriction cannot be lifted by a leftover storage value.
+environmental::environmental!(internal_transfer: ());
 
 impl<T: Config> Pallet<T> {
 	///The function below create a virtual account from the NFT collection and item id's
@@ -67,55 +72,53 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	///Collect contributors to the bid, and their shares
+	///Collect contributors to the bid, and their shares.
+	///Shares are rounded down, the remaining tokens staying in the virtual account
 	pub fn owner_and_shares(
 		collection_id: T::NftCollectionId,
 		item_id: T::NftItemId,
-		_total_tokens: <T as Assets::Config>::Balance,
-	) -> Vec<(T::AccountId, u128)> {
+		total_tokens: <T as Assets::Config>::Balance,
+	) -> Result<Vec<(T::AccountId, u128)>, DispatchError> {
 		//Get owners and their reserved contribution to the bid
-
-		let reservation_infos =
-			HousingFund::Reservations::<T>::get((collection_id, item_id)).unwrap();
+		let reservation_infos = HousingFund::Reservations::<T>::get((collection_id, item_id))
+			.ok_or(Error::<T>::InvalidValue)?;
 		let vec0 = reservation_infos.contributions;
-		let price = reservation_infos.amount;
-		let virtual_acc = Self::virtual_acc(collection_id, item_id).unwrap().virtual_account;
+		let price0 =
+			Self::hfund_bal_to_u128(reservation_infos.amount).ok_or(Error::<T>::InvalidValue)?;
+		ensure!(price0 > 0, Error::<T>::InvalidValue);
+		let total_tokens0 = total_tokens.saturated_into::<u128>();
+		let virtual_acc = Self::virtual_acc(collection_id, item_id)
+			.ok_or(Error::<T>::InvalidValue)?
+			.virtual_account;
 
 		let mut vec = Vec::new();
 		for i in vec0.iter() {
-			let price0 = Self::hfund_bal_to_u128(price).unwrap();
-			let contribution0 = Self::hfund_bal_to_u128(i.1).unwrap();
-
-			let price1 = Self::balance_to_f64_option0(price).unwrap();
-			let contribution1 = Self::balance_to_f64_option0(i.1).unwrap();
-			let frac = (contribution1 / price1).round();
-			let mut share = FixedU128::saturating_from_rational(contribution0, price0)
-				.saturating_mul_int(1000u128);
-			let fl = share as f64;
-			if (fl + 0.5) < frac {
-				share += 1;
-			}
+			let contribution0 = Self::hfund_bal_to_u128(i.1).ok_or(Error::<T>::InvalidValue)?;
+			let share = Perbill::from_rational(contribution0, price0).mul_floor(total_tokens0);
 
 			debug_assert!(share < 1000);
 			debug_assert!(share > 0);
 
 			vec.push((i.0.clone(), share));
+		}
+
+		for (owner, share) in vec.iter() {
 			//Update Virtual_account storage
 			Virtual::<T>::mutate(collection_id, item_id, |val| {
 				let mut val0 = val.clone().unwrap();
-				val0.owners.push(i.0.clone());
+				val0.owners.push(owner.clone());
 				*val = Some(val0);
 			});
 			//Update owners in Tokens storage
 			Tokens::<T>::mutate(&virtual_acc, |val| {
 				let amount: <T as Assets::Config>::Balance =
-					share.saturated_into::<<T as Assets::Config>::Balance>();
+					(*share).saturated_into::<<T as Assets::Config>::Balance>();
 				let mut val0 = val.clone().unwrap();
-				val0.owners.push((i.0.clone(), amount));
+				val0.owners.push((owner.clone(), amount));
 				*val = Some(val0);
 			});
 		}
-		vec
+		Ok(vec)
 	}
 
 	///Create 1000 Ownership tokens owned by a virtual account
@@ -129,6 +132,7 @@ impl<T: Config> Pallet<T> {
 		ensure!(Virtual::<T>::get(collection_id, item_id).is_some(), Error::<T>::InvalidValue);
 		let token_id = Virtual::<T>::get(collection_id, item_id).unwrap().token_id;
 		let to = T::Lookup::unlookup(account.clone());
+		TokenAssets::<T>::insert(token_id, (collection_id, item_id));
 		TokenId::<T>::mutate(|val| {
 			let val0 = *val;
 			*val = val0 + 1;
@@ -187,7 +191,7 @@ impl<T: Config> Pallet<T> {
 		let token_id = Virtual::<T>::get(collection_id, item_id).unwrap().token_id;
 		let total_tokens = Assets::Pallet::<T>::total_supply(token_id.into());
 		debug_assert!(total_tokens == Self::u32_to_balance_option(1000).unwrap());
-		let shares = Self::owner_and_shares(collection_id, item_id, total_tokens);
+		let shares = Self::owner_and_shares(collection_id, item_id, total_tokens)?;
 
 		let from = T::Lookup::unlookup(account.clone());
 		let origin: OriginFor<T> = RawOrigin::Signed(account).into();
@@ -212,20 +216,481 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	///Move ownership tokens between two accounts, and keep the owners lists
+	///of the `Tokens` and `Virtual` storages in sync with the new balances.
+	///Tokens listed on the secondary market cannot be moved.
+	pub fn transfer_ownership_tokens(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		from: T::AccountId,
+		to: T::AccountId,
+		amount: <T as Assets::Config>::Balance,
+	) -> DispatchResult {
+		let ownership =
+			Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+		ensure!(
+			Self::unlisted_balance(ownership.token_id, &from) >= amount,
+			Error::<T>::NotEnoughTokens
+		);
+		let virtual_account = ownership.virtual_account;
+		let origin: OriginFor<T> = RawOrigin::Signed(virtual_account).into();
+
+		Self::with_internal_transfer(|| {
+			Assets::Pallet::<T>::force_transfer(
+				origin,
+				ownership.token_id.into(),
+				T::Lookup::unlookup(from.clone()),
+				T::Lookup::unlookup(to.clone()),
+				amount,
+			)
+		})?;
+
+		Self::update_owner(collection_id, item_id, from)?;
+		Self::update_owner(collection_id, item_id, to)?;
+
+		Ok(())
+	}
+
+	///Update the owners lists of an asset with the current token balance of an account.
+	///Accounts without tokens are removed from the lists.
+	pub fn update_owner(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		account: T::AccountId,
+	) -> DispatchResult {
+		let mut ownership =
+			Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+		let mut tokens =
+			Self::tokens_infos(&ownership.virtual_account).ok_or(Error::<T>::InvalidValue)?;
+		let balance = Assets::Pallet::<T>::balance(ownership.token_id.into(), &account);
+
+		//Update owners in Tokens storage
+		tokens.owners.retain(|owner| owner.0 != account);
+		if !balance.is_zero() {
+			tokens.owners.push((account.clone(), balance));
+		}
+		Tokens::<T>::insert(&ownership.virtual_account, tokens);
+
+		//Update the tokens held by the housing fund
+		if account == HousingFund::Pallet::<T>::fund_account_id() {
+			if balance.is_zero() {
+				FundShares::<T>::remove(collection_id, item_id);
+			} else {
+				FundShares::<T>::insert(collection_id, item_id, balance);
+			}
+		}
+
+		//Update Virtual_account storage
+		if balance.is_zero() {
+			ownership.owners.retain(|owner| owner != &account);
+		} else if !ownership.owners.contains(&account) {
+			ensure!(
+				ownership.owners.len() < T::MaxOwners::get() as usize,
+				Error::<T>::TooManyOwners
+			);
+			ownership.owners.push(account);
+		}
+		Virtual::<T>::insert(collection_id, item_id, ownership);
+
+		Ok(())
+	}
+
+	///Run a move of ownership tokens made by this pallet, which is not restricted by the
+	///freezer
+	pub(crate) fn with_internal_transfer<R>(f: impl FnOnce() -> R) -> R {
+		internal_transfer::using(&mut (), f)
+	}
+
+	///Put ownership tokens of an owner on sale on the secondary market
+	pub fn do_list_tokens(
+		seller: T::AccountId,
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		amount: <T as Assets::Config>::Balance,
+		price: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidValue);
+		let ownership =
+			Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+		ensure!(ownership.owners.contains(&seller), Error::<T>::NotAnOwner);
+		let unlisted = Self::unlisted_balance(ownership.token_id, &seller);
+		ensure!(unlisted >= amount, Error::<T>::NotEnoughTokens);
+
+		//The price must stay close to the net asset value of the tokens, when it is known
+		if let Some(nav) = T::NetAssetValue::nav_per_token(collection_id, item_id) {
+			let price0: u128 = price.saturated_into();
+			let deviation = T::MaxNavDeviation::get().mul_floor(nav);
+			ensure!(
+				price0 >= nav.saturating_sub(deviation) && price0 <= nav.saturating_add(deviation),
+				Error::<T>::PriceAwayFromNav
+			);
+		}
+
+		let listing_id =
+			TokenListing::<T>::new(seller.clone(), collection_id, item_id, amount, price)?;
+		ListedTokens::<T>::mutate(ownership.token_id, &seller, |val| {
+			*val = val.saturating_add(amount);
+		});
+
+		Self::deposit_event(Event::TokensListed {
+			listing_id,
+			seller,
+			token_id: ownership.token_id,
+			amount,
+			price,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+
+		Ok(())
+	}
+
+	///Ownership tokens of an account which are not listed on the secondary market
+	pub fn unlisted_balance(
+		token_id: <T as pallet::Config>::AssetId,
+		account: &T::AccountId,
+	) -> <T as Assets::Config>::Balance {
+		let balance = Assets::Pallet::<T>::balance(token_id.into(), account);
+		balance.saturating_sub(Self::listed_tokens(token_id, account))
+	}
+
+	///Collection and item of the asset owned by a virtual account
+	pub fn virtual_asset(
+		virtual_account: &T::AccountId,
+	) -> Option<(T::NftCollectionId, T::NftItemId)> {
+		Self::tokens_infos(virtual_account).and_then(|tokens| Self::token_asset(tokens.token_id))
+	}
+
+	///Burn the ownership tokens of an asset, and close its `Virtual` and `Tokens` records.
+	///Used when the owners sell the asset.
+	pub fn close_virtual(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> DispatchResult {
+		let ownership =
+			Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+		let virtual_account = ownership.virtual_account;
+		let token_id = ownership.token_id;
+		let origin: OriginFor<T> = RawOrigin::Signed(virtual_account.clone()).into();
+
+		//Burn the tokens of the owners and of the virtual account
+		let mut holders = ownership.owners;
+		holders.push(virtual_account.clone());
+		Self::with_internal_transfer(|| -> DispatchResult {
+			for holder in holders {
+				let balance = Assets::Pallet::<T>::balance(token_id.into(), &holder);
+				if balance.is_zero() {
+					continue
+				}
+				Assets::Pallet::<T>::burn(
+					origin.clone(),
+					token_id.into(),
+					T::Lookup::unlookup(holder),
+					balance,
+				)?;
+			}
+			Ok(())
+		})?;
+
+		//Remove the secondary market listings of the burned tokens
+		for (listing_id, _) in TokenListings::<T>::drain_prefix(token_id) {
+			Listings::<T>::remove(listing_id);
+		}
+		let _ = ListedTokens::<T>::clear_prefix(token_id, u32::MAX, None);
+
+		Virtual::<T>::remove(collection_id, item_id);
+		Tokens::<T>::remove(&virtual_account);
+		TokenAssets::<T>::remove(token_id);
+		Valuations::<T>::remove(collection_id, item_id);
+		FundShares::<T>::remove(collection_id, item_id);
+		let _ = RentPeriods::<T>::clear_prefix(&virtual_account, u32::MAX, None);
+		RentPeriodId::<T>::remove(&virtual_account);
+		RentRemainder::<T>::remove(&virtual_account);
+
+		Self::deposit_event(Event::VirtualClosed {
+			account: virtual_account,
+			collection: collection_id,
+			item: item_id,
+			token_id,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+
+		Ok(())
+	}
+
+	///Open a rent period for a rent received by a virtual account,
+	///with a snapshot of the owners token balances
+	pub fn record_rent(virtual_account: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let period = RentPeriod::<T>::new(virtual_account.clone(), amount)?;
+
+		Self::deposit_event(Event::RentPeriodRecorded {
+			account: virtual_account,
+			period,
+			amount,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+
+		Ok(())
+	}
+
+	///Rent periods of a virtual account awaiting distribution.
+	///Distributed periods are removed from storage.
+	pub fn pending_rent_periods(virtual_account: T::AccountId) -> Vec<(u32, RentPeriod<T>)> {
+		let mut periods = RentPeriods::<T>::iter_prefix(virtual_account).collect::<Vec<_>>();
+		periods.sort_by_key(|(id, _)| *id);
+		periods
+	}
+
+	///Share an amount between the owners of a rent period snapshot.
+	///Each owner is paid right away, and the dividends that could not be
+	///transferred are kept for a later claim.
+	///The rounding remainder is added to the next distribution of the virtual account.
+	pub fn distribute_dividends(
+		virtual_account: T::AccountId,
+		period_id: u32,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let period = match Self::rent_periods(&virtual_account, period_id) {
+			Some(period) => period,
+			None if period_id < Self::rent_period_id(&virtual_account) =>
+				return Err(Error::<T>::AlreadyDistributed.into()),
+			None => return Err(Error::<T>::InvalidValue.into()),
+		};
+		let supply = period.supply.saturated_into::<u128>();
+		ensure!(supply > 0, Error::<T>::InvalidValue);
+		let amount = amount.saturating_add(RentRemainder::<T>::take(&virtual_account));
+		let amount0 = amount.saturated_into::<u128>();
+		let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+		let asset = Self::virtual_asset(&virtual_account);
+
+		let mut distributed: BalanceOf<T> = Zero::zero();
+		for (owner, balance) in period.snapshot.iter() {
+			//Owner's dividend: rent amount * owner tokens / total supply
+			let dividend0 = amount0.saturating_mul(balance.saturated_into::<u128>()) / supply;
+			let dividend: BalanceOf<T> = dividend0.saturated_into();
+			if dividend.is_zero() {
+				continue
+			}
+
+			let res = <T as pallet::Config>::Currency::transfer(
+				&virtual_account,
+				owner,
+				dividend,
+				ExistenceRequirement::AllowDeath,
+			);
+			if res.is_err() {
+				UnclaimedDividends::<T>::mutate(&virtual_account, owner, |val| {
+					*val = val.saturating_add(dividend);
+				});
+			} else if *owner == fund_account {
+				//The dividends of the tokens held by the fund go to the contributors
+				if let Some((collection, item)) = asset {
+					HousingFund::Pallet::<T>::record_dividends(
+						collection,
+						item,
+						Self::to_fund_balance(dividend),
+					);
+				}
+			}
+			LifetimeDividends::<T>::mutate(owner, |val| {
+				*val = val.saturating_add(dividend);
+			});
+			distributed = distributed.saturating_add(dividend);
+		}
+
+		//The distributed period is pruned, and the remainder is kept for the next one
+		RentPeriods::<T>::remove(&virtual_account, period_id);
+		RentRemainder::<T>::insert(&virtual_account, amount.saturating_sub(distributed));
+
+		Self::deposit_event(Event::DividendsDistributed {
+			account: virtual_account,
+			period: period_id,
+			amount: distributed,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+
+		Ok(())
+	}
+
+	///Pay the dividends still held by a virtual account before it is closed.
+	///The pending rent periods are distributed, and the unclaimed dividends are paid.
+	///Returns the rent remainder and the dividends that could not be paid.
+	pub fn settle_dividends(virtual_account: T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+		for (period_id, period) in Self::pending_rent_periods(virtual_account.clone()) {
+			Self::distribute_dividends(virtual_account.clone(), period_id, period.amount)?;
+		}
+
+		let mut left = RentRemainder::<T>::take(&virtual_account);
+		let unclaimed = UnclaimedDividends::<T>::drain_prefix(&virtual_account).collect::<Vec<_>>();
+		for (owner, amount) in unclaimed {
+			let res = <T as pallet::Config>::Currency::transfer(
+				&virtual_account,
+				&owner,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			);
+			if res.is_err() {
+				left = left.saturating_add(amount);
+				continue
+			}
+
+			Self::deposit_event(Event::DividendsClaimed {
+				owner,
+				account: virtual_account.clone(),
+				amount,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+		}
+
+		Ok(left)
+	}
+
+	///Native currency cost of an amount of ownership tokens
+	pub fn tokens_cost(
+		price: BalanceOf<T>,
+		amount: <T as Assets::Config>::Balance,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let amount0: BalanceOf<T> = amount.saturated_into::<u128>().saturated_into();
+		let cost = price.checked_mul(&amount0).ok_or(Error::<T>::InvalidValue)?;
+		Ok(cost)
+	}
+
+	///Housing fund cost of an amount of ownership tokens
+	pub fn buyback_price(
+		valuation: HousingFund::BalanceOf<T>,
+		amount: <T as Assets::Config>::Balance,
+	) -> Result<HousingFund::BalanceOf<T>, DispatchError> {
+		let amount0: HousingFund::BalanceOf<T> = amount.saturated_into::<u128>().saturated_into();
+		let price = valuation.checked_mul(&amount0).ok_or(Error::<T>::InvalidValue)?;
+		Ok(price)
+	}
+
+	///Housing fund price of the ownership tokens it holds for an asset,
+	///at the approved valuation
+	pub fn fund_shares_price(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+	) -> Option<HousingFund::BalanceOf<T>> {
+		let tokens = Self::fund_shares(collection_id, item_id);
+		if tokens.is_zero() {
+			return None
+		}
+		let valuation = Self::valuations(collection_id, item_id)?;
+		Self::buyback_price(valuation, tokens).ok()
+	}
+
+	///Resell the ownership tokens held by the housing fund for an asset to a list of investors.
+	///The tokens are shared in proportion of the investors contributions, and each investor
+	///pays its tokens at the approved valuation from its housing fund balance.
+	///The caller must revert the storage changes when an error is returned.
+	pub fn resell_fund_shares(
+		collection_id: T::NftCollectionId,
+		item_id: T::NftItemId,
+		contributions: Vec<(T::AccountId, HousingFund::BalanceOf<T>)>,
+	) -> DispatchResult {
+		let ownership =
+			Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+		let valuation = Self::valuations(collection_id, item_id).ok_or(Error::<T>::NoValuation)?;
+		let tokens = Self::fund_shares(collection_id, item_id);
+		ensure!(!tokens.is_zero(), Error::<T>::NotEnoughTokens);
+
+		//Share the tokens between the investors
+		let tokens0: HousingFund::BalanceOf<T> = tokens.saturated_into::<u128>().saturated_into();
+		let (parts, left) =
+			HousingFund::Pallet::<T>::share_by_weight(contributions, tokens0, false);
+		ensure!(left.is_zero(), Error::<T>::InvalidValue);
+		let mut buyers = Vec::new();
+		let mut payments = Vec::new();
+		for (account, part) in parts.into_iter() {
+			let amount: <T as Assets::Config>::Balance =
+				part.saturated_into::<u128>().saturated_into();
+			payments.push((account.clone(), Self::buyback_price(valuation, amount)?));
+			buyers.push((account, amount));
+		}
+		let price = Self::buyback_price(valuation, tokens)?;
+
+		//The investors pay the tokens to the fund contributors
+		HousingFund::Pallet::<T>::resell_shares(collection_id, item_id, payments)
+			.map_err(|e| e.error)?;
+
+		//Move the tokens from the fund account to the investors
+		let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+		for (account, amount) in buyers.iter() {
+			Self::transfer_ownership_tokens(
+				collection_id,
+				item_id,
+				fund_account.clone(),
+				account.clone(),
+				*amount,
+			)?;
+		}
+
+		Self::deposit_event(Event::FundSharesResold {
+			token_id: ownership.token_id,
+			buyers,
+			price,
+			when: <frame_system::Pallet<T>>::block_number(),
+		});
+
+		Ok(())
+	}
+
+	///Account of the treasury receiving the secondary market fees
+	pub fn treasury_account() -> T::AccountId {
+		T::Treasury::get().into_account_truncating()
+	}
+
+	///Protocol fee of a secondary market sale. The fee is dropped when the treasury
+	///account cannot receive it, because it would stay below the existential deposit.
+	pub fn market_fee(cost: BalanceOf<T>) -> BalanceOf<T> {
+		let fee = T::MarketFee::get().mul_floor(cost);
+		let treasury = <T as pallet::Config>::Currency::total_balance(&Self::treasury_account());
+		if treasury.saturating_add(fee) < <T as pallet::Config>::Currency::minimum_balance() {
+			return Zero::zero()
+		}
+		fee
+	}
+
 	// Conversion of u32 to Balance
 	pub fn u32_to_balance_option(input: u32) -> Option<T::Balance> {
 		input.try_into().ok()
 	}
 
+	// Conversion of BalanceOf<T> to the housing fund balance
+	pub fn to_fund_balance(input: BalanceOf<T>) -> HousingFund::BalanceOf<T> {
+		input.saturated_into::<u128>().saturated_into()
+	}
+
 	// Conversion of BalanceOf<T> to u128
 	pub fn hfund_bal_to_u128(input: HousingFund::BalanceOf<T>) -> Option<u128> {
 		input.try_into().ok()
 	}
+}
 
-	// Conversion of BalanceOf<T> to f64
-	pub fn balance_to_f64_option0(input: HousingFund::BalanceOf<T>) -> Option<f64> {
-		let integer: u64 = input.try_into().ok().unwrap();
-		let float = integer as f64;
-		Some(float)
+///Ownership tokens held by the owners are frozen in the assets pallet:
+///they can only be moved by this pallet, which keeps the `Tokens` and `Virtual`
+///storages in sync with the token balances.
+impl<T: Config>
+	Assets::FrozenBalance<
+		<T as Assets::Config>::AssetId,
+		T::AccountId,
+		<T as Assets::Config>::Balance,
+	> for Pallet<T>
+{
+	fn frozen_balance(
+		asset: <T as Assets::Config>::AssetId,
+		who: &T::AccountId,
+	) -> Option<<T as Assets::Config>::Balance> {
+		let token_id = *<T as pallet::Config>::AssetId::from_ref(&asset);
+		//Moves made by this pallet, tokens of other assets, and tokens held by virtual
+		//accounts are not restricted
+		if internal_transfer::with(|_| ()).is_some() ||
+			!TokenAssets::<T>::contains_key(token_id) ||
+			Tokens::<T>::contains_key(who)
+		{
+			return None
+		}
+		Some(Assets::Pallet::<T>::balance(asset, who))
 	}
+
+	fn died(_asset: <T as Assets::Config>::AssetId, _who: &T::AccountId) {}
 }
@@ -16,10 +16,35 @@
 //! each owner
 //! - Distribute the ownership tokens to the new owners.
 //!
+//! The Share_Distributor Pallet also provides a secondary market where owners can
+//! sell their ownership tokens to other investors, against the native currency.
+//!
+//! Each rent received by a virtual account opens a rent period, which keeps a snapshot
+//! of the owners token balances. The rent is later shared according to this snapshot,
+//! and the dividends received by each owner are recorded in a ledger.
+//!
+//! The pallet is used as the `Freezer` of the assets pallet: ownership tokens held by
+//! the owners cannot be moved with the assets pallet extrinsics, and can only be
+//! transferred to investors through this pallet, which keeps the ownership records in sync.
+//!
 //! Dispatchable Functions
 //!
 //! * `create_virtual` - Will sequencially execute each of the steps
 //! described in the Overview.
+//! * `list_tokens` - An owner puts a part of its ownership tokens on sale at a fixed price.
+//! The price cannot deviate from the net asset value of the tokens by more than `MaxNavDeviation`.
+//! * `buy_tokens` - An investor buys all or part of the tokens of a listing.
+//! A protocol fee is sent to the treasury.
+//! * `cancel_listing` - The seller removes its listing from the market.
+//! * `transfer_tokens` - An owner transfers ownership tokens to an investor.
+//! * `claim_dividends` - An owner claims the rent dividends that could not be paid
+//! automatically.
+//! * `set_valuation` - Governance sets the price of an ownership token of an asset,
+//! used when the housing fund buys back shares.
+//! * `sell_to_fund` - An owner sells ownership tokens to the housing fund at the approved
+//! valuation. The tokens are held by the fund account, and are resold to new investors
+//! assembled by the bidding pallet. The rent dividends and the sale proceeds of these tokens
+//! go back to the fund contributors.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -31,6 +56,7 @@ pub use pallet_onboarding as Onboarding;
 pub use pallet_roles as Roles;
 
 mod functions;
+pub mod migration;
 mod types;
 pub use functions::*;
 pub use types::*;
@@ -48,8 +74,11 @@ mod benchmarking;
 pub mod pallet {
 	use super::*;
 
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -69,6 +98,20 @@ pub mod pallet {
 		type AssetId: IsType<<Self as Assets::Config>::AssetId> + Parameter + From<u32> + Ord + Copy;
 		#[pallet::constant]
 		type Fees: Get<BalanceOf<Self>>;
+		/// Percentage of each secondary market sale sent to the treasury
+		#[pallet::constant]
+		type MarketFee: Get<Percent>;
+		/// Treasury receiving the secondary market fees
+		#[pallet::constant]
+		type Treasury: Get<PalletId>;
+		/// Maximum number of owners of an asset
+		#[pallet::constant]
+		type MaxOwners: Get<u32>;
+		/// Net asset value of the ownership tokens
+		type NetAssetValue: NetAssetValue<Self::NftCollectionId, Self::NftItemId>;
+		/// Maximum deviation of a listing price from the net asset value of the tokens
+		#[pallet::constant]
+		type MaxNavDeviation: Get<Percent>;
 	}
 
 	#[pallet::storage]
@@ -101,6 +144,126 @@ pub mod pallet {
 	/// Stores Ownership Tokens id number
 	pub type TokenId<T: Config> = StorageValue<_, u32, ValueQuery, InitDefault<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn token_asset)]
+	/// Stores the asset connected to each ownership token
+	pub type TokenAssets<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		<T as pallet::Config>::AssetId,
+		(T::NftCollectionId, T::NftItemId),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn valuations)]
+	/// Stores the governance-approved price of an ownership token, per asset
+	pub type Valuations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		HousingFund::BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_periods)]
+	/// Stores the rent periods of each virtual account
+	pub type RentPeriods<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u32,
+		RentPeriod<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_period_id)]
+	/// Stores the next rent period id number of each virtual account
+	pub type RentPeriodId<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_remainder)]
+	/// Stores the rounding remainder of the last rent distribution of each virtual account,
+	/// added to the next distribution
+	pub type RentRemainder<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_dividends)]
+	/// Stores the dividends waiting to be claimed by the owners of each virtual account
+	pub type UnclaimedDividends<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // virtual account
+		Blake2_128Concat,
+		T::AccountId, // owner
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lifetime_dividends)]
+	/// Stores the total amount of dividends earned by each owner
+	pub type LifetimeDividends<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	/// Stores ownership tokens listed on the secondary market
+	pub type Listings<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, TokenListing<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn token_listings)]
+	/// Stores the ids of the listings of each ownership token
+	pub type TokenListings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		<T as pallet::Config>::AssetId,
+		Blake2_128Concat,
+		u32,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn listing_id)]
+	/// Stores the next listing id number
+	pub type ListingId<T: Config> = StorageValue<_, u32, ValueQuery, InitDefault<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn listed_tokens)]
+	/// Stores the amount of ownership tokens listed by each seller, per token
+	pub type ListedTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		<T as pallet::Config>::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		<T as Assets::Config>::Balance,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fund_shares)]
+	/// Stores the amount of ownership tokens held by the housing fund, per asset,
+	/// until they are resold to new investors
+	pub type FundShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::NftCollectionId,
+		Blake2_128Concat,
+		T::NftItemId,
+		<T as Assets::Config>::Balance,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -122,6 +285,94 @@ pub mod pallet {
 			token_id: <T as pallet::Config>::AssetId,
 			owners: Vec<(T::AccountId, <T as Assets::Config>::Balance)>,
 		},
+		/// Ownership tokens were put on sale
+		TokensListed {
+			listing_id: u32,
+			seller: T::AccountId,
+			token_id: <T as pallet::Config>::AssetId,
+			amount: <T as Assets::Config>::Balance,
+			price: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// Ownership tokens were bought on the secondary market
+		TokensPurchased {
+			listing_id: u32,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			amount: <T as Assets::Config>::Balance,
+			cost: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// A listing was removed by the seller
+		ListingCancelled { listing_id: u32, seller: T::AccountId, when: BlockNumberOf<T> },
+		/// A rent was received and a rent period was opened
+		RentPeriodRecorded {
+			account: T::AccountId,
+			period: u32,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// The rent of a period was shared between the owners
+		DividendsDistributed {
+			account: T::AccountId,
+			period: u32,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// An owner claimed its dividends
+		DividendsClaimed {
+			owner: T::AccountId,
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// The valuation of an asset ownership token was approved
+		ValuationSet {
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			price: HousingFund::BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// Ownership tokens were bought back by the housing fund
+		SharesSoldToFund {
+			seller: T::AccountId,
+			token_id: <T as pallet::Config>::AssetId,
+			amount: <T as Assets::Config>::Balance,
+			price: HousingFund::BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		/// Ownership tokens were transferred between two accounts
+		TokensTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			token_id: <T as pallet::Config>::AssetId,
+			amount: <T as Assets::Config>::Balance,
+			when: BlockNumberOf<T>,
+		},
+		/// The ownership tokens of a sold asset were burned and its virtual account closed
+		VirtualClosed {
+			account: T::AccountId,
+			collection: T::NftCollectionId,
+			item: T::NftItemId,
+			token_id: <T as pallet::Config>::AssetId,
+			when: BlockNumberOf<T>,
+		},
+		/// Ownership tokens held by the housing fund were resold to new investors
+		FundSharesResold {
+			token_id: <T as pallet::Config>::AssetId,
+			buyers: Vec<(T::AccountId, <T as Assets::Config>::Balance)>,
+			price: HousingFund::BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Connect the ownership tokens created before the transfer restrictions to their asset
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
+		}
 	}
 
 	#[pallet::error]
@@ -134,6 +385,28 @@ pub mod pallet {
 		ReservedToServicer,
 		/// Not enough funds in the fees_account
 		NotEnoughFees,
+		/// This action is reserved to Accounts holding the INVESTOR role.
+		NotAnInvestor,
+		/// The caller is not an owner of this asset
+		NotAnOwner,
+		/// The owner does not hold enough ownership tokens
+		NotEnoughTokens,
+		/// The listing does not exist
+		ListingNotFound,
+		/// This action is reserved to the seller of the listing
+		NotTheSeller,
+		/// The seller cannot buy its own tokens
+		CannotBuyOwnTokens,
+		/// No valuation was approved for this asset
+		NoValuation,
+		/// The rent period was already distributed
+		AlreadyDistributed,
+		/// There are no dividends to claim
+		NoDividends,
+		/// The asset cannot have more owners
+		TooManyOwners,
+		/// The listing price is too far from the net asset value of the tokens
+		PriceAwayFromNav,
 	}
 
 	#[pallet::call]
@@ -191,5 +464,260 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// An owner of the asset puts some of its ownership tokens on sale.
+		/// The price is given per token, in native currency.
+		/// The caller must be an owner of the asset, and cannot list more tokens
+		/// than it holds across all of its listings.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,4).ref_time())]
+		pub fn list_tokens(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			item_id: T::NftItemId,
+			amount: <T as Assets::Config>::Balance,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			Self::do_list_tokens(seller, collection_id, item_id, amount, price)
+		}
+
+		/// An investor buys `amount` tokens from an existing listing.
+		/// The seller is paid in native currency, minus the protocol fee
+		/// which goes to the treasury. A fee too small to be received by the
+		/// treasury is left to the seller.
+		/// The caller must hold the Investor role
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7,8).ref_time())]
+		pub fn buy_tokens(
+			origin: OriginFor<T>,
+			listing_id: u32,
+			amount: <T as Assets::Config>::Balance,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			ensure!(Roles::Pallet::<T>::investors(&buyer).is_some(), Error::<T>::NotAnInvestor);
+			let listing = Self::listings(listing_id).ok_or(Error::<T>::ListingNotFound)?;
+			ensure!(buyer != listing.seller, Error::<T>::CannotBuyOwnTokens);
+			ensure!(!amount.is_zero() && amount <= listing.amount, Error::<T>::InvalidValue);
+
+			//Compute the cost of the purchase and the protocol fee
+			let cost = Self::tokens_cost(listing.price, amount)?;
+			let fee = Self::market_fee(cost);
+
+			//Pay the seller and the treasury
+			<T as pallet::Config>::Currency::transfer(
+				&buyer,
+				&listing.seller,
+				cost.saturating_sub(fee),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			if !fee.is_zero() {
+				<T as pallet::Config>::Currency::transfer(
+					&buyer,
+					&Self::treasury_account(),
+					fee,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+
+			//Move the listed tokens and update owners lists
+			ListedTokens::<T>::mutate(listing.token_id, &listing.seller, |val| {
+				*val = val.saturating_sub(amount);
+			});
+			Self::transfer_ownership_tokens(
+				listing.collection,
+				listing.item,
+				listing.seller.clone(),
+				buyer.clone(),
+				amount,
+			)?;
+
+			//Update or remove the listing
+			let remaining = listing.amount.saturating_sub(amount);
+			if remaining.is_zero() {
+				Listings::<T>::remove(listing_id);
+				TokenListings::<T>::remove(listing.token_id, listing_id);
+			} else {
+				Listings::<T>::mutate(listing_id, |val| {
+					let mut val0 = val.clone().unwrap();
+					val0.amount = remaining;
+					*val = Some(val0);
+				});
+			}
+
+			Self::deposit_event(Event::TokensPurchased {
+				listing_id,
+				seller: listing.seller,
+				buyer,
+				amount,
+				cost,
+				fee,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
+
+		/// The seller removes its listing from the secondary market.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,3).ref_time())]
+		pub fn cancel_listing(origin: OriginFor<T>, listing_id: u32) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			let listing = Self::listings(listing_id).ok_or(Error::<T>::ListingNotFound)?;
+			ensure!(listing.seller == seller, Error::<T>::NotTheSeller);
+
+			Listings::<T>::remove(listing_id);
+			TokenListings::<T>::remove(listing.token_id, listing_id);
+			ListedTokens::<T>::mutate(listing.token_id, &seller, |val| {
+				*val = val.saturating_sub(listing.amount);
+			});
+
+			Self::deposit_event(Event::ListingCancelled {
+				listing_id,
+				seller,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
+
+		/// An owner transfers some of its ownership tokens to another account.
+		/// The receiver must hold the Investor role
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4).ref_time())]
+		pub fn transfer_tokens(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			item_id: T::NftItemId,
+			to: T::AccountId,
+			amount: <T as Assets::Config>::Balance,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			ensure!(Roles::Pallet::<T>::investors(&to).is_some(), Error::<T>::NotAnInvestor);
+			ensure!(!amount.is_zero() && from != to, Error::<T>::InvalidValue);
+			let ownership =
+				Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+			ensure!(ownership.owners.contains(&from), Error::<T>::NotAnOwner);
+			let unlisted = Self::unlisted_balance(ownership.token_id, &from);
+			ensure!(unlisted >= amount, Error::<T>::NotEnoughTokens);
+
+			Self::transfer_ownership_tokens(
+				collection_id,
+				item_id,
+				from.clone(),
+				to.clone(),
+				amount,
+			)?;
+
+			Self::deposit_event(Event::TokensTransferred {
+				from,
+				to,
+				token_id: ownership.token_id,
+				amount,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
+
+		/// An owner claims the dividends of an asset that could not be paid
+		/// during the rent distribution.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,2).ref_time())]
+		pub fn claim_dividends(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			item_id: T::NftItemId,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let account = Self::virtual_acc(collection_id, item_id)
+				.ok_or(Error::<T>::InvalidValue)?
+				.virtual_account;
+			let amount = Self::unclaimed_dividends(&account, &owner);
+			ensure!(!amount.is_zero(), Error::<T>::NoDividends);
+
+			<T as pallet::Config>::Currency::transfer(
+				&account,
+				&owner,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			UnclaimedDividends::<T>::remove(&account, &owner);
+
+			Self::deposit_event(Event::DividendsClaimed {
+				owner,
+				account,
+				amount,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
+
+		/// Set the price of an ownership token of an asset,
+		/// used by the housing fund to buy back shares.
+		/// The origin must be root, through a governance decision
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		pub fn set_valuation(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			item_id: T::NftItemId,
+			price: HousingFund::BalanceOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(Self::virtual_acc(collection_id, item_id).is_some(), Error::<T>::InvalidValue);
+
+			Valuations::<T>::insert(collection_id, item_id, price);
+
+			Self::deposit_event(Event::ValuationSet {
+				collection: collection_id,
+				item: item_id,
+				price,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
+
+		/// An owner sells ownership tokens to the housing fund, at the approved valuation.
+		/// The purchase is paid from the transferable part of the fund,
+		/// and the tokens are held by the fund account.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,7).ref_time())]
+		pub fn sell_to_fund(
+			origin: OriginFor<T>,
+			collection_id: T::NftCollectionId,
+			item_id: T::NftItemId,
+			amount: <T as Assets::Config>::Balance,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidValue);
+			let ownership =
+				Self::virtual_acc(collection_id, item_id).ok_or(Error::<T>::InvalidValue)?;
+			ensure!(ownership.owners.contains(&seller), Error::<T>::NotAnOwner);
+			let unlisted = Self::unlisted_balance(ownership.token_id, &seller);
+			ensure!(unlisted >= amount, Error::<T>::NotEnoughTokens);
+			let valuation =
+				Self::valuations(collection_id, item_id).ok_or(Error::<T>::NoValuation)?;
+
+			//Pay the seller from the housing fund
+			let price = Self::buyback_price(valuation, amount)?;
+			HousingFund::Pallet::<T>::buyback_shares(collection_id, item_id, seller.clone(), price)
+				.map_err(|e| e.error)?;
+
+			//The fund account becomes an owner of the asset
+			let fund_account = HousingFund::Pallet::<T>::fund_account_id();
+			Self::transfer_ownership_tokens(
+				collection_id,
+				item_id,
+				seller.clone(),
+				fund_account,
+				amount,
+			)?;
+
+			Self::deposit_event(Event::SharesSoldToFund {
+				seller,
+				token_id: ownership.token_id,
+				amount,
+				price,
+				when: <frame_system::Pallet<T>>::block_number(),
+			});
+
+			Ok(())
+		}
 	}
 }
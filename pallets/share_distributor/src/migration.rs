@@ -0,0 +1,33 @@
+//! Storage migrations of the share_distributor pallet
+pub use super::*;
+
+/// Storage items removed in version 1
+pub mod v0 {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub type TransferAllowed<T: Config> = StorageValue<Pallet<T>, bool, ValueQuery>;
+}
+
+/// Connect the ownership tokens created before `TokenAssets` existed to their asset, so that
+/// the freezer restricts them, and remove the old transfer flag
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut reads = 1u64;
+	let mut writes = 0u64;
+	for (collection, item, ownership) in Virtual::<T>::iter() {
+		reads = reads.saturating_add(2);
+		if !TokenAssets::<T>::contains_key(ownership.token_id) {
+			TokenAssets::<T>::insert(ownership.token_id, (collection, item));
+			writes = writes.saturating_add(1);
+		}
+	}
+	v0::TransferAllowed::<T>::kill();
+
+	STORAGE_VERSION.put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(reads, writes.saturating_add(2))
+}
@@ -293,19 +293,28 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributor;
 	type Extra = ();
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const AssetsFees: Balance = 15000;
+	pub const MarketFee: Percent = Percent::from_percent(2);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(50);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxOwners: u32 = 50;
 }
 impl pallet_share_distributor::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = ();
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
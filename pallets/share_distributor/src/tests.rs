@@ -1,5 +1,6 @@
 pub use super::*;
 pub use crate::mock::*;
+use frame_support::traits::{GetStorageVersion, StorageVersion};
 pub use frame_support::{assert_noop, assert_ok};
 use frame_system::pallet_prelude::OriginFor;
 
@@ -294,3 +295,459 @@ fn share_distributor1() {
 		]);
 	})
 }
+
+pub fn prep_virtual_asset() -> (u32, u32) {
+	let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+	let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+	let metadata2 = b"metadata2".to_vec().try_into().unwrap();
+	//put some funds in FairSquare SlashFees account
+	let fees_account = Onboarding::Pallet::<Test>::account_id();
+	<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+
+	let price1 = 40_000;
+	let price2 = 30_000;
+	prep_test(price1, price2, metadata0, metadata1, metadata2);
+	let coll_id0 = NftColl::OFFICESTEST.value();
+	let item_id0 = pallet_nft::ItemsCount::<Test>::get()[coll_id0 as usize] - 1;
+	let origin: OriginFor<Test> = frame_system::RawOrigin::Root.into();
+
+	//The housing fund reserves the contributions of EVE & DAVE for this asset
+	assert_ok!(HousingFund::Pallet::<Test>::house_bidding(
+		coll_id0,
+		item_id0,
+		price1,
+		vec![(EVE, 25_000), (DAVE, 15_000)]
+	));
+
+	//Change first asset status to FINALISED
+	Onboarding::Pallet::<Test>::change_status(
+		Origin::signed(BOB),
+		NftColl::OFFICESTEST,
+		item_id0,
+		Onboarding::AssetStatus::FINALISED,
+	)
+	.ok();
+
+	assert_ok!(ShareDistributor::create_virtual(origin, coll_id0, item_id0));
+	(coll_id0, item_id0)
+}
+
+#[test]
+fn secondary_market_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap();
+		let id = virtual0.token_id;
+		//EVE owns 625 tokens & DAVE owns 375 tokens
+		assert_eq!(625, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(375, Assets::Pallet::<Test>::balance(id, DAVE));
+
+		//GERARD is not an investor yet
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 100, 10));
+		assert_eq!(100, ShareDistributor::listed_tokens(id, EVE));
+		assert!(ShareDistributor::token_listings(id, 0).is_some());
+		assert_noop!(
+			ShareDistributor::buy_tokens(Origin::signed(GERARD), 0, 40),
+			Error::<Test>::NotAnInvestor
+		);
+		RoleModule::set_role(Origin::signed(GERARD), GERARD, Acc::INVESTOR).ok();
+
+		//Listing can only be partially filled within the listed amount
+		assert_noop!(
+			ShareDistributor::buy_tokens(Origin::signed(GERARD), 0, 101),
+			Error::<Test>::InvalidValue
+		);
+
+		let eve_balance = Balances::free_balance(EVE);
+		let gerard_balance = Balances::free_balance(GERARD);
+		assert_ok!(ShareDistributor::buy_tokens(Origin::signed(GERARD), 0, 40));
+
+		//Cost is 400, the 2% fee goes to the treasury
+		assert_eq!(Balances::free_balance(GERARD), gerard_balance - 400);
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 392);
+		assert_eq!(Balances::free_balance(ShareDistributor::treasury_account()), 8);
+
+		//Tokens and owners lists are updated
+		assert_eq!(585, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(40, Assets::Pallet::<Test>::balance(id, GERARD));
+		assert_eq!(60, ShareDistributor::listings(0).unwrap().amount);
+		assert_eq!(60, ShareDistributor::listed_tokens(id, EVE));
+		let owners =
+			ShareDistributor::tokens_infos(virtual0.virtual_account.clone()).unwrap().owners;
+		assert!(owners.contains(&(GERARD, 40)));
+		assert!(owners.contains(&(EVE, 585)));
+		assert!(ShareDistributor::virtual_acc(coll_id0, item_id0)
+			.unwrap()
+			.owners
+			.contains(&GERARD));
+
+		//DAVE sells all his tokens and leaves the owners lists
+		assert_ok!(ShareDistributor::list_tokens(
+			Origin::signed(DAVE),
+			coll_id0,
+			item_id0,
+			375,
+			10
+		));
+		assert_ok!(ShareDistributor::buy_tokens(Origin::signed(GERARD), 1, 375));
+		assert!(ShareDistributor::listings(1).is_none());
+		assert!(ShareDistributor::token_listings(id, 1).is_none());
+		assert!(!ShareDistributor::virtual_acc(coll_id0, item_id0)
+			.unwrap()
+			.owners
+			.contains(&DAVE));
+		let owners = ShareDistributor::tokens_infos(virtual0.virtual_account).unwrap().owners;
+		assert!(owners.iter().all(|owner| owner.0 != DAVE));
+
+		//Only the seller can cancel its listing
+		assert_noop!(
+			ShareDistributor::cancel_listing(Origin::signed(DAVE), 0),
+			Error::<Test>::NotTheSeller
+		);
+		assert_ok!(ShareDistributor::cancel_listing(Origin::signed(EVE), 0));
+		assert!(ShareDistributor::listings(0).is_none());
+		assert!(ShareDistributor::token_listings(id, 0).is_none());
+		assert_eq!(0, ShareDistributor::listed_tokens(id, EVE));
+	});
+}
+
+#[test]
+fn close_virtual_should_remove_listings() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let id = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().token_id;
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 100, 10));
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(DAVE), coll_id0, item_id0, 50, 10));
+
+		//The listings of the burned tokens are removed with the virtual account
+		assert_ok!(ShareDistributor::close_virtual(coll_id0, item_id0));
+		assert!(ShareDistributor::virtual_acc(coll_id0, item_id0).is_none());
+		assert!(ShareDistributor::listings(0).is_none());
+		assert!(ShareDistributor::listings(1).is_none());
+		assert_eq!(0, TokenListings::<Test>::iter_prefix(id).count());
+		assert_eq!(0, ShareDistributor::listed_tokens(id, EVE));
+		assert_eq!(0, Assets::Pallet::<Test>::balance(id, EVE));
+	});
+}
+
+#[test]
+fn listed_tokens_cannot_be_listed_twice() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let id = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().token_id;
+		RoleModule::set_role(Origin::signed(GERARD), GERARD, Acc::INVESTOR).ok();
+
+		//EVE owns 625 tokens and lists 500 of them
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 500, 10));
+
+		//The listed tokens cannot be listed again, transferred, or sold to the fund
+		assert_noop!(
+			ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 126, 10),
+			Error::<Test>::NotEnoughTokens
+		);
+		assert_noop!(
+			ShareDistributor::transfer_tokens(Origin::signed(EVE), coll_id0, item_id0, GERARD, 126),
+			Error::<Test>::NotEnoughTokens
+		);
+		let origin: OriginFor<Test> = frame_system::RawOrigin::Root.into();
+		assert_ok!(ShareDistributor::set_valuation(origin, coll_id0, item_id0, 20));
+		assert_noop!(
+			ShareDistributor::sell_to_fund(Origin::signed(EVE), coll_id0, item_id0, 126),
+			Error::<Test>::NotEnoughTokens
+		);
+
+		//The unlisted tokens are still free to move
+		assert_ok!(ShareDistributor::list_tokens(Origin::signed(EVE), coll_id0, item_id0, 125, 10));
+		assert_eq!(625, ShareDistributor::listed_tokens(id, EVE));
+		assert_eq!(0, ShareDistributor::unlisted_balance(id, &EVE));
+
+		//Both listings can be filled
+		assert_ok!(ShareDistributor::buy_tokens(Origin::signed(GERARD), 0, 500));
+		assert_ok!(ShareDistributor::buy_tokens(Origin::signed(GERARD), 1, 125));
+		assert_eq!(625, Assets::Pallet::<Test>::balance(id, GERARD));
+		assert_eq!(0, ShareDistributor::listed_tokens(id, EVE));
+	});
+}
+
+#[test]
+fn ownership_tokens_transfers_are_restricted() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap();
+		let id = virtual0.token_id;
+		assert_eq!(Some((coll_id0, item_id0)), ShareDistributor::token_asset(id));
+
+		//Ownership tokens cannot be moved through the assets pallet
+		assert!(Assets::Pallet::<Test>::transfer(Origin::signed(EVE), id, GERARD, 10).is_err());
+		assert_eq!(625, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(0, Assets::Pallet::<Test>::balance(id, GERARD));
+
+		//The receiver must be an investor
+		assert_noop!(
+			ShareDistributor::transfer_tokens(Origin::signed(EVE), coll_id0, item_id0, GERARD, 10),
+			Error::<Test>::NotAnInvestor
+		);
+		RoleModule::set_role(Origin::signed(GERARD), GERARD, Acc::INVESTOR).ok();
+		assert_noop!(
+			ShareDistributor::transfer_tokens(Origin::signed(EVE), coll_id0, item_id0, GERARD, 626),
+			Error::<Test>::NotEnoughTokens
+		);
+		assert_ok!(ShareDistributor::transfer_tokens(
+			Origin::signed(EVE),
+			coll_id0,
+			item_id0,
+			GERARD,
+			10
+		));
+
+		//Balances and ownership records are updated
+		assert_eq!(615, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(10, Assets::Pallet::<Test>::balance(id, GERARD));
+		let owners = ShareDistributor::tokens_infos(virtual0.virtual_account).unwrap().owners;
+		assert!(owners.contains(&(GERARD, 10)));
+		assert!(owners.contains(&(EVE, 615)));
+		assert!(ShareDistributor::virtual_acc(coll_id0, item_id0)
+			.unwrap()
+			.owners
+			.contains(&GERARD));
+
+		//The new owner is also restricted
+		assert!(Assets::Pallet::<Test>::transfer(Origin::signed(GERARD), id, EVE, 10).is_err());
+		assert_eq!(
+			Some(10),
+			<ShareDistributor as Assets::FrozenBalance<_, _, _>>::frozen_balance(id, &GERARD)
+		);
+	});
+}
+
+#[test]
+fn migration_restricts_existing_ownership_tokens() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let id = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().token_id;
+
+		//Tokens created before the transfer restrictions are not connected to their asset
+		TokenAssets::<Test>::remove(id);
+		migration::v0::TransferAllowed::<Test>::put(true);
+		StorageVersion::new(0).put::<ShareDistributor>();
+		assert_ok!(Assets::Pallet::<Test>::transfer(Origin::signed(EVE), id, GERARD, 10));
+
+		migration::migrate_to_v1::<Test>();
+
+		assert_eq!(ShareDistributor::on_chain_storage_version(), STORAGE_VERSION);
+		assert_eq!(Some((coll_id0, item_id0)), ShareDistributor::token_asset(id));
+		assert!(!migration::v0::TransferAllowed::<Test>::exists());
+		assert!(Assets::Pallet::<Test>::transfer(Origin::signed(EVE), id, GERARD, 10).is_err());
+	});
+}
+
+#[test]
+fn housing_fund_buyback_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap();
+		let id = virtual0.token_id;
+		let fund_account = HousingFund::Pallet::<Test>::fund_account_id();
+
+		//No valuation approved yet
+		assert_noop!(
+			ShareDistributor::sell_to_fund(Origin::signed(EVE), coll_id0, item_id0, 100),
+			Error::<Test>::NoValuation
+		);
+		//Only governance can set the valuation
+		assert!(
+			ShareDistributor::set_valuation(Origin::signed(EVE), coll_id0, item_id0, 20).is_err()
+		);
+		let origin: OriginFor<Test> = frame_system::RawOrigin::Root.into();
+		assert_ok!(ShareDistributor::set_valuation(origin, coll_id0, item_id0, 20));
+
+		//GERARD is not an owner
+		assert_noop!(
+			ShareDistributor::sell_to_fund(Origin::signed(GERARD), coll_id0, item_id0, 100),
+			Error::<Test>::NotAnOwner
+		);
+
+		let eve_balance = Balances::free_balance(EVE);
+		let transferable = HousingFund::Pallet::<Test>::fund_balance().transferable;
+		assert_ok!(ShareDistributor::sell_to_fund(Origin::signed(EVE), coll_id0, item_id0, 100));
+
+		//EVE is paid from the transferable fund
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 2_000);
+		assert_eq!(HousingFund::Pallet::<Test>::fund_balance().transferable, transferable - 2_000);
+
+		//The fund account holds the tokens and is an owner of the asset
+		assert_eq!(525, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(100, Assets::Pallet::<Test>::balance(id, fund_account.clone()));
+		let owners = ShareDistributor::tokens_infos(virtual0.virtual_account).unwrap().owners;
+		assert!(owners.contains(&(fund_account.clone(), 100)));
+		assert!(owners.contains(&(EVE, 525)));
+		assert!(ShareDistributor::virtual_acc(coll_id0, item_id0)
+			.unwrap()
+			.owners
+			.contains(&fund_account));
+	});
+}
+
+#[test]
+fn housing_fund_shares_can_be_resold() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap();
+		let id = virtual0.token_id;
+		let account = virtual0.virtual_account;
+		let fund_account = HousingFund::Pallet::<Test>::fund_account_id();
+		RoleModule::set_role(Origin::signed(GERARD), GERARD, Acc::INVESTOR).ok();
+		let origin: OriginFor<Test> = frame_system::RawOrigin::Root.into();
+		assert_ok!(ShareDistributor::set_valuation(origin, coll_id0, item_id0, 20));
+		assert_ok!(ShareDistributor::sell_to_fund(Origin::signed(EVE), coll_id0, item_id0, 100));
+
+		//The rent dividends of the fund tokens are added to the fund
+		<Test as pallet::Config>::Currency::make_free_balance_be(&account, 20_000u32.into());
+		let transferable = HousingFund::Pallet::<Test>::fund_balance().transferable;
+		assert_ok!(ShareDistributor::record_rent(account.clone(), 1_000));
+		assert_ok!(ShareDistributor::distribute_dividends(account, 0, 1_000));
+		assert_eq!(HousingFund::Pallet::<Test>::fund_balance().transferable, transferable + 100);
+
+		//The fund tokens are waiting to be resold at the approved valuation
+		assert_eq!(100, ShareDistributor::fund_shares(coll_id0, item_id0));
+		assert_eq!(Some(2_000), ShareDistributor::fund_shares_price(coll_id0, item_id0));
+
+		//The investors must be fund contributors
+		assert_noop!(
+			ShareDistributor::resell_fund_shares(coll_id0, item_id0, vec![(GERARD, 1_000)]),
+			HousingFund::Error::<Test>::NotAContributor
+		);
+
+		//The tokens are shared in proportion of the contributions, and paid from the fund
+		let transferable = HousingFund::Pallet::<Test>::fund_balance().transferable;
+		let dave_available =
+			HousingFund::Pallet::<Test>::contributions(DAVE).unwrap().available_balance;
+		assert_ok!(ShareDistributor::resell_fund_shares(
+			coll_id0,
+			item_id0,
+			vec![(DAVE, 1_500), (EVE, 500)]
+		));
+		assert_eq!(450, Assets::Pallet::<Test>::balance(id, DAVE));
+		assert_eq!(550, Assets::Pallet::<Test>::balance(id, EVE));
+		assert_eq!(0, Assets::Pallet::<Test>::balance(id, fund_account.clone()));
+		assert!(!ShareDistributor::virtual_acc(coll_id0, item_id0)
+			.unwrap()
+			.owners
+			.contains(&fund_account));
+		assert_eq!(0, ShareDistributor::fund_shares(coll_id0, item_id0));
+		assert_eq!(None, ShareDistributor::fund_shares_price(coll_id0, item_id0));
+
+		//The money stays in the fund, and DAVE paid its tokens with its contribution
+		assert_eq!(HousingFund::Pallet::<Test>::fund_balance().transferable, transferable);
+		assert!(
+			HousingFund::Pallet::<Test>::contributions(DAVE).unwrap().available_balance <
+				dave_available
+		);
+		expect_events(vec![crate::Event::FundSharesResold {
+			token_id: id,
+			buyers: vec![(DAVE, 75), (EVE, 25)],
+			price: 2_000,
+			when: <frame_system::Pallet<Test>>::block_number(),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn rent_dividends_follow_snapshots() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let virtual0 = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap();
+		let account = virtual0.virtual_account;
+		RoleModule::set_role(Origin::signed(GERARD), GERARD, Acc::INVESTOR).ok();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&account, 20_000u32.into());
+
+		//A rent of 1000 is received while EVE owns 625 tokens & DAVE owns 375 tokens
+		assert_ok!(ShareDistributor::record_rent(account.clone(), 1_000));
+		assert_eq!(1, ShareDistributor::rent_period_id(account.clone()));
+
+		//EVE transfers some tokens to GERARD before the distribution
+		assert_ok!(ShareDistributor::transfer_tokens(
+			Origin::signed(EVE),
+			coll_id0,
+			item_id0,
+			GERARD,
+			125
+		));
+
+		let eve_balance = Balances::free_balance(EVE);
+		let dave_balance = Balances::free_balance(DAVE);
+		let gerard_balance = Balances::free_balance(GERARD);
+		assert_eq!(1, ShareDistributor::pending_rent_periods(account.clone()).len());
+		assert_ok!(ShareDistributor::distribute_dividends(account.clone(), 0, 1_000));
+
+		//The rent is shared according to the snapshot
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 625);
+		assert_eq!(Balances::free_balance(DAVE), dave_balance + 375);
+		assert_eq!(Balances::free_balance(GERARD), gerard_balance);
+		assert!(ShareDistributor::rent_periods(account.clone(), 0).is_none());
+		assert!(ShareDistributor::pending_rent_periods(account.clone()).is_empty());
+		assert_noop!(
+			ShareDistributor::distribute_dividends(account.clone(), 0, 1_000),
+			Error::<Test>::AlreadyDistributed
+		);
+
+		//A second rent is received, but the virtual account cannot pay the owners
+		assert_ok!(ShareDistributor::record_rent(account.clone(), 1_000));
+		<Test as pallet::Config>::Currency::make_free_balance_be(&account, 100u32.into());
+		assert_ok!(ShareDistributor::distribute_dividends(account.clone(), 1, 1_000));
+		assert_eq!(125, ShareDistributor::unclaimed_dividends(account.clone(), GERARD));
+		assert_eq!(500, ShareDistributor::unclaimed_dividends(account.clone(), EVE));
+
+		//Lifetime totals include the unclaimed dividends
+		assert_eq!(1_125, ShareDistributor::lifetime_dividends(EVE));
+		assert_eq!(750, ShareDistributor::lifetime_dividends(DAVE));
+		assert_eq!(125, ShareDistributor::lifetime_dividends(GERARD));
+
+		//GERARD claims his dividends
+		<Test as pallet::Config>::Currency::make_free_balance_be(&account, 20_000u32.into());
+		assert_ok!(ShareDistributor::claim_dividends(Origin::signed(GERARD), coll_id0, item_id0));
+		assert_eq!(Balances::free_balance(GERARD), gerard_balance + 125);
+		assert_eq!(0, ShareDistributor::unclaimed_dividends(account, GERARD));
+		assert_noop!(
+			ShareDistributor::claim_dividends(Origin::signed(GERARD), coll_id0, item_id0),
+			Error::<Test>::NoDividends
+		);
+	});
+}
+
+#[test]
+fn rent_dividends_remainder_is_carried_to_the_next_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (coll_id0, item_id0) = prep_virtual_asset();
+		let account = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().virtual_account;
+		<Test as pallet::Config>::Currency::make_free_balance_be(&account, 20_000u32.into());
+
+		//EVE owns 625 tokens & DAVE owns 375 tokens: a rent of 999 leaves a remainder of 1
+		let eve_balance = Balances::free_balance(EVE);
+		let dave_balance = Balances::free_balance(DAVE);
+		assert_ok!(ShareDistributor::record_rent(account.clone(), 999));
+		assert_ok!(ShareDistributor::distribute_dividends(account.clone(), 0, 999));
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 624);
+		assert_eq!(Balances::free_balance(DAVE), dave_balance + 374);
+		assert_eq!(1, ShareDistributor::rent_remainder(account.clone()));
+
+		//The remainder is added to the next rent
+		assert_ok!(ShareDistributor::record_rent(account.clone(), 999));
+		assert_ok!(ShareDistributor::distribute_dividends(account.clone(), 1, 999));
+		assert_eq!(Balances::free_balance(EVE), eve_balance + 1_249);
+		assert_eq!(Balances::free_balance(DAVE), dave_balance + 749);
+		assert_eq!(0, ShareDistributor::rent_remainder(account.clone()));
+
+		//Distributed periods are pruned
+		assert!(ShareDistributor::pending_rent_periods(account.clone()).is_empty());
+		assert_noop!(
+			ShareDistributor::distribute_dividends(account.clone(), 1, 999),
+			Error::<Test>::AlreadyDistributed
+		);
+		assert_noop!(
+			ShareDistributor::distribute_dividends(account, 2, 999),
+			Error::<Test>::InvalidValue
+		);
+	});
+}
@@ -85,3 +85,94 @@ impl<T: Config> Ownership<T> {
 		Ok(())
 	}
 }
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct TokenListing<T: Config> {
+	///Owner selling the ownership tokens
+	pub seller: T::AccountId,
+	///Asset collection id
+	pub collection: T::NftCollectionId,
+	///Asset item id
+	pub item: T::NftItemId,
+	///TokenId
+	pub token_id: <T as pallet::Config>::AssetId,
+	///Number of tokens still on sale
+	pub amount: <T as Assets::Config>::Balance,
+	///Price of a single token in native currency
+	pub price: BalanceOf<T>,
+	///Creation Blocknumber
+	pub created: BlockNumberOf<T>,
+}
+
+impl<T: Config> TokenListing<T> {
+	pub fn new(
+		seller: T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+		amount: <T as Assets::Config>::Balance,
+		price: BalanceOf<T>,
+	) -> Result<u32, DispatchError> {
+		let token_id =
+			Virtual::<T>::get(collection, item).ok_or(Error::<T>::InvalidValue)?.token_id;
+		let created = <frame_system::Pallet<T>>::block_number();
+		let listing_id = ListingId::<T>::get();
+		let listing =
+			TokenListing::<T> { seller, collection, item, token_id, amount, price, created };
+
+		Listings::<T>::insert(listing_id, listing);
+		TokenListings::<T>::insert(token_id, listing_id, ());
+		ListingId::<T>::mutate(|val| {
+			let val0 = *val;
+			*val = val0 + 1;
+		});
+
+		Ok(listing_id)
+	}
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RentPeriod<T: Config> {
+	///Rent received by the virtual account
+	pub amount: BalanceOf<T>,
+	///Owners token balances when the rent was received
+	pub snapshot: BoundedVec<(T::AccountId, <T as Assets::Config>::Balance), T::MaxOwners>,
+	///Total supply of tokens when the rent was received
+	pub supply: <T as Assets::Config>::Balance,
+	///Creation Blocknumber
+	pub created: BlockNumberOf<T>,
+}
+
+impl<T: Config> RentPeriod<T> {
+	pub fn new(virtual_account: T::AccountId, amount: BalanceOf<T>) -> Result<u32, DispatchError> {
+		let tokens = Tokens::<T>::get(&virtual_account).ok_or(Error::<T>::InvalidValue)?;
+		let snapshot = tokens.owners.try_into().map_err(|_| Error::<T>::TooManyOwners)?;
+		let supply = Assets::Pallet::<T>::total_supply(tokens.token_id.into());
+		let created = <frame_system::Pallet<T>>::block_number();
+		let period_id = RentPeriodId::<T>::get(&virtual_account);
+		let period = RentPeriod::<T> { amount, snapshot, supply, created };
+
+		RentPeriods::<T>::insert(&virtual_account, period_id, period);
+		RentPeriodId::<T>::mutate(&virtual_account, |val| {
+			let val0 = *val;
+			*val = val0 + 1;
+		});
+
+		Ok(period_id)
+	}
+}
+
+///Net asset value of the ownership tokens of an asset, used to check secondary market prices
+pub trait NetAssetValue<CollectionId, ItemId> {
+	///Net asset value of an ownership token, in native currency, if the asset has one
+	fn nav_per_token(collection: CollectionId, item: ItemId) -> Option<u128>;
+}
+
+impl<CollectionId, ItemId> NetAssetValue<CollectionId, ItemId> for () {
+	fn nav_per_token(_collection: CollectionId, _item: ItemId) -> Option<u128> {
+		None
+	}
+}
@@ -24,45 +24,201 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	pub fn rent_helper(tenant_account: T::AccountId) -> DispatchResult {
-		let tenant = Roles::Pallet::<T>::tenants(tenant_account.clone()).unwrap();
-		let total_rent = tenant.remaining_rent;
-		let remaining_p = tenant.remaining_payments;
-		let rent0: u128 = Self::roles_bal_to_u128(tenant.rent).unwrap();
-		let rent = Self::u128_to_balance_option(rent0).unwrap();
-		let asset_account = tenant.asset_account.unwrap();
+	///Pay an amount of the rent of a tenant. A failed transfer leaves the rent unpaid.
+	pub fn rent_helper(tenant_account: T::AccountId, amount: u128) -> DispatchResult {
+		let tenant =
+			Roles::Pallet::<T>::tenants(tenant_account.clone()).ok_or(Error::<T>::NotATenant)?;
+		let asset_account = tenant.asset_account.ok_or(Error::<T>::TenantAssetNotLinked)?;
+		let schedule = RentSchedule::<T>::of(&tenant_account).ok_or(Error::<T>::NoRentToPay)?;
+		ensure!(schedule.remaining() > 0, Error::<T>::NoRentToPay);
+		ensure!(amount <= schedule.remaining(), Error::<T>::ExcessiveRentPayment);
+
+		//A share of the rent of a rent-to-own tenant is put aside to buy ownership tokens
+		let equity = Self::rent_to_own_part(&tenant_account, amount);
+		let equity0 = Self::u128_to_balance_option(equity).ok_or(Error::<T>::InvalidRentAmount)?;
+		let rent =
+			Self::u128_to_balance_option(amount - equity).ok_or(Error::<T>::InvalidRentAmount)?;
+		let rent1 = Self::u128_to_share_balance_option(amount - equity)
+			.ok_or(Error::<T>::InvalidRentAmount)?;
+		<T as Config>::Currency::reserve(&tenant_account, equity0)?;
 		<T as Config>::Currency::transfer(
 			&tenant_account,
 			&asset_account,
 			rent,
 			ExistenceRequirement::AllowDeath,
-		)
-		.ok();
+		)?;
 
-		Roles::TenantLog::<T>::mutate(tenant_account, |val| {
-			let mut val0 = val.clone().unwrap();
-			val0.remaining_rent = total_rent.saturating_sub(tenant.rent);
-			val0.remaining_payments = remaining_p - 1;
-			*val = Some(val0);
+		//A payment made while in arrears is late
+		let late = Self::active_lease(&tenant_account)
+			.map(|lease_id| Arrears::<T>::contains_key(lease_id, &tenant_account))
+			.unwrap_or(false);
+		Self::update_ledger(&tenant_account, |ledger| {
+			if late {
+				ledger.late = ledger.late.saturating_add(1);
+			} else {
+				ledger.on_time = ledger.on_time.saturating_add(1);
+			}
 		});
 
+		Self::apply_rent_payment(&tenant_account, amount);
+
+		//The rent opens a new rent period, with a snapshot of the owners shares
+		Share::Pallet::<T>::record_rent(asset_account.clone(), rent1)?;
+
 		//Paying the rent will increment the `awaiting_number_of_rents` field
 		// in the Share_distributor --> Virtual storage --> Ownership struct
-		let ownership_infos = Share::Virtual::<T>::iter_keys();
-		for (i, j) in ownership_infos {
-			let infos = Share::Pallet::<T>::virtual_acc(i, j).unwrap();
-			if infos.virtual_account == asset_account {
-				Share::Virtual::<T>::mutate(i, j, |val| {
-					let mut val0 = val.clone().unwrap();
-					val0.rent_nbr += 1;
-					*val = Some(val0);
-				});
+		let (collection, item) =
+			Share::Pallet::<T>::virtual_asset(&asset_account).ok_or(Error::<T>::NotAnAsset)?;
+		Share::Virtual::<T>::mutate(collection, item, |val| {
+			if let Some(val0) = val {
+				val0.rent_nbr = val0.rent_nbr.saturating_add(1);
 			}
+		});
+
+		if equity > 0 {
+			RentToOwnContracts::<T>::mutate(&tenant_account, |val| {
+				if let Some(val0) = val {
+					val0.credit = val0.credit.saturating_add(equity0);
+				}
+			});
+			Self::buy_ownership_tokens(&tenant_account)?;
+		}
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let tenant = Roles::Pallet::<T>::tenants(&tenant_account).ok_or(Error::<T>::NotATenant)?;
+		Self::deposit_event(Event::RentPayment {
+			tenant: tenant_account,
+			when: now,
+			asset_account,
+			amount: Self::u128_to_roles_balance_option(amount).unwrap_or_default(),
+			remaining: tenant.remaining_rent,
+		});
+
+		Ok(())
+	}
+
+	///Record a rent payment of a tenant. Partial payments decrease the remaining rent, and the
+	///remaining payments count the rents which are not fully payed.
+	pub fn apply_rent_payment(tenant: &T::AccountId, amount: u128) {
+		Roles::TenantLog::<T>::mutate(tenant, |val| {
+			if let Some(val0) = val {
+				let amount0 = Self::u128_to_roles_balance_option(amount).unwrap_or_default();
+				val0.remaining_rent = val0.remaining_rent.saturating_sub(amount0);
+				let rent = Self::roles_bal_to_u128(val0.rent).unwrap_or_default().max(1);
+				let remaining = Self::roles_bal_to_u128(val0.remaining_rent).unwrap_or_default();
+				let rents = remaining.saturating_add(rent - 1) / rent;
+				val0.remaining_payments = rents.min(val0.remaining_payments as u128) as u8;
+			}
+		});
+	}
+
+	///Share of a rent payment put aside to buy ownership tokens, for a rent-to-own tenant
+	pub fn rent_to_own_part(tenant: &T::AccountId, amount: u128) -> u128 {
+		Self::rent_to_own(tenant)
+			.and_then(|contract| {
+				Assets::Pallet::<T>::rent_to_own_share(contract.collection, contract.item)
+			})
+			.map(|share| share.mul_floor(amount))
+			.unwrap_or_default()
+	}
+
+	///Ownership tokens of the asset of a rent-to-own tenant which are for sale: the tokens of
+	///the consenting owners, then the tokens held by the housing fund
+	pub fn tokens_for_sale(
+		tenant: &T::AccountId,
+		contract: &RentToOwn<T>,
+	) -> Vec<(T::AccountId, u128)> {
+		let ownership = match Share::Pallet::<T>::virtual_acc(contract.collection, contract.item) {
+			Some(ownership) => ownership,
+			None => return Vec::new(),
+		};
+		let mut sellers = Assets::Pallet::<T>::rent_to_own_sellers(&contract.asset_account);
+		sellers.push(Share::HousingFund::Pallet::<T>::fund_account_id());
+		sellers
+			.into_iter()
+			.filter(|seller| seller != tenant && ownership.owners.contains(seller))
+			.map(|seller| {
+				let balance = Share::Pallet::<T>::unlisted_balance(ownership.token_id, &seller);
+				(seller, balance.saturated_into::<u128>())
+			})
+			.filter(|(_, tokens)| *tokens > 0)
+			.collect()
+	}
+
+	///Buy ownership tokens with the credit of a rent-to-own tenant, at the net asset value per
+	///token. The rest of the credit is kept for the next purchases.
+	pub fn buy_ownership_tokens(tenant: &T::AccountId) -> DispatchResult {
+		let mut contract = Self::rent_to_own(tenant).ok_or(Error::<T>::NotARentToOwnTenant)?;
+		let nav = match Assets::Pallet::<T>::nav_per_token(contract.collection, contract.item) {
+			Ok(nav) if nav > 0 => nav,
+			_ => return Ok(()),
+		};
+		let fund_account = Share::HousingFund::Pallet::<T>::fund_account_id();
+
+		for (seller, available) in Self::tokens_for_sale(tenant, &contract) {
+			let credit = Self::balance_to_u128_option(contract.credit).unwrap_or_default();
+			let tokens = available.min(credit / nav);
+			if tokens == 0 {
+				break
+			}
+			let cost = tokens.saturating_mul(nav);
+			let cost0 = Self::u128_to_balance_option(cost).ok_or(Error::<T>::InvalidRentAmount)?;
+
+			//Move the tokens, and pay the seller with the credit
+			Share::Pallet::<T>::transfer_ownership_tokens(
+				contract.collection,
+				contract.item,
+				seller.clone(),
+				tenant.clone(),
+				tokens.saturated_into(),
+			)?;
+			<T as Config>::Currency::repatriate_reserved(
+				tenant,
+				&seller,
+				cost0,
+				BalanceStatus::Free,
+			)?;
+			if seller == fund_account {
+				Share::HousingFund::Pallet::<T>::record_shares_sale(
+					contract.collection,
+					contract.item,
+					tenant.clone(),
+					cost.saturated_into(),
+				);
+			}
+
+			contract.credit = contract.credit.saturating_sub(cost0);
+			contract.tokens = contract.tokens.saturating_add(tokens);
+			contract.payed = contract.payed.saturating_add(cost0);
+			Self::deposit_event(Event::OwnershipTokensBought {
+				tenant: tenant.clone(),
+				seller,
+				tokens,
+				cost: cost0,
+			});
 		}
+		RentToOwnContracts::<T>::insert(tenant, contract);
 
 		Ok(())
 	}
 
+	///Close the rent-to-own contract of a leaving tenant. The unused credit is refunded to the
+	///tenant.
+	pub fn close_rent_to_own(tenant: &T::AccountId) {
+		let mut contract = match RentToOwnContracts::<T>::take(tenant) {
+			Some(contract) => contract,
+			None => return,
+		};
+		let missing = <T as Config>::Currency::unreserve(tenant, contract.credit);
+		contract.credit = contract.credit.saturating_sub(missing);
+
+		Self::deposit_event(Event::RentToOwnClosed {
+			tenant: tenant.clone(),
+			tokens: contract.tokens,
+			credit: contract.credit,
+		});
+	}
+
 	pub fn payment_helper(
 		from: OriginFor<T>,
 		virtual_account: T::AccountId,
@@ -71,23 +227,884 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let tenant = ensure_signed(from.clone())?;
 
-		//Accept and pay the guaranty
-		Payment::Pallet::<T>::accept_and_pay(from, virtual_account.clone()).ok();
+		//Accept and pay the guaranty, the payment state becomes PaymentCompleted
+		let payment_id = Assets::Pallet::<T>::guaranty_id(&tenant, &virtual_account)
+			.ok_or(Error::<T>::NotAValidPayment)?;
+		Payment::Pallet::<T>::accept_and_pay(from, payment_id).map_err(|err| err.error)?;
 		let origin2 = frame_system::RawOrigin::Signed(virtual_account.clone());
 
-		//Change payment state in Asset_Management storage
-		Assets::GuarantyPayment::<T>::mutate(tenant.clone(), virtual_account, |val| {
-			let mut infos = val.clone().unwrap();
-			infos.state = Payment::PaymentState::PaymentCompleted;
-			*val = Some(infos);
+		//Connect tenant with asset
+		Assets::Pallet::<T>::link_tenant_to_asset(
+			origin2.into(),
+			tenant.clone(),
+			collection,
+			item,
+		)?;
+
+		//Start the lease of the tenant
+		let linked = Roles::Pallet::<T>::tenants(&tenant)
+			.map(|infos| infos.asset_account == Some(virtual_account.clone()))
+			.unwrap_or(false);
+		if linked {
+			Self::release_applicants(&tenant, &virtual_account, collection, item);
+			let deposit = Assets::Pallet::<T>::guaranty(&tenant, &virtual_account)
+				.map(|payment| payment.amount)
+				.unwrap_or_default();
+			//A tenant sharing the rent joins the running lease of the co-tenants
+			if let Some(lease_id) = Self::shared_lease(&tenant, &virtual_account, collection, item)
+			{
+				return Self::join_lease(lease_id, tenant, deposit)
+			}
+			let lease_id = Lease::<T>::new(
+				tenant.clone(),
+				virtual_account.clone(),
+				collection,
+				item,
+				deposit,
+			)?;
+			let end = Self::leases(lease_id).unwrap().end;
+			Self::deposit_event(Event::LeaseStarted {
+				lease_id,
+				tenant,
+				asset_account: virtual_account,
+				end,
+			});
+		}
+
+		Ok(())
+	}
+
+	///Application of a tenant in the queue of an asset
+	pub fn application(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+	) -> Option<Application<T>> {
+		Self::applications(asset_account)
+			.into_iter()
+			.find(|application| application.tenant == *tenant)
+	}
+
+	pub fn update_application(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+		f: impl FnOnce(&mut Application<T>),
+	) {
+		Applications::<T>::mutate(asset_account, |val| {
+			if let Some(application) = val.iter_mut().find(|val0| val0.tenant == *tenant) {
+				f(application);
+			}
 		});
+	}
 
-		//Connect tenant with asset
-		Assets::Pallet::<T>::link_tenant_to_asset(origin2.into(), tenant, collection, item).ok();
+	///Add a tenant to the application queue of an asset. The application of the tenant for the
+	///asset he requested before is withdrawn, and a closed application is opened again.
+	pub fn queue_application(
+		tenant: &T::AccountId,
+		previous: Option<T::AccountId>,
+		asset_account: &T::AccountId,
+	) -> DispatchResult {
+		if let Some(previous) = previous.filter(|account| account != asset_account) {
+			Self::withdraw_application(tenant, &previous);
+		}
+		match Self::application(tenant, asset_account).map(|application| application.status) {
+			Some(ApplicationStatus::Pending) |
+			Some(ApplicationStatus::Shortlisted) |
+			Some(ApplicationStatus::Selected) => (),
+			Some(_) => Self::update_application(tenant, asset_account, |val| {
+				*val = Application::<T>::new(tenant.clone());
+			}),
+			None => Applications::<T>::try_mutate(asset_account, |val| {
+				val.try_push(Application::<T>::new(tenant.clone()))
+					.map_err(|_| Error::<T>::TooManyApplications)
+			})?,
+		}
+		Ok(())
+	}
+
+	pub fn withdraw_application(tenant: &T::AccountId, asset_account: &T::AccountId) {
+		Applications::<T>::mutate(asset_account, |val| {
+			val.retain(|application| application.tenant != *tenant);
+		});
+		Shortlists::<T>::mutate(asset_account, |val| {
+			val.retain(|applicant| applicant != tenant);
+		});
+	}
+
+	///An asset is let when the rent shares of its tenants cover the whole rent
+	pub fn asset_let(
+		asset_account: &T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> bool {
+		Assets::Onboarding::Pallet::<T>::houses(collection, item)
+			.map(|house| {
+				house
+					.tenants
+					.iter()
+					.map(|tenant| {
+						Assets::Pallet::<T>::rent_share(tenant, asset_account).deconstruct() as u32
+					})
+					.sum::<u32>() >= 100
+			})
+			.unwrap_or(false)
+	}
+
+	///Close the application of a tenant linked to an asset. Once the asset is let, the other
+	///applicants are released, and can request another asset.
+	pub fn release_applicants(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) {
+		Self::withdraw_application(tenant, asset_account);
+		if !Self::asset_let(asset_account, collection, item) {
+			return
+		}
+
+		Shortlists::<T>::remove(asset_account);
+		let mut released = Vec::new();
+		Applications::<T>::mutate(asset_account, |val| {
+			for application in val.iter_mut() {
+				if application.status != ApplicationStatus::Released {
+					application.status = ApplicationStatus::Released;
+					released.push(application.tenant.clone());
+				}
+			}
+		});
+		for applicant in released {
+			Tenants::<T>::mutate(&applicant, |val| {
+				if let Some(val0) = val {
+					if val0.asset_requested.as_ref() == Some(asset_account) {
+						val0.asset_requested = None;
+					}
+				}
+			});
+			Self::deposit_event(Event::ApplicantReleased {
+				tenant: applicant,
+				asset_account: asset_account.clone(),
+			});
+		}
+	}
+
+	///Number of blocks of a lease of the given number of months
+	pub fn lease_blocks(months: u32) -> BlockNumberOf<T> {
+		let year: BlockNumberOf<T> = <T as Assets::Config>::ContractLength::get();
+		let lease = <T as Assets::Config>::Lease::get().max(1);
+		year.saturating_mul(months.into()) / lease.into()
+	}
+
+	///Schedule the end of a lease at the first block from `end` with room left, and return it
+	pub fn schedule_lease_end(lease_id: u32, end: BlockNumberOf<T>) -> BlockNumberOf<T> {
+		let mut when = end;
+		while LeaseExpiries::<T>::try_mutate(when, |val| val.try_push(lease_id)).is_err() {
+			when = when.saturating_add(One::one());
+		}
+		when
+	}
+
+	pub fn unschedule_lease_end(lease_id: u32, end: BlockNumberOf<T>) {
+		LeaseExpiries::<T>::mutate(end, |val| {
+			val.retain(|id| *id != lease_id);
+		});
+	}
+
+	///Check that an account is the representative of an asset
+	pub fn is_asset_representative(
+		account: &T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> bool {
+		Assets::Onboarding::Pallet::<T>::houses(collection, item)
+			.map(|house| house.representative == Some(account.clone()))
+			.unwrap_or(false)
+	}
+
+	///The function below closes or renews the leases ending at the current block
+	pub fn begin_block(now: BlockNumberOf<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		for lease_id in LeaseExpiries::<T>::take(now) {
+			let accepted = Self::renewal_offers(lease_id).filter(|offer| {
+				Self::leases(lease_id)
+					.map(|lease| offer.accepted_by_all(&lease.tenants))
+					.unwrap_or(false)
+			});
+			match accepted {
+				Some(offer) => Self::renew_lease(lease_id, offer, now),
+				None => Self::end_lease(lease_id, now),
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 4));
+		}
+
+		//Settle the deposit returns of the ended leases
+		for (lease_id, tenant) in DepositSettlements::<T>::take(now) {
+			if let Some(deposit_return) = Self::deposit_returns(lease_id, tenant) {
+				Self::settle_deposit_return(lease_id, deposit_return, now);
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 6));
+		}
+
+		//Start the arrears check of the running leases, executed in `on_idle`
+		if (now % <T as Assets::Config>::RentCheck::get()).is_zero() {
+			if !ArrearsScan::<T>::exists() {
+				ArrearsScan::<T>::put(Vec::<u8>::new());
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+		weight
+	}
+
+	///The function below checks the arrears of the running leases within the remaining weight
+	///of the block. The running leases are first queued by a scan of `ActiveLeases`, which
+	///resumes in the next block once the weight is used or the queue is full.
+	pub fn finish_block(now: BlockNumberOf<T>, max_weight: Weight) -> Weight {
+		let scan_weight = T::DbWeight::get().reads(1);
+		let check_weight = T::DbWeight::get().reads_writes(8, 6);
+		let mut used_weight = T::DbWeight::get().reads_writes(2, 2);
+		if used_weight > max_weight {
+			return Weight::zero()
+		}
+		let mut queue = Self::arrears_queue();
+
+		//Queue the running leases, co-tenants sharing the same lease
+		if let Some(cursor) = Self::arrears_scan() {
+			let mut leases = if cursor.is_empty() {
+				ActiveLeases::<T>::iter()
+			} else {
+				ActiveLeases::<T>::iter_from(cursor)
+			};
+			let mut finished = true;
+			loop {
+				if queue.len() as u32 >= T::MaxQueuedLeases::get() ||
+					used_weight.saturating_add(scan_weight) > max_weight
+				{
+					finished = false;
+					break
+				}
+				match leases.next() {
+					Some((_, lease_id)) => {
+						used_weight = used_weight.saturating_add(scan_weight);
+						if !queue.contains(&lease_id) {
+							queue.try_push(lease_id).ok();
+						}
+					},
+					None => break,
+				}
+			}
+			if finished {
+				ArrearsScan::<T>::kill();
+			} else {
+				ArrearsScan::<T>::put(leases.last_raw_key().to_vec());
+			}
+		}
+
+		//Check the arrears of the queued leases
+		let mut checked = 0;
+		for lease_id in queue.iter() {
+			if used_weight.saturating_add(check_weight) > max_weight {
+				break
+			}
+			Self::check_lease_arrears(*lease_id, now);
+			used_weight = used_weight.saturating_add(check_weight);
+			checked += 1;
+		}
+		let remaining = BoundedVec::<_, T::MaxQueuedLeases>::try_from(queue[checked..].to_vec());
+		ArrearsQueue::<T>::put(remaining.unwrap_or_default());
+		used_weight
+	}
+
+	///Start a new term of a lease, with the terms of the accepted renewal offer
+	pub fn renew_lease(lease_id: u32, offer: RenewalOffer<T>, now: BlockNumberOf<T>) {
+		let lease = match Self::leases(lease_id) {
+			Some(lease) => lease,
+			None => return,
+		};
+		let end = Self::schedule_lease_end(
+			lease_id,
+			now.saturating_add(Self::lease_blocks(offer.months)),
+		);
+		let mut lease_rent: Roles::BalanceOf<T> = Zero::zero();
+
+		//Update the tenants contract, with the share of the rent of each co-tenant
+		for tenant in lease.tenants.iter() {
+			let rent =
+				Assets::Pallet::<T>::rent_share(tenant, &lease.asset_account).mul_floor(offer.rent);
+			let rent0 = Self::roles_bal_to_u128(rent).unwrap_or_default();
+			let total_rent =
+				Self::u128_to_roles_balance_option(rent0.saturating_mul(offer.months as u128))
+					.unwrap_or_default();
+			lease_rent = lease_rent.saturating_add(rent);
+			Roles::TenantLog::<T>::mutate(tenant, |val| {
+				if let Some(val0) = val {
+					val0.rent = rent;
+					val0.remaining_rent = total_rent;
+					val0.remaining_payments = offer.months.saturated_into();
+					val0.contract_start = now;
+				}
+			});
+			Assets::TenantLeases::<T>::insert(tenant, offer.months);
+		}
+
+		Leases::<T>::mutate(lease_id, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.rent = lease_rent;
+			val0.start = now;
+			val0.end = end;
+			*val = Some(val0);
+		});
+		RenewalOffers::<T>::remove(lease_id);
+
+		Self::deposit_event(Event::LeaseRenewed { lease_id, start: now, end });
+	}
+
+	///End a lease, and unlink its tenants from the asset
+	pub fn end_lease(lease_id: u32, now: BlockNumberOf<T>) {
+		let lease = match Self::leases(lease_id) {
+			Some(lease) => lease,
+			None => return,
+		};
+		let origin: OriginFor<T> = RawOrigin::Signed(lease.asset_account.clone()).into();
+		for tenant in lease.tenants.iter() {
+			//An evicted tenant was already unlinked from the asset
+			let linked = Roles::Pallet::<T>::tenants(tenant).and_then(|infos| infos.asset_account) ==
+				Some(lease.asset_account.clone());
+			if linked {
+				Self::update_ledger(tenant, |ledger| {
+					ledger.completed_leases = ledger.completed_leases.saturating_add(1);
+				});
+			}
+			Assets::Pallet::<T>::unlink_tenant_to_asset(
+				origin.clone(),
+				tenant.clone(),
+				lease.collection,
+				lease.item,
+			)
+			.ok();
+			ActiveLeases::<T>::remove(tenant);
+			RentPeriods::<T>::remove(tenant);
+			Self::close_rent_to_own(tenant);
+
+			//The guaranty deposit is returned after the deductions claims
+			if !DepositReturns::<T>::contains_key(lease_id, tenant) {
+				Self::open_deposit_return(&lease, tenant.clone());
+			}
+		}
+
+		Leases::<T>::mutate(lease_id, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.status = LeaseStatus::Ended;
+			val0.end = now;
+			*val = Some(val0);
+		});
+		RenewalOffers::<T>::remove(lease_id);
+
+		Self::deposit_event(Event::LeaseEnded {
+			lease_id,
+			asset_account: lease.asset_account,
+			when: now,
+		});
+	}
+
+	///Check the arrears of each tenant of a lease. A tenant evicted by the owners leaves the
+	///lease, which ends with its last tenant.
+	pub fn check_lease_arrears(lease_id: u32, now: BlockNumberOf<T>) {
+		let lease = match Self::leases(lease_id) {
+			Some(lease) if lease.status != LeaseStatus::Ended => lease,
+			_ => return,
+		};
+
+		for tenant in lease.tenants.iter() {
+			let evicted = Roles::Pallet::<T>::tenants(tenant).and_then(|infos| infos.asset_account) !=
+				Some(lease.asset_account.clone());
+			if evicted {
+				Self::update_ledger(tenant, |ledger| {
+					ledger.evictions = ledger.evictions.saturating_add(1);
+				});
+				Self::remove_tenant(lease_id, tenant.clone(), now);
+			} else {
+				Self::check_tenant_arrears(lease_id, tenant.clone(), now);
+			}
+		}
+	}
+
+	///Record the arrears of a tenant. Once the grace period is over, a late fee is charged at
+	///each check and the arrears are recovered from the guaranty deposit of the tenant. An
+	///eviction vote session is started when the tenant stays in arrears for
+	///`EvictionThreshold` checks.
+	pub fn check_tenant_arrears(lease_id: u32, tenant: T::AccountId, now: BlockNumberOf<T>) {
+		let lease = match Self::leases(lease_id) {
+			Some(lease) => lease,
+			None => return,
+		};
+		let debt = Assets::Pallet::<T>::tenant_debt(&tenant, now);
+		let amount = Self::u128_to_roles_balance_option(debt).unwrap_or_default();
+		let mut record = match Self::arrears(lease_id, &tenant) {
+			Some(record) => record,
+			None if debt > 0 => {
+				Self::deposit_event(Event::LeaseInArrears {
+					lease_id,
+					tenant: tenant.clone(),
+					amount,
+					when: now,
+				});
+				ArrearsRecord::<T>::new(now)
+			},
+			None => return,
+		};
+		record.amount = amount;
+
+		let late = now >= record.since.saturating_add(T::ArrearsGracePeriod::get());
+		if late && debt > 0 {
+			//The fee is charged on the unpaid rent and on the previous fees
+			let fee = T::LateFee::get().mul_floor(record.amount.saturating_add(record.fees));
+			record.fees = record.fees.saturating_add(fee);
+			record.late_periods = record.late_periods.saturating_add(1);
+
+			//Each unpaid rent is counted once as missed
+			let unpaid = Self::unpaid_rents(&tenant, debt);
+			if unpaid > record.missed_rents {
+				Self::update_ledger(&tenant, |ledger| {
+					ledger.missed = ledger.missed.saturating_add(unpaid - record.missed_rents);
+				});
+			}
+			Self::deposit_event(Event::LateFeeCharged {
+				lease_id,
+				tenant: tenant.clone(),
+				fee,
+				total_fees: record.fees,
+				late_periods: record.late_periods,
+			});
+		}
+		if late {
+			Self::deduct_from_guaranty(&lease, &tenant, &mut record, now);
+			let debt = Self::roles_bal_to_u128(record.amount).unwrap_or_default();
+			record.missed_rents = Self::unpaid_rents(&tenant, debt);
+		}
+
+		if record.is_cleared() {
+			Arrears::<T>::remove(lease_id, &tenant);
+			Self::deposit_event(Event::ArrearsCleared { lease_id, tenant, when: now });
+			return
+		}
+
+		//After a rejected eviction session, a new one can be started once the threshold is
+		//reached again
+		if let Some(index) = record.eviction {
+			let rejected = Assets::Pallet::<T>::proposals(index)
+				.map(|proposal| proposal.vote_result == Assets::VoteResult::REJECTED)
+				.unwrap_or(true);
+			if rejected {
+				record.eviction = None;
+				record.late_periods = 0;
+			}
+		}
+		if record.eviction.is_none() && record.late_periods >= T::EvictionThreshold::get() {
+			let session = Assets::Pallet::<T>::start_tenant_eviction(
+				tenant.clone(),
+				lease.collection,
+				lease.item,
+			);
+			if let Ok(referendum_index) = session {
+				record.eviction = Some(referendum_index);
+				Self::deposit_event(Event::EvictionStarted {
+					lease_id,
+					tenant: tenant.clone(),
+					referendum_index,
+				});
+			}
+		}
+
+		Arrears::<T>::insert(lease_id, &tenant, record);
+	}
+
+	///Recover the arrears of a tenant from his guaranty deposit, which is reserved in the asset
+	///account. The unpaid rents are payed first, then the late fees.
+	pub fn deduct_from_guaranty(
+		lease: &Lease<T>,
+		tenant: &T::AccountId,
+		record: &mut ArrearsRecord<T>,
+		now: BlockNumberOf<T>,
+	) {
+		let payment_id = match Assets::Pallet::<T>::guaranty_id(tenant, &lease.asset_account) {
+			Some(payment_id) => payment_id,
+			None => return,
+		};
+		let initial_deposit = Assets::Pallet::<T>::guaranty(tenant, &lease.asset_account)
+			.and_then(|payment| Self::payment_bal_to_u128(payment.amount))
+			.unwrap_or_default();
+
+		//The deposit pays whole rents
+		let mut rents = 0;
+		let mut rent = 0;
+		if let Some(infos) = Roles::Pallet::<T>::tenants(tenant) {
+			rent = Self::roles_bal_to_u128(infos.rent).unwrap_or_default();
+			let debt = Assets::Pallet::<T>::tenant_debt(tenant, now);
+			if rent > 0 && debt > 0 {
+				rents = debt
+					.saturating_add(rent - 1)
+					.saturating_div(rent)
+					.min(initial_deposit / rent)
+					.min(infos.remaining_payments as u128);
+			}
+		}
+		let mut deposit = initial_deposit.saturating_sub(rents.saturating_mul(rent));
+
+		//The rest of the deposit pays the late fees
+		let fees = Self::roles_bal_to_u128(record.fees).unwrap_or_default();
+		let recovered = fees.min(deposit);
+		deposit = deposit.saturating_sub(recovered);
+
+		let deducted = initial_deposit.saturating_sub(deposit);
+		if deducted == 0 {
+			return
+		}
+
+		//Release the deducted amount to the asset account, the arrears are only recovered
+		//once the deposit was released
+		let amount = Self::u128_to_payment_balance_option(deducted).unwrap_or_default();
+		if let Err(err) = Payment::Pallet::<T>::release_partial_amount(payment_id, amount) {
+			log::warn!(
+				target: "runtime::tenancy",
+				"Unable to deduct {:?} from the guaranty deposit of {:?}: {:?}",
+				amount,
+				tenant,
+				err
+			);
+			return
+		}
+		if rents > 0 {
+			Self::pay_rent_from_deposit(tenant, lease, rents as u8, rent);
+		}
+		let debt = Assets::Pallet::<T>::tenant_debt(tenant, now);
+		record.amount = Self::u128_to_roles_balance_option(debt).unwrap_or_default();
+		record.fees = Self::u128_to_roles_balance_option(fees - recovered).unwrap_or_default();
+		Self::record_deduction(tenant, deducted);
+		Leases::<T>::mutate(lease.id, |val| {
+			if let Some(val0) = val {
+				val0.deposit = val0.deposit.saturating_sub(amount);
+			}
+		});
+
+		Self::deposit_event(Event::GuarantyDeducted {
+			lease_id: lease.id,
+			tenant: tenant.clone(),
+			amount,
+			remaining_deposit: Self::u128_to_payment_balance_option(deposit).unwrap_or_default(),
+		});
+	}
+
+	///Number of rents, started or not, covered by the unpaid rent of a tenant
+	pub fn unpaid_rents(tenant: &T::AccountId, debt: u128) -> u32 {
+		let rent = Roles::Pallet::<T>::tenants(tenant)
+			.and_then(|infos| Self::roles_bal_to_u128(infos.rent))
+			.unwrap_or_default();
+		if rent == 0 {
+			return 0
+		}
+		debt.saturating_add(rent - 1)
+			.saturating_div(rent)
+			.try_into()
+			.unwrap_or(u32::MAX)
+	}
+
+	///Open a rent period for a rent received outside of `rent_helper`, where a failure cannot
+	///revert the payment
+	pub fn record_rent_or_warn(asset_account: &T::AccountId, rent: Share::BalanceOf<T>) {
+		if let Err(err) = Share::Pallet::<T>::record_rent(asset_account.clone(), rent) {
+			log::warn!(
+				target: "runtime::tenancy",
+				"Unable to record a rent of {:?} for {:?}: {:?}",
+				rent,
+				asset_account,
+				err
+			);
+		}
+	}
+
+	///Pay rents of a tenant with the guaranty deposit already held by the asset account
+	pub fn pay_rent_from_deposit(tenant: &T::AccountId, lease: &Lease<T>, rents: u8, rent: u128) {
+		Self::apply_rent_payment(tenant, rent.saturating_mul(rents as u128));
+
+		//Each rent opens a new rent period, as in `rent_helper`
+		let rent1 = Self::u128_to_share_balance_option(rent).unwrap_or_default();
+		for _ in 0..rents {
+			Self::record_rent_or_warn(&lease.asset_account, rent1);
+		}
+		Share::Virtual::<T>::mutate(lease.collection, lease.item, |val| {
+			if let Some(val0) = val {
+				val0.rent_nbr = val0.rent_nbr.saturating_add(rents as u32);
+			}
+		});
+	}
+
+	///Running lease of the co-tenants of an asset, joined by a tenant with a share of the rent
+	pub fn shared_lease(
+		tenant: &T::AccountId,
+		asset_account: &T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+	) -> Option<u32> {
+		let (account, _) = Assets::Pallet::<T>::tenant_shares(tenant)?;
+		if account != *asset_account {
+			return None
+		}
+		let house = Assets::Onboarding::Pallet::<T>::houses(collection, item)?;
+		house
+			.tenants
+			.iter()
+			.filter(|co_tenant| *co_tenant != tenant)
+			.filter_map(|co_tenant| Self::active_lease(co_tenant))
+			.find(|lease_id| {
+				Self::leases(lease_id)
+					.map(|lease| {
+						lease.status == LeaseStatus::Active && lease.asset_account == *asset_account
+					})
+					.unwrap_or(false)
+			})
+	}
+
+	///Add a co-tenant to a running lease. The contract of the co-tenant ends with the lease.
+	pub fn join_lease(
+		lease_id: u32,
+		tenant: T::AccountId,
+		deposit: Payment::BalanceOf<T>,
+	) -> DispatchResult {
+		let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+		let infos = Roles::Pallet::<T>::tenants(&tenant).ok_or(Error::<T>::NotATenant)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		//Number of months left in the lease, rounded up
+		let year: u128 = <T as Assets::Config>::ContractLength::get().saturated_into();
+		let lease_months = <T as Assets::Config>::Lease::get() as u128;
+		let remaining: u128 = lease.end.saturating_sub(now).saturated_into();
+		let months = remaining
+			.saturating_mul(lease_months)
+			.saturating_add(year.saturating_sub(1))
+			.saturating_div(year.max(1))
+			.clamp(1, lease_months.max(1)) as u32;
+
+		let rent0 = Self::roles_bal_to_u128(infos.rent).ok_or(Error::<T>::InvalidRentAmount)?;
+		let total_rent = Self::u128_to_roles_balance_option(rent0.saturating_mul(months as u128))
+			.ok_or(Error::<T>::InvalidRentAmount)?;
+		Roles::TenantLog::<T>::mutate(&tenant, |val| {
+			if let Some(val0) = val {
+				val0.remaining_rent = total_rent;
+				val0.remaining_payments = months as u8;
+			}
+		});
+		Assets::TenantLeases::<T>::insert(&tenant, months);
+
+		Leases::<T>::mutate(lease_id, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.tenants.push(tenant.clone());
+			val0.rent = val0.rent.saturating_add(infos.rent);
+			val0.deposit = val0.deposit.saturating_add(deposit);
+			*val = Some(val0);
+		});
+		ActiveLeases::<T>::insert(&tenant, lease_id);
+
+		let share = Assets::Pallet::<T>::rent_share(&tenant, &lease.asset_account);
+		Self::deposit_event(Event::CoTenantJoined { lease_id, tenant, share });
 
 		Ok(())
 	}
 
+	///Remove a tenant unlinked from the asset from his lease. The lease ends with its last
+	///tenant.
+	pub fn remove_tenant(lease_id: u32, tenant: T::AccountId, now: BlockNumberOf<T>) {
+		let lease = match Self::leases(lease_id) {
+			Some(lease) => lease,
+			None => return,
+		};
+		if lease.tenants.len() <= 1 {
+			Self::unschedule_lease_end(lease_id, lease.end);
+			Self::end_lease(lease_id, now);
+			return
+		}
+
+		let rent = Roles::Pallet::<T>::tenants(&tenant).map(|infos| infos.rent).unwrap_or_default();
+		let deposit = Assets::Pallet::<T>::guaranty(&tenant, &lease.asset_account)
+			.map(|payment| payment.amount)
+			.unwrap_or_default();
+		Leases::<T>::mutate(lease_id, |val| {
+			let mut val0 = val.clone().unwrap();
+			val0.tenants.retain(|t| *t != tenant);
+			val0.rent = val0.rent.saturating_sub(rent);
+			val0.deposit = val0.deposit.saturating_sub(deposit);
+			*val = Some(val0);
+		});
+		ActiveLeases::<T>::remove(&tenant);
+		RentPeriods::<T>::remove(&tenant);
+		Self::close_rent_to_own(&tenant);
+		if !DepositReturns::<T>::contains_key(lease_id, &tenant) {
+			Self::open_deposit_return(&lease, tenant.clone());
+		}
+
+		Self::deposit_event(Event::CoTenantLeft { lease_id, tenant, when: now });
+	}
+
+	///Open the return of the guaranty deposit of a tenant leaving a lease. The arrears of the
+	///tenant are claimed on the deposit.
+	pub fn open_deposit_return(lease: &Lease<T>, tenant: T::AccountId) {
+		let mut claims = Vec::new();
+		if let Some(record) = Arrears::<T>::take(lease.id, &tenant) {
+			let deposit = Assets::Pallet::<T>::guaranty(&tenant, &lease.asset_account)
+				.and_then(|payment| Self::payment_bal_to_u128(payment.amount))
+				.unwrap_or_default();
+			let arrears = Self::roles_bal_to_u128(record.amount.saturating_add(record.fees))
+				.unwrap_or_default()
+				.min(deposit);
+			if arrears > 0 {
+				claims.push(DeductionClaim::<T> {
+					reason: DeductionReason::UnpaidRent,
+					amount: Self::u128_to_payment_balance_option(arrears).unwrap_or_default(),
+					evidence: None,
+				});
+			}
+		}
+		let mut deposit_return = DepositReturn::<T>::new(lease, tenant.clone(), claims);
+		deposit_return.settle_at =
+			Self::schedule_deposit_settlement(lease.id, tenant.clone(), deposit_return.settle_at);
+		DepositReturns::<T>::insert(lease.id, tenant, deposit_return);
+	}
+
+	///Schedule a deposit settlement at the first block from `when` with room left, and return it
+	pub fn schedule_deposit_settlement(
+		lease_id: u32,
+		tenant: T::AccountId,
+		when: BlockNumberOf<T>,
+	) -> BlockNumberOf<T> {
+		let mut at = when;
+		while DepositSettlements::<T>::try_mutate(at, |val| {
+			val.try_push((lease_id, tenant.clone()))
+		})
+		.is_err()
+		{
+			at = at.saturating_add(One::one());
+		}
+		at
+	}
+
+	pub fn unschedule_deposit_settlement(
+		lease_id: u32,
+		tenant: &T::AccountId,
+		when: BlockNumberOf<T>,
+	) {
+		DepositSettlements::<T>::mutate(when, |val| {
+			val.retain(|(id, account)| *id != lease_id || account != tenant);
+		});
+	}
+
+	///Settle the return of a guaranty deposit. The claimed deductions are payed to the asset
+	///account, and the rest of the deposit is refunded to the tenant. A disputed deposit is
+	///closed once the payment resolver settled the guaranty payment.
+	pub fn settle_deposit_return(
+		lease_id: u32,
+		mut deposit_return: DepositReturn<T>,
+		now: BlockNumberOf<T>,
+	) {
+		let tenant = deposit_return.tenant.clone();
+		let asset_account = deposit_return.asset_account.clone();
+		let guaranty = Assets::Pallet::<T>::guaranty_id(&tenant, &asset_account)
+			.and_then(|payment_id| Some((payment_id, Payment::Pallet::<T>::payment(payment_id)?)));
+		match deposit_return.status {
+			DepositReturnStatus::Open => {
+				let mut deducted: Payment::BalanceOf<T> = Zero::zero();
+				let mut refunded: Payment::BalanceOf<T> = Zero::zero();
+				if let Some((payment_id, payment)) = guaranty {
+					let claimed = deposit_return.claimed().min(payment.amount);
+					let settled = with_transaction(|| {
+						let result = Self::release_deductions(payment_id, claimed);
+						match result {
+							Ok(_) => TransactionOutcome::Commit(result),
+							Err(_) => TransactionOutcome::Rollback(result),
+						}
+					});
+					match settled {
+						Ok(released) => {
+							deducted = released;
+							refunded = payment.amount.saturating_sub(released);
+						},
+						Err(err) => {
+							log::warn!(
+								target: "runtime::tenancy",
+								"Unable to return the guaranty deposit of {:?}: {:?}",
+								tenant,
+								err
+							);
+							//The deposit return is retried after a new claim period
+							let next = now.saturating_add(T::DepositClaimPeriod::get());
+							deposit_return.settle_at =
+								Self::schedule_deposit_settlement(lease_id, tenant.clone(), next);
+							DepositReturns::<T>::insert(lease_id, &tenant, deposit_return);
+							return
+						},
+					}
+				}
+				Assets::Pallet::<T>::remove_guaranty(&tenant, &asset_account);
+				deposit_return.status = DepositReturnStatus::Settled;
+				DepositReturns::<T>::insert(lease_id, &tenant, deposit_return);
+				let deducted0 = Self::payment_bal_to_u128(deducted).unwrap_or_default();
+				if deducted0 > 0 {
+					Self::record_deduction(&tenant, deducted0);
+				}
+
+				Self::deposit_event(Event::DepositReturned {
+					lease_id,
+					tenant,
+					deducted,
+					refunded,
+				});
+			},
+			DepositReturnStatus::Disputed => {
+				if guaranty.is_some() {
+					//Check the dispute again after a new claim period
+					let next = now.saturating_add(T::DepositClaimPeriod::get());
+					deposit_return.settle_at =
+						Self::schedule_deposit_settlement(lease_id, tenant.clone(), next);
+					DepositReturns::<T>::insert(lease_id, &tenant, deposit_return);
+					return
+				}
+				Assets::Pallet::<T>::remove_guaranty(&tenant, &asset_account);
+				deposit_return.status = DepositReturnStatus::Settled;
+				DepositReturns::<T>::insert(lease_id, &tenant, deposit_return);
+
+				Self::deposit_event(Event::DepositDisputeResolved { lease_id, tenant, when: now });
+			},
+			DepositReturnStatus::Settled => (),
+		}
+	}
+
+	///Release the claimed deductions of a guaranty deposit to the asset account, and refund the
+	///rest of the deposit to the tenant. Returns the released amount.
+	pub fn release_deductions(
+		payment_id: Payment::PaymentId,
+		claimed: Payment::BalanceOf<T>,
+	) -> Result<Payment::BalanceOf<T>, DispatchError> {
+		let released = Payment::Pallet::<T>::release_partial_amount(payment_id, claimed)?;
+		<Payment::Pallet<T> as Payment::PaymentHandler<T>>::settle_payment(
+			payment_id,
+			Percent::from_percent(0),
+		)?;
+		Ok(released)
+	}
+
+	pub fn update_ledger(tenant: &T::AccountId, f: impl FnOnce(&mut TenantLedger)) {
+		TenantLedgers::<T>::mutate(tenant, f);
+	}
+
+	pub fn record_deduction(tenant: &T::AccountId, amount: u128) {
+		Self::update_ledger(tenant, |ledger| {
+			ledger.deductions = ledger.deductions.saturating_add(1);
+			ledger.deducted = ledger.deducted.saturating_add(amount);
+		});
+	}
+
+	///Reputation score of a tenant, derived from his payment history
+	pub fn reputation(tenant: &T::AccountId) -> Percent {
+		Self::ledger(tenant).score()
+	}
+
 	pub fn balance_to_u128_option(input: BalanceOf<T>) -> Option<u128> {
 		input.try_into().ok()
 	}
@@ -97,4 +1114,16 @@ impl<T: Config> Pallet<T> {
 	pub fn roles_bal_to_u128(input: Roles::BalanceOf<T>) -> Option<u128> {
 		input.try_into().ok()
 	}
+	pub fn u128_to_share_balance_option(input: u128) -> Option<Share::BalanceOf<T>> {
+		input.try_into().ok()
+	}
+	pub fn u128_to_roles_balance_option(input: u128) -> Option<Roles::BalanceOf<T>> {
+		input.try_into().ok()
+	}
+	pub fn payment_bal_to_u128(input: Payment::BalanceOf<T>) -> Option<u128> {
+		input.try_into().ok()
+	}
+	pub fn u128_to_payment_balance_option(input: u128) -> Option<Payment::BalanceOf<T>> {
+		input.try_into().ok()
+	}
 }
@@ -8,6 +8,7 @@
 //! - Request a lease for a purchased asset
 //! - Pay a guaranty_deposit to confirm the lease start
 //! - Pay his rent
+//! - Renew or terminate his lease
 //!
 //!### Dispatchable Functions
 //!
@@ -17,8 +18,86 @@
 //! * `pay_guaranty_deposit` - A newly selected tenant pays for a guaranty deposit
 //!  requested by the asset's owners, and confirms the start of his contract/lease.
 //!  
-//! * `pay_rent` - The Tenant can pay the monthly rent anytime.
-//!  He cannot pay more than 12 months, which is the length of the lease/contract.
+//! * `pay_rent` - The Tenant can pay the next installment of his rent anytime.
+//!  He cannot pay more than the rent of his lease/contract.
+//!
+//! * `pay_rent_amount` - The Tenant pays any amount of his rent, applied to the oldest unpaid
+//!  installments first.
+//!
+//! * `set_rent_schedule` - The Tenant chooses monthly, weekly or custom rent installments.
+//!
+//! * `enable_rent_to_own` - Once the owners approved the rent-to-own mode of the asset, the Tenant
+//!  converts a share of each rent payment into ownership tokens of the asset. The tokens are
+//!  bought at the net asset value per token, from the consenting owners or from the housing fund.
+//!
+//! * `buy_out_shares` - A rent-to-own Tenant buys all the ownership tokens for sale. When the
+//!  tenant leaves the asset, his unused credit is refunded.
+//!
+//! * `screen_applicant` - The Representative of an asset screens an applicant of the asset,
+//!  with the hash of his notes, and ranks him in the shortlist or turns him down.
+//!
+//! * `select_applicant` - The Representative launches the tenant session of a shortlisted
+//!  applicant. When the asset is let, the other applicants are released.
+//!
+//! * `open_ticket` - A tenant opens a maintenance ticket against his asset, with its category,
+//!  urgency and the hash of its description.
+//!
+//! * `assign_ticket` - The Representative of the asset assigns the ticket to a servicer.
+//!
+//! * `submit_quote` - The servicer quotes the work of the ticket.
+//!
+//! * `approve_quote` - The Representative approves the quote. The quoted amount is taken from
+//!  the maintenance reserve of the asset, within its spend limit, and put in a `pallet_payment`
+//!  escrow. A cancelled or refunded escrow goes back to the maintenance reserve.
+//!
+//! * `submit_completion` - The servicer submits the hash of the completion proof of the work.
+//!
+//! * `sign_off_ticket` - The tenant accepts the work, and the escrow is released to the
+//!  servicer, or rejects it and the servicer has to complete it.
+//!
+//! * `resolve_ticket` - Once the completion deadline of a ticket is over, the Representative
+//!  pays a share of the escrow to the servicer, and the rest goes back to the maintenance
+//!  reserve.
+//!
+//! * `share_reputation` - A tenant shares his payment history and reputation score with his
+//!  application for an asset.
+//!
+//! * `offer_renewal` - The Representative of an asset offers new lease terms to the tenants.
+//!  An offer accepted by all the co-tenants starts a new lease term when the current one ends.
+//!
+//! * `accept_renewal` - A tenant accepts the renewal offer of his lease.
+//!
+//! * `give_notice` - A tenant or the Representative of the asset terminates a lease early.
+//!  The lease ends after its notice period.
+//!
+//! * `claim_deduction` - When a tenant leaves a lease, the Representative claims deductions on
+//!  his guaranty deposit, with the hash of their evidence.
+//!
+//! * `accept_deductions` - A tenant accepts the deductions, and gets the rest of the deposit
+//!  back. Undisputed deductions are accepted at the end of the claim period.
+//!
+//! * `dispute_deductions` - A tenant disputes the deductions, and gets the undisputed part of the
+//!  deposit back. The claimed amount is settled by the resolver of `pallet_payment`.
+//!
+//! Each guaranty deposit payment creates a `Lease` record. When a lease ends without an accepted
+//! renewal, its tenants are unlinked from the asset.
+//! Co-tenants of a shared asset, whose share of the rent is set by the Representative, join the
+//! running lease of the asset. Each co-tenant pays his own rent and guaranty share, and his
+//! arrears are tracked separately.
+//!
+//! The arrears of each tenant are recorded at every rent check. After a grace period, a late
+//! fee is charged and compounded at each check, and the arrears are payed with the guaranty
+//! deposit of the tenant. A tenant staying in arrears for `EvictionThreshold` checks starts a
+//! `Demotion` vote session, and an evicted co-tenant leaves the lease.
+//! The running leases are queued for their arrears check in a bounded queue, executed in
+//! `on_idle` within the remaining weight of the blocks.
+//!
+//! When a tenant leaves a lease, his unpaid arrears are claimed on his guaranty deposit, and the
+//! rest of the deposit is refunded to him once the deductions are settled.
+//!
+//! Each tenant has a ledger of his on-time, late and missed payments, completed leases,
+//! evictions and deposit deductions. The reputation score derived from the ledger can be read
+//! by the Representatives, and shared by the tenant with his applications.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -73,6 +152,38 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Number of blocks between a lease termination notice and the end of the lease
+		#[pallet::constant]
+		type LeaseNoticePeriod: Get<Self::BlockNumber>;
+		/// Number of blocks in arrears before late fees are charged
+		#[pallet::constant]
+		type ArrearsGracePeriod: Get<Self::BlockNumber>;
+		/// Late fee charged on the arrears at each rent check, and compounded
+		#[pallet::constant]
+		type LateFee: Get<Percent>;
+		/// Number of late periods before an eviction vote session is started
+		#[pallet::constant]
+		type EvictionThreshold: Get<u32>;
+		/// Number of blocks after the end of a lease for the representative to claim
+		/// deductions on the guaranty deposit, and then for the tenants to dispute them
+		#[pallet::constant]
+		type DepositClaimPeriod: Get<Self::BlockNumber>;
+		/// Number of blocks given to a servicer to complete the work of an approved ticket,
+		/// before the representative can resolve it
+		#[pallet::constant]
+		type TicketCompletionPeriod: Get<Self::BlockNumber>;
+		/// Maximum number of running leases waiting for their arrears check
+		#[pallet::constant]
+		type MaxQueuedLeases: Get<u32>;
+		/// Maximum length of a renewed lease, in number of months
+		#[pallet::constant]
+		type MaxLeaseMonths: Get<u32>;
+		/// Maximum number of applications queued for an asset
+		#[pallet::constant]
+		type MaxApplications: Get<u32>;
+		/// Maximum number of lease ends, or of deposit settlements, scheduled at the same block
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
 	}
 
 	#[pallet::storage]
@@ -81,6 +192,138 @@ pub mod pallet {
 	pub type Tenants<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, RegisteredTenant<T>, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn leases)]
+	/// Stores the lease contracts
+	pub type Leases<T: Config> = StorageMap<_, Blake2_128Concat, u32, Lease<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lease_id)]
+	/// Id of the next lease contract
+	pub type LeaseId<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn active_lease)]
+	/// Running lease of each tenant
+	pub type ActiveLeases<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lease_expiries)]
+	/// Leases ending at each block
+	pub type LeaseExpiries<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberOf<T>,
+		BoundedVec<u32, T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn renewal_offers)]
+	/// Renewal offers of the leases
+	pub type RenewalOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, RenewalOffer<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arrears)]
+	/// Arrears of the tenants of each lease
+	pub type Arrears<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		ArrearsRecord<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_returns)]
+	/// Guaranty deposit returns of the tenants leaving a lease
+	pub type DepositReturns<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		DepositReturn<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_settlements)]
+	/// Deposit returns settled at each block
+	pub type DepositSettlements<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberOf<T>,
+		BoundedVec<(u32, T::AccountId), T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_period)]
+	/// Frequency of the rent installments chosen by the tenants
+	pub type RentPeriods<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RentPeriod<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rent_to_own)]
+	/// Rent-to-own contracts of the tenants
+	pub type RentToOwnContracts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RentToOwn<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn applications)]
+	/// Queue of the tenants applications for an asset, in order of application
+	pub type Applications<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Application<T>, T::MaxApplications>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn shortlist)]
+	/// Applicants shortlisted by the Representative of an asset, ranked by preference
+	pub type Shortlists<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxApplications>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tickets)]
+	/// Maintenance tickets opened by the tenants
+	pub type Tickets<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, MaintenanceTicket<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ticket_id)]
+	/// Id of the next maintenance ticket
+	pub type TicketId<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ledger)]
+	/// Payment history of the tenants
+	pub type TenantLedgers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, TenantLedger, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arrears_queue)]
+	/// Running leases waiting for their arrears check in `on_idle`
+	pub type ArrearsQueue<T: Config> =
+		StorageValue<_, BoundedVec<u32, T::MaxQueuedLeases>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arrears_scan)]
+	/// Last key of `ActiveLeases` queued by the ongoing arrears scan, empty when it starts
+	pub type ArrearsScan<T> = StorageValue<_, Vec<u8>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -101,6 +344,132 @@ pub mod pallet {
 			amount: Roles::BalanceOf<T>,
 			remaining: Roles::BalanceOf<T>,
 		},
+		///A lease started
+		LeaseStarted {
+			lease_id: u32,
+			tenant: T::AccountId,
+			asset_account: T::AccountId,
+			end: BlockNumberOf<T>,
+		},
+		///New lease terms were offered to the tenants
+		RenewalOffered {
+			lease_id: u32,
+			representative: T::AccountId,
+			rent: Roles::BalanceOf<T>,
+			months: u32,
+		},
+		///A tenant accepted the renewal offer. The lease is renewed once all its tenants accepted
+		RenewalAccepted { lease_id: u32, tenant: T::AccountId },
+		///A new lease term started
+		LeaseRenewed { lease_id: u32, start: BlockNumberOf<T>, end: BlockNumberOf<T> },
+		///A lease termination notice was given
+		LeaseNoticeGiven { lease_id: u32, by: T::AccountId, end: BlockNumberOf<T> },
+		///A lease ended, and its tenants were unlinked from the asset
+		LeaseEnded { lease_id: u32, asset_account: T::AccountId, when: BlockNumberOf<T> },
+		///A tenant of a lease fell into arrears
+		LeaseInArrears {
+			lease_id: u32,
+			tenant: T::AccountId,
+			amount: Roles::BalanceOf<T>,
+			when: BlockNumberOf<T>,
+		},
+		///A late fee was charged on the arrears of a tenant
+		LateFeeCharged {
+			lease_id: u32,
+			tenant: T::AccountId,
+			fee: Roles::BalanceOf<T>,
+			total_fees: Roles::BalanceOf<T>,
+			late_periods: u32,
+		},
+		///Arrears of a tenant were deducted from his guaranty deposit
+		GuarantyDeducted {
+			lease_id: u32,
+			tenant: T::AccountId,
+			amount: Payment::BalanceOf<T>,
+			remaining_deposit: Payment::BalanceOf<T>,
+		},
+		///The arrears of a tenant were cleared
+		ArrearsCleared { lease_id: u32, tenant: T::AccountId, when: BlockNumberOf<T> },
+		///An eviction vote session was started for a tenant in arrears
+		EvictionStarted {
+			lease_id: u32,
+			tenant: T::AccountId,
+			referendum_index: Assets::Dem::ReferendumIndex,
+		},
+		///A deduction was claimed on the guaranty deposit of a leaving tenant
+		DeductionClaimed {
+			lease_id: u32,
+			tenant: T::AccountId,
+			reason: DeductionReason,
+			amount: Payment::BalanceOf<T>,
+			evidence: Option<T::Hash>,
+		},
+		///The tenant disputed the deductions, which are sent to the payment resolver. The rest
+		///of the deposit was refunded.
+		DeductionsDisputed { lease_id: u32, tenant: T::AccountId, refunded: Payment::BalanceOf<T> },
+		///The deductions were payed to the asset, and the rest of the deposit refunded
+		DepositReturned {
+			lease_id: u32,
+			tenant: T::AccountId,
+			deducted: Payment::BalanceOf<T>,
+			refunded: Payment::BalanceOf<T>,
+		},
+		///The payment resolver settled the disputed deposit
+		DepositDisputeResolved { lease_id: u32, tenant: T::AccountId, when: BlockNumberOf<T> },
+		///A co-tenant joined a running lease
+		CoTenantJoined { lease_id: u32, tenant: T::AccountId, share: Percent },
+		///A co-tenant left a lease, which goes on with the other tenants
+		CoTenantLeft { lease_id: u32, tenant: T::AccountId, when: BlockNumberOf<T> },
+		///A tenant changed the frequency of his rent installments
+		RentScheduleSet { tenant: T::AccountId, period: RentPeriod<T>, installments: u32 },
+		///A tenant started to convert a share of his rent into ownership tokens
+		RentToOwnEnabled { tenant: T::AccountId, asset_account: T::AccountId, share: Percent },
+		///A rent-to-own tenant bought ownership tokens of his asset
+		OwnershipTokensBought {
+			tenant: T::AccountId,
+			seller: T::AccountId,
+			tokens: u128,
+			cost: BalanceOf<T>,
+		},
+		///The rent-to-own contract of a leaving tenant was closed, the unused credit being
+		///refunded
+		RentToOwnClosed { tenant: T::AccountId, tokens: u128, credit: BalanceOf<T> },
+		///The Representative screened an applicant for an asset
+		ApplicantScreened {
+			tenant: T::AccountId,
+			asset_account: T::AccountId,
+			status: ApplicationStatus,
+			rank: Option<u32>,
+		},
+		///The Representative launched a tenant session for an applicant
+		ApplicantSelected { tenant: T::AccountId, asset_account: T::AccountId },
+		///The asset was let to another tenant, and the applicant is free to request another asset
+		ApplicantReleased { tenant: T::AccountId, asset_account: T::AccountId },
+		///A tenant opened a maintenance ticket
+		TicketOpened {
+			ticket_id: u32,
+			tenant: T::AccountId,
+			asset_account: T::AccountId,
+			category: MaintenanceCategory,
+			urgency: TicketUrgency,
+		},
+		///The Representative assigned a servicer to a maintenance ticket
+		TicketAssigned { ticket_id: u32, servicer: T::AccountId },
+		///A servicer quoted the work of a maintenance ticket
+		QuoteSubmitted { ticket_id: u32, servicer: T::AccountId, amount: Assets::BalanceOf<T> },
+		///The Representative approved a quote, and the payment is in escrow
+		QuoteApproved { ticket_id: u32, servicer: T::AccountId, amount: Assets::BalanceOf<T> },
+		///A servicer submitted the completion proof of a maintenance ticket
+		CompletionSubmitted { ticket_id: u32, servicer: T::AccountId, proof: T::Hash },
+		///The tenant signed off the work, and the escrow was released to the servicer
+		TicketClosed { ticket_id: u32, servicer: T::AccountId, amount: Assets::BalanceOf<T> },
+		///The tenant rejected the completion of the work
+		CompletionRejected { ticket_id: u32, tenant: T::AccountId },
+		///A tenant shared his payment history with his application for an asset
+		ReputationShared { tenant: T::AccountId, asset_account: T::AccountId, score: Percent },
+		///The Representative resolved an overdue ticket, the rest of the escrow went back to the
+		///maintenance reserve
+		TicketResolved { ticket_id: u32, servicer: T::AccountId, amount: Assets::BalanceOf<T> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -124,35 +493,152 @@ pub mod pallet {
 		NoRentToPay,
 		/// The tenant is not linked to the asset
 		TenantAssetNotLinked,
+		/// The rent amount cannot be computed
+		InvalidRentAmount,
+		/// The lease does not exist
+		NotALease,
+		/// The caller is not a tenant of the lease
+		NotALeaseTenant,
+		/// The caller is not the representative of the leased asset
+		NotTheAssetRepresentative,
+		/// The lease is not running anymore
+		LeaseNotActive,
+		/// The lease length is not valid
+		InvalidLeaseLength,
+		/// There is no renewal offer for the lease
+		NoRenewalOffer,
+		/// There is no deposit return for the lease
+		NoDepositReturn,
+		/// The deposit return is not open anymore
+		DepositReturnClosed,
+		/// The period to claim deductions is over
+		ClaimPeriodOver,
+		/// The deductions exceed the guaranty deposit
+		ExcessiveDeduction,
+		/// No deduction was claimed on the deposit
+		NoDeductionClaimed,
+		/// The rent period is empty, or longer than the lease
+		InvalidRentPeriod,
+		/// The payment exceeds the rent left to pay
+		ExcessiveRentPayment,
+		/// The owners did not approve the rent-to-own mode for the asset
+		RentToOwnNotApproved,
+		/// The tenant already has a rent-to-own contract
+		RentToOwnAlreadyEnabled,
+		/// The tenant has no rent-to-own contract
+		NotARentToOwnTenant,
+		/// No ownership tokens are for sale
+		NoTokensForSale,
+		///The tenant did not apply for this asset
+		NotAnApplicant,
+		///The applicant is not shortlisted
+		ApplicantNotShortlisted,
+		///The application was already processed
+		ApplicationClosed,
+		///This maintenance ticket does not exist
+		NotATicket,
+		///The account does not have the servicer role
+		NotAServicer,
+		///The caller is not the servicer assigned to the ticket
+		NotTheTicketServicer,
+		///The caller is not the tenant who opened the ticket
+		NotTheTicketTenant,
+		///The ticket status does not allow this action
+		InvalidTicketStatus,
+		///The quote of a ticket cannot be zero
+		InvalidQuote,
+		///The servicer still has time to complete the work of the ticket
+		TicketNotOverdue,
+		///The tenant already accepted the renewal offer
+		RenewalAlreadyAccepted,
+		///The application queue of the asset is full
+		TooManyApplications,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Weight: see `begin_block`
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::begin_block(n)
+		}
+
+		/// Weight: see `finish_block`
+		fn on_idle(n: T::BlockNumber, max_weight: Weight) -> Weight {
+			Self::finish_block(n, max_weight)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// The function below allows an active tenant to pay for his rent.
 		/// The origin must be the tenant accountId.
-		/// The amount payed is the monthly_rent, and can be payed at any moment.
+		/// The amount payed is the oldest unpaid installment of the rent schedule, and can be
+		/// payed at any moment.
 		/// The sum of all payments cannot exceed the yearly_rent  .
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2).ref_time())]
 		pub fn pay_rent(origin: OriginFor<T>) -> DispatchResult {
-			let tenant_account = ensure_signed(origin.clone())?;
-			let tenant = Roles::Pallet::<T>::tenants(tenant_account.clone()).unwrap();
+			let tenant_account = ensure_signed(origin)?;
+			let tenant = Roles::Pallet::<T>::tenants(tenant_account.clone())
+				.ok_or(Error::<T>::NotATenant)?;
 
 			//Check that the Tenant is connected to the asset
 			ensure!(tenant.asset_account.is_some(), Error::<T>::TenantAssetNotLinked);
-			//Check that the remaining rent-to-pay is greater than 1
-			ensure!(tenant.remaining_payments > 0, Error::<T>::NoRentToPay);
+			//Check that some rent is left to pay
+			let schedule = RentSchedule::<T>::of(&tenant_account).ok_or(Error::<T>::NoRentToPay)?;
+			let amount = schedule.next_payment();
+			ensure!(amount > 0, Error::<T>::NoRentToPay);
 			//Pay the rent
-			Self::rent_helper(tenant_account.clone()).ok();
+			Self::rent_helper(tenant_account, amount)
+		}
 
-			let now = <frame_system::Pallet<T>>::block_number();
+		/// The function below allows an active tenant to pay any amount of his rent: a part of
+		/// an installment, or several installments in advance.
+		/// The payment is applied to the oldest unpaid installments first.
+		/// The origin must be the tenant accountId.
+		/// - amount: amount payed
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2).ref_time())]
+		pub fn pay_rent_amount(
+			origin: OriginFor<T>,
+			amount: Roles::BalanceOf<T>,
+		) -> DispatchResult {
+			let tenant_account = ensure_signed(origin)?;
+			let tenant = Roles::Pallet::<T>::tenants(tenant_account.clone())
+				.ok_or(Error::<T>::NotATenant)?;
 
-			Self::deposit_event(Event::RentPayment {
-				tenant: tenant_account,
-				when: now,
-				asset_account: tenant.asset_account.unwrap(),
-				amount: tenant.rent,
-				remaining: tenant.remaining_rent,
-			});
+			//Check that the Tenant is connected to the asset
+			ensure!(tenant.asset_account.is_some(), Error::<T>::TenantAssetNotLinked);
+			let amount0 = Self::roles_bal_to_u128(amount).ok_or(Error::<T>::InvalidRentAmount)?;
+			ensure!(amount0 > 0, Error::<T>::InvalidRentAmount);
+			//Pay the rent
+			Self::rent_helper(tenant_account, amount0)
+		}
+
+		/// The function below allows an active tenant to choose the frequency of his rent
+		/// installments. The rent left to pay is spread over the installments of the lease.
+		/// The origin must be the tenant accountId.
+		/// - period: monthly, weekly, or custom number of blocks between two installments
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn set_rent_schedule(origin: OriginFor<T>, period: RentPeriod<T>) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let tenant =
+				Roles::Pallet::<T>::tenants(caller.clone()).ok_or(Error::<T>::NotATenant)?;
+			ensure!(tenant.asset_account.is_some(), Error::<T>::TenantAssetNotLinked);
+
+			//A custom period must fit in the lease
+			if let RentPeriod::Custom(blocks) = period {
+				let months = Assets::Pallet::<T>::tenant_leases(&caller)
+					.unwrap_or_else(<T as Assets::Config>::Lease::get);
+				ensure!(
+					!blocks.is_zero() && blocks <= Self::lease_blocks(months),
+					Error::<T>::InvalidRentPeriod
+				);
+			}
+			RentPeriods::<T>::insert(&caller, period.clone());
+			let installments = RentSchedule::<T>::of(&caller)
+				.map(|schedule| schedule.installments)
+				.unwrap_or_default();
+
+			Self::deposit_event(Event::RentScheduleSet { tenant: caller, period, installments });
 
 			Ok(())
 		}
@@ -179,6 +665,7 @@ pub mod pallet {
 			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
 			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
 			let virtual_account = ownership.unwrap().virtual_account;
+			let previous = Self::infos(&caller).and_then(|infos| infos.asset_requested);
 
 			if !Tenants::<T>::contains_key(caller.clone()) {
 				RegisteredTenant::<T>::new(
@@ -196,6 +683,7 @@ pub mod pallet {
 			}
 
 			Self::request_helper(origin.clone(), virtual_account.clone(), info).ok();
+			Self::queue_application(&caller, previous, &virtual_account)?;
 			let now = <frame_system::Pallet<T>>::block_number();
 
 			Self::deposit_event(Event::AssetRequested {
@@ -224,23 +712,20 @@ pub mod pallet {
 
 			// Ensure that the asset is valid
 			let collection_id: T::NftCollectionId = asset_type.value().into();
-			let ownership = Share::Pallet::<T>::virtual_acc(collection_id, asset_id);
-			ensure!(ownership.is_some(), Error::<T>::NotAnAsset);
-			let virtual_account = ownership.unwrap().virtual_account;
+			let virtual_account = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
 
 			//Ensure that payment request exists
-			ensure!(
-				Assets::GuarantyPayment::<T>::contains_key(&caller, &virtual_account),
-				Error::<T>::NotAValidPayment
-			);
-			let payment_infos = Assets::Pallet::<T>::guaranty(&caller, &virtual_account).unwrap();
+			let payment_infos = Assets::Pallet::<T>::guaranty(&caller, &virtual_account)
+				.ok_or(Error::<T>::NotAValidPayment)?;
 			let status = payment_infos.state;
 			ensure!(
 				status == Payment::PaymentState::PaymentRequested,
 				Error::<T>::NotAValidPayment
 			);
 
-			Self::payment_helper(origin, virtual_account.clone(), collection_id, asset_id).ok();
+			Self::payment_helper(origin, virtual_account.clone(), collection_id, asset_id)?;
 			let now = <frame_system::Pallet<T>>::block_number();
 
 			Self::deposit_event(Event::GuarantyDepositPayment {
@@ -252,5 +737,689 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// The representative of an asset offers new lease terms to the tenants of a lease.
+		/// The origin must be the representative of the asset.
+		/// - lease_id: id of the lease
+		/// - rent: monthly rent of the renewed lease
+		/// - months: length of the renewed lease, in number of months
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1).ref_time())]
+		pub fn offer_renewal(
+			origin: OriginFor<T>,
+			lease_id: u32,
+			rent: Roles::BalanceOf<T>,
+			months: u32,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+			ensure!(lease.status == LeaseStatus::Active, Error::<T>::LeaseNotActive);
+			ensure!(
+				Self::is_asset_representative(&caller, lease.collection, lease.item),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			ensure!(
+				months > 0 && months <= T::MaxLeaseMonths::get().min(u8::MAX as u32),
+				Error::<T>::InvalidLeaseLength
+			);
+
+			RenewalOffers::<T>::insert(
+				lease_id,
+				RenewalOffer::<T> {
+					offered_by: caller.clone(),
+					rent,
+					months,
+					accepted_by: Vec::new(),
+				},
+			);
+
+			Self::deposit_event(Event::RenewalOffered {
+				lease_id,
+				representative: caller,
+				rent,
+				months,
+			});
+
+			Ok(())
+		}
+
+		/// A tenant accepts the renewal offer of his lease.
+		/// The new lease term starts when the current one ends, if all the co-tenants accepted.
+		/// The origin must be a tenant of the lease.
+		/// - lease_id: id of the lease
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn accept_renewal(origin: OriginFor<T>, lease_id: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+			ensure!(lease.tenants.contains(&caller), Error::<T>::NotALeaseTenant);
+			ensure!(lease.status == LeaseStatus::Active, Error::<T>::LeaseNotActive);
+
+			RenewalOffers::<T>::try_mutate(lease_id, |val| -> DispatchResult {
+				let offer = val.as_mut().ok_or(Error::<T>::NoRenewalOffer)?;
+				ensure!(!offer.accepted_by.contains(&caller), Error::<T>::RenewalAlreadyAccepted);
+				offer.accepted_by.push(caller.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RenewalAccepted { lease_id, tenant: caller });
+
+			Ok(())
+		}
+
+		/// A tenant of a lease, or the representative of the leased asset,
+		/// terminates the lease early. The lease ends after its notice period,
+		/// or at its normal end if it comes first.
+		/// The origin must be a tenant of the lease or the representative of the asset.
+		/// - lease_id: id of the lease
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,4).ref_time())]
+		pub fn give_notice(origin: OriginFor<T>, lease_id: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+			ensure!(lease.status == LeaseStatus::Active, Error::<T>::LeaseNotActive);
+			ensure!(
+				lease.tenants.contains(&caller) ||
+					Self::is_asset_representative(&caller, lease.collection, lease.item),
+				Error::<T>::NotALeaseTenant
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let end = now.saturating_add(lease.notice_period).min(lease.end);
+			Self::unschedule_lease_end(lease_id, lease.end);
+			let end = Self::schedule_lease_end(lease_id, end);
+			Leases::<T>::mutate(lease_id, |val| {
+				let mut val0 = val.clone().unwrap();
+				val0.end = end;
+				val0.status = LeaseStatus::Terminating;
+				*val = Some(val0);
+			});
+			RenewalOffers::<T>::remove(lease_id);
+
+			Self::deposit_event(Event::LeaseNoticeGiven { lease_id, by: caller, end });
+
+			Ok(())
+		}
+
+		/// The representative of an asset claims a deduction on the guaranty deposit
+		/// of a tenant leaving a lease.
+		/// The origin must be the representative of the asset.
+		/// - lease_id: id of the lease
+		/// - tenant: tenant who left the lease
+		/// - reason: reason of the deduction
+		/// - amount: amount deducted from the deposit
+		/// - evidence: hash of the evidence of the claim
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1).ref_time())]
+		pub fn claim_deduction(
+			origin: OriginFor<T>,
+			lease_id: u32,
+			tenant: T::AccountId,
+			reason: DeductionReason,
+			amount: Payment::BalanceOf<T>,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+			ensure!(
+				Self::is_asset_representative(&caller, lease.collection, lease.item),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			let mut deposit_return =
+				Self::deposit_returns(lease_id, &tenant).ok_or(Error::<T>::NoDepositReturn)?;
+			ensure!(
+				deposit_return.status == DepositReturnStatus::Open,
+				Error::<T>::DepositReturnClosed
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= deposit_return.claims_end, Error::<T>::ClaimPeriodOver);
+			ensure!(
+				deposit_return.claimed().saturating_add(amount) <= deposit_return.deposit,
+				Error::<T>::ExcessiveDeduction
+			);
+
+			deposit_return.claims.push(DeductionClaim::<T> {
+				reason,
+				amount,
+				evidence: Some(evidence),
+			});
+			DepositReturns::<T>::insert(lease_id, &tenant, deposit_return);
+
+			Self::deposit_event(Event::DeductionClaimed {
+				lease_id,
+				tenant,
+				reason,
+				amount,
+				evidence: Some(evidence),
+			});
+
+			Ok(())
+		}
+
+		/// A tenant who left a lease accepts the deductions claimed on his guaranty deposit.
+		/// The deductions are payed to the asset account, and the rest of the deposit is
+		/// refunded to the tenant.
+		/// The origin must be the tenant.
+		/// - lease_id: id of the lease
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4).ref_time())]
+		pub fn accept_deductions(origin: OriginFor<T>, lease_id: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let deposit_return =
+				Self::deposit_returns(lease_id, &caller).ok_or(Error::<T>::NoDepositReturn)?;
+			ensure!(
+				deposit_return.status == DepositReturnStatus::Open,
+				Error::<T>::DepositReturnClosed
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::unschedule_deposit_settlement(lease_id, &caller, deposit_return.settle_at);
+			Self::settle_deposit_return(lease_id, deposit_return, now);
+
+			Ok(())
+		}
+
+		/// A tenant who left a lease disputes the deductions claimed on his guaranty deposit.
+		/// The undisputed part of the deposit is refunded, and the claimed amount is sent to the
+		/// payment resolver, who decides the share payed to the asset account.
+		/// The origin must be the tenant.
+		/// - lease_id: id of the lease
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3).ref_time())]
+		pub fn dispute_deductions(origin: OriginFor<T>, lease_id: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut deposit_return =
+				Self::deposit_returns(lease_id, &caller).ok_or(Error::<T>::NoDepositReturn)?;
+			ensure!(
+				deposit_return.status == DepositReturnStatus::Open,
+				Error::<T>::DepositReturnClosed
+			);
+			ensure!(!deposit_return.claims.is_empty(), Error::<T>::NoDeductionClaimed);
+
+			let payment_id =
+				Assets::Pallet::<T>::guaranty_id(&caller, &deposit_return.asset_account)
+					.ok_or(Error::<T>::NotAValidPayment)?;
+			let payment =
+				Payment::Pallet::<T>::payment(payment_id).ok_or(Error::<T>::NotAValidPayment)?;
+
+			//The undisputed part of the deposit is returned, and only the claimed amount is
+			//reviewed
+			let claimed = deposit_return.claimed().min(payment.amount);
+			let refunded = Payment::Pallet::<T>::refund_partial_amount(
+				payment_id,
+				payment.amount.saturating_sub(claimed),
+			)?;
+			Payment::Pallet::<T>::request_review(payment_id)?;
+			deposit_return.status = DepositReturnStatus::Disputed;
+			DepositReturns::<T>::insert(lease_id, &caller, deposit_return);
+
+			Self::deposit_event(Event::DeductionsDisputed { lease_id, tenant: caller, refunded });
+
+			Ok(())
+		}
+
+		/// The function below allows a tenant to convert a share of his rent into ownership
+		/// tokens of his asset, once the owners approved the rent-to-own mode of the asset.
+		/// The tokens are bought from the consenting owners, or from the housing fund, at the
+		/// net asset value per token.
+		/// The origin must be the tenant accountId.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,1).ref_time())]
+		pub fn enable_rent_to_own(origin: OriginFor<T>) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease_id = Self::active_lease(&caller).ok_or(Error::<T>::NotALeaseTenant)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+			let share = Assets::Pallet::<T>::rent_to_own_share(lease.collection, lease.item)
+				.ok_or(Error::<T>::RentToOwnNotApproved)?;
+			ensure!(
+				!RentToOwnContracts::<T>::contains_key(&caller),
+				Error::<T>::RentToOwnAlreadyEnabled
+			);
+
+			let contract = RentToOwn::<T> {
+				asset_account: lease.asset_account.clone(),
+				collection: lease.collection,
+				item: lease.item,
+				credit: Zero::zero(),
+				tokens: 0,
+				payed: Zero::zero(),
+				start: <frame_system::Pallet<T>>::block_number(),
+			};
+			RentToOwnContracts::<T>::insert(&caller, contract);
+
+			Self::deposit_event(Event::RentToOwnEnabled {
+				tenant: caller,
+				asset_account: lease.asset_account,
+				share,
+			});
+
+			Ok(())
+		}
+
+		/// The function below allows a rent-to-own tenant to buy all the ownership tokens for
+		/// sale, at the net asset value per token. The credit put aside from the rent is used
+		/// first.
+		/// The origin must be the tenant accountId.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8,8).ref_time())]
+		pub fn buy_out_shares(origin: OriginFor<T>) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let contract = Self::rent_to_own(&caller).ok_or(Error::<T>::NotARentToOwnTenant)?;
+			let nav = Assets::Pallet::<T>::nav_per_token(contract.collection, contract.item)?;
+			let available: u128 = Self::tokens_for_sale(&caller, &contract)
+				.iter()
+				.map(|(_, tokens)| *tokens)
+				.sum();
+			ensure!(available > 0 && nav > 0, Error::<T>::NoTokensForSale);
+
+			//Put aside the missing amount, and buy the tokens
+			let credit = Self::balance_to_u128_option(contract.credit).unwrap_or_default();
+			let needed =
+				Self::u128_to_balance_option(available.saturating_mul(nav).saturating_sub(credit))
+					.ok_or(Error::<T>::InvalidRentAmount)?;
+			<T as Config>::Currency::reserve(&caller, needed)?;
+			RentToOwnContracts::<T>::mutate(&caller, |val| {
+				if let Some(val0) = val {
+					val0.credit = val0.credit.saturating_add(needed);
+				}
+			});
+			Self::buy_ownership_tokens(&caller)?;
+
+			//The credit put aside from the rent is spent first, the unspent part of the amount
+			//reserved for a partial fill is released
+			let mut contract = Self::rent_to_own(&caller).ok_or(Error::<T>::NotARentToOwnTenant)?;
+			let unspent = contract.credit.min(needed);
+			let missing = <T as Config>::Currency::unreserve(&caller, unspent);
+			contract.credit = contract.credit.saturating_sub(unspent.saturating_sub(missing));
+			RentToOwnContracts::<T>::insert(&caller, contract);
+
+			Ok(())
+		}
+
+		/// The representative of an asset screens an applicant of the asset.
+		/// A ranked applicant is shortlisted, and an applicant without rank is turned down.
+		/// The origin must be the representative of the asset.
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - tenant: applicant of the asset
+		/// - notes: hash of the screening notes
+		/// - rank: rank of the applicant in the shortlist, starting at 0
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2).ref_time())]
+		pub fn screen_applicant(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			tenant: T::AccountId,
+			notes: T::Hash,
+			rank: Option<u32>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(
+				Self::is_asset_representative(&caller, collection_id, asset_id),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			let application =
+				Self::application(&tenant, &asset_account).ok_or(Error::<T>::NotAnApplicant)?;
+			ensure!(
+				matches!(
+					application.status,
+					ApplicationStatus::Pending |
+						ApplicationStatus::Shortlisted |
+						ApplicationStatus::Rejected
+				),
+				Error::<T>::ApplicationClosed
+			);
+
+			//Move the applicant to his rank in the shortlist
+			let status = match rank {
+				Some(_) => ApplicationStatus::Shortlisted,
+				None => ApplicationStatus::Rejected,
+			};
+			Shortlists::<T>::try_mutate(&asset_account, |val| -> DispatchResult {
+				val.retain(|applicant| *applicant != tenant);
+				if let Some(rank) = rank {
+					let rank = (rank as usize).min(val.len());
+					val.try_insert(rank, tenant.clone())
+						.map_err(|_| Error::<T>::TooManyApplications)?;
+				}
+				Ok(())
+			})?;
+			Self::update_application(&tenant, &asset_account, |val| {
+				val.status = status;
+				val.notes = Some(notes);
+			});
+
+			Self::deposit_event(Event::ApplicantScreened { tenant, asset_account, status, rank });
+
+			Ok(())
+		}
+
+		/// The representative of an asset selects a shortlisted applicant, and starts the
+		/// owners referendum of the tenant with `launch_tenant_session`.
+		/// The origin must be the representative of the asset.
+		/// - asset_type: type of the asset
+		/// - asset_id: id of the asset
+		/// - tenant: shortlisted applicant
+		/// - judgement: judgement of the identity of the applicant
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3).ref_time())]
+		pub fn select_applicant(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+			tenant: T::AccountId,
+			judgement: Ident::Judgement<Assets::IdentBalanceOf<T>>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin.clone())?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			ensure!(
+				Self::is_asset_representative(&caller, collection_id, asset_id),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			let application =
+				Self::application(&tenant, &asset_account).ok_or(Error::<T>::NotAnApplicant)?;
+			ensure!(
+				application.status == ApplicationStatus::Shortlisted,
+				Error::<T>::ApplicantNotShortlisted
+			);
+
+			Assets::Pallet::<T>::launch_tenant_session(
+				origin,
+				asset_type,
+				asset_id,
+				tenant.clone(),
+				Assets::VoteProposals::Election,
+				judgement,
+			)?;
+
+			Shortlists::<T>::mutate(&asset_account, |val| {
+				val.retain(|applicant| *applicant != tenant);
+			});
+			Self::update_application(&tenant, &asset_account, |val| {
+				val.status = ApplicationStatus::Selected;
+			});
+
+			Self::deposit_event(Event::ApplicantSelected { tenant, asset_account });
+
+			Ok(())
+		}
+
+		/// A tenant opens a maintenance ticket against the asset of his lease.
+		/// The origin must be the tenant.
+		/// - category: category of the maintenance work
+		/// - urgency: urgency of the maintenance work
+		/// - description: hash of the description of the problem
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2).ref_time())]
+		pub fn open_ticket(
+			origin: OriginFor<T>,
+			category: MaintenanceCategory,
+			urgency: TicketUrgency,
+			description: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let lease_id = Self::active_lease(&caller).ok_or(Error::<T>::NotALeaseTenant)?;
+			let lease = Self::leases(lease_id).ok_or(Error::<T>::NotALease)?;
+
+			let ticket_id =
+				MaintenanceTicket::<T>::new(caller.clone(), &lease, category, urgency, description);
+
+			Self::deposit_event(Event::TicketOpened {
+				ticket_id,
+				tenant: caller,
+				asset_account: lease.asset_account,
+				category,
+				urgency,
+			});
+
+			Ok(())
+		}
+
+		/// The representative of an asset assigns a servicer to a maintenance ticket.
+		/// A ticket can be assigned again until a quote is approved.
+		/// The origin must be the representative of the asset.
+		/// - ticket_id: id of the ticket
+		/// - servicer: an account with the servicer role
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1).ref_time())]
+		pub fn assign_ticket(
+			origin: OriginFor<T>,
+			ticket_id: u32,
+			servicer: T::AccountId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(
+				Self::is_asset_representative(&caller, ticket.collection, ticket.item),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			ensure!(
+				matches!(
+					ticket.status,
+					TicketStatus::Open | TicketStatus::Assigned | TicketStatus::Quoted
+				),
+				Error::<T>::InvalidTicketStatus
+			);
+			ensure!(Roles::Pallet::<T>::servicers(&servicer).is_some(), Error::<T>::NotAServicer);
+
+			ticket.servicer = Some(servicer.clone());
+			ticket.quote = None;
+			ticket.status = TicketStatus::Assigned;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::TicketAssigned { ticket_id, servicer });
+
+			Ok(())
+		}
+
+		/// The servicer assigned to a maintenance ticket quotes the work.
+		/// The origin must be the servicer of the ticket.
+		/// - ticket_id: id of the ticket
+		/// - amount: price of the work
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		pub fn submit_quote(
+			origin: OriginFor<T>,
+			ticket_id: u32,
+			amount: Assets::BalanceOf<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(ticket.servicer == Some(caller.clone()), Error::<T>::NotTheTicketServicer);
+			ensure!(
+				matches!(ticket.status, TicketStatus::Assigned | TicketStatus::Quoted),
+				Error::<T>::InvalidTicketStatus
+			);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidQuote);
+
+			ticket.quote = Some(amount);
+			ticket.status = TicketStatus::Quoted;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::QuoteSubmitted { ticket_id, servicer: caller, amount });
+
+			Ok(())
+		}
+
+		/// The representative of an asset approves the quote of a maintenance ticket.
+		/// The quoted amount is taken from the maintenance reserve of the asset, within the
+		/// maintenance spend limit of the period, and put in a `pallet_payment` escrow for the
+		/// servicer. The servicer has `TicketCompletionPeriod` blocks to complete the work.
+		/// The origin must be the representative of the asset.
+		/// - ticket_id: id of the ticket
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5).ref_time())]
+		pub fn approve_quote(origin: OriginFor<T>, ticket_id: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(
+				Self::is_asset_representative(&caller, ticket.collection, ticket.item),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			ensure!(ticket.status == TicketStatus::Quoted, Error::<T>::InvalidTicketStatus);
+			let servicer = ticket.servicer.clone().ok_or(Error::<T>::InvalidTicketStatus)?;
+			let amount = ticket.quote.ok_or(Error::<T>::InvalidTicketStatus)?;
+
+			let payment_id = Assets::Pallet::<T>::escrow_maintenance_reserve(
+				ticket.asset_account.clone(),
+				servicer.clone(),
+				amount,
+				ticket.description,
+			)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ticket.payment_id = Some(payment_id);
+			ticket.deadline = Some(now.saturating_add(T::TicketCompletionPeriod::get()));
+			ticket.status = TicketStatus::Approved;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::QuoteApproved { ticket_id, servicer, amount });
+
+			Ok(())
+		}
+
+		/// The servicer of a maintenance ticket submits the proof of completion of the work.
+		/// The origin must be the servicer of the ticket.
+		/// - ticket_id: id of the ticket
+		/// - proof: hash of the completion proof
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		pub fn submit_completion(
+			origin: OriginFor<T>,
+			ticket_id: u32,
+			proof: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(ticket.servicer == Some(caller.clone()), Error::<T>::NotTheTicketServicer);
+			ensure!(ticket.status == TicketStatus::Approved, Error::<T>::InvalidTicketStatus);
+
+			ticket.proof = Some(proof);
+			ticket.status = TicketStatus::Completed;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::CompletionSubmitted { ticket_id, servicer: caller, proof });
+
+			Ok(())
+		}
+
+		/// The tenant who opened a maintenance ticket signs off the completed work.
+		/// An accepted work releases the escrow to the servicer, and closes the ticket.
+		/// A rejected work goes back to the servicer.
+		/// The origin must be the tenant of the ticket.
+		/// - ticket_id: id of the ticket
+		/// - accept: acceptance of the work
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3).ref_time())]
+		pub fn sign_off_ticket(
+			origin: OriginFor<T>,
+			ticket_id: u32,
+			accept: bool,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(ticket.tenant == caller, Error::<T>::NotTheTicketTenant);
+			ensure!(ticket.status == TicketStatus::Completed, Error::<T>::InvalidTicketStatus);
+			let servicer = ticket.servicer.clone().ok_or(Error::<T>::InvalidTicketStatus)?;
+
+			if !accept {
+				ticket.proof = None;
+				ticket.status = TicketStatus::Approved;
+				Tickets::<T>::insert(ticket_id, ticket);
+				Self::deposit_event(Event::CompletionRejected { ticket_id, tenant: caller });
+				return Ok(())
+			}
+
+			let payment_id = ticket.payment_id.ok_or(Error::<T>::InvalidTicketStatus)?;
+			let origin2 = RawOrigin::Signed(ticket.asset_account.clone());
+			Payment::Pallet::<T>::release(origin2.into(), payment_id).map_err(|err| err.error)?;
+
+			let amount = ticket.quote.unwrap_or_default();
+			ticket.status = TicketStatus::Closed;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::TicketClosed { ticket_id, servicer, amount });
+
+			Ok(())
+		}
+
+		/// The representative of an asset resolves a maintenance ticket whose work was not
+		/// signed off by the tenant before its deadline. The servicer gets a share of the
+		/// escrow, and the rest goes back to the maintenance reserve of the asset.
+		/// The origin must be the representative of the asset.
+		/// - ticket_id: id of the ticket
+		/// - servicer_share: share of the escrow paid to the servicer
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5).ref_time())]
+		pub fn resolve_ticket(
+			origin: OriginFor<T>,
+			ticket_id: u32,
+			servicer_share: Percent,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let mut ticket = Self::tickets(ticket_id).ok_or(Error::<T>::NotATicket)?;
+			ensure!(
+				Self::is_asset_representative(&caller, ticket.collection, ticket.item),
+				Error::<T>::NotTheAssetRepresentative
+			);
+			ensure!(
+				matches!(ticket.status, TicketStatus::Approved | TicketStatus::Completed),
+				Error::<T>::InvalidTicketStatus
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			let deadline = ticket.deadline.ok_or(Error::<T>::InvalidTicketStatus)?;
+			ensure!(now >= deadline, Error::<T>::TicketNotOverdue);
+			let servicer = ticket.servicer.clone().ok_or(Error::<T>::InvalidTicketStatus)?;
+			let payment_id = ticket.payment_id.ok_or(Error::<T>::InvalidTicketStatus)?;
+
+			//The refunded part of the escrow goes back to the maintenance reserve. An escrow
+			//cancelled by the servicer was already refunded.
+			let mut amount = Zero::zero();
+			if Payment::Pallet::<T>::payment(payment_id).is_some() {
+				<Payment::Pallet<T> as Payment::PaymentHandler<T>>::settle_payment(
+					payment_id,
+					servicer_share,
+				)?;
+				amount = servicer_share.mul_floor(ticket.quote.unwrap_or_default());
+			}
+
+			ticket.status = TicketStatus::Closed;
+			Tickets::<T>::insert(ticket_id, ticket);
+
+			Self::deposit_event(Event::TicketResolved { ticket_id, servicer, amount });
+
+			Ok(())
+		}
+
+		/// A tenant shares his payment history and reputation score with his application
+		/// for an asset.
+		/// The origin must be the tenant accountId.
+		/// - asset_type: Asset class requested by the tenant.
+		/// - asset_id: ID of the Asset requested by the tenant.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1).ref_time())]
+		pub fn share_reputation(
+			origin: OriginFor<T>,
+			asset_type: Nft::PossibleCollections,
+			asset_id: T::NftItemId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let collection_id: T::NftCollectionId = asset_type.value().into();
+			let asset_account = Share::Pallet::<T>::virtual_acc(collection_id, asset_id)
+				.ok_or(Error::<T>::NotAnAsset)?
+				.virtual_account;
+			let application =
+				Self::application(&caller, &asset_account).ok_or(Error::<T>::NotAnApplicant)?;
+			ensure!(
+				!matches!(
+					application.status,
+					ApplicationStatus::Rejected | ApplicationStatus::Released
+				),
+				Error::<T>::ApplicationClosed
+			);
+
+			let ledger = Self::ledger(&caller);
+			Self::update_application(&caller, &asset_account, |val| {
+				val.record = Some(ledger);
+			});
+
+			Self::deposit_event(Event::ReputationShared {
+				tenant: caller,
+				asset_account,
+				score: ledger.score(),
+			});
+
+			Ok(())
+		}
 	}
 }
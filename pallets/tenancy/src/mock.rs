@@ -97,10 +97,33 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+parameter_types! {
+	pub const LeaseNoticePeriod: BlockNumber = 30;
+	pub const ArrearsGracePeriod: BlockNumber = 30;
+	pub const LateFee: Percent = Percent::from_percent(5);
+	pub const EvictionThreshold: u32 = 3;
+	pub const DepositClaimPeriod: BlockNumber = 20;
+	pub const TicketCompletionPeriod: BlockNumber = 20;
+	pub const MaxLeaseMonths: u32 = 24;
+	pub const MaxApplications: u32 = 10;
+	pub const MaxScheduledPerBlock: u32 = 100;
+	pub const MaxQueuedLeases: u32 = 100;
+}
+
 impl pallet_tenancy::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = ();
+	type LeaseNoticePeriod = LeaseNoticePeriod;
+	type ArrearsGracePeriod = ArrearsGracePeriod;
+	type LateFee = LateFee;
+	type EvictionThreshold = EvictionThreshold;
+	type DepositClaimPeriod = DepositClaimPeriod;
+	type TicketCompletionPeriod = TicketCompletionPeriod;
+	type MaxQueuedLeases = MaxQueuedLeases;
+	type MaxLeaseMonths = MaxLeaseMonths;
+	type MaxApplications = MaxApplications;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 }
 
 pub struct MockDisputeResolver;
@@ -139,15 +162,29 @@ impl pallet_payment::Config for Test {
 	type DisputeResolver = MockDisputeResolver;
 	type IncentivePercentage = IncentivePercentage;
 	type FeeHandler = MockFeeHandler;
+	type OnPaymentSettled = (Finalise, AssetManagement);
 	type MaxRemarkLength = MaxRemarkLength;
 	type CancelBufferBlockLength = CancelBufferBlockLength;
 	type MaxScheduledTaskListLength = MaxScheduledTaskListLength;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const NotariesPerPurchase: u32 = 3;
+	pub const RequiredApprovals: u32 = 1;
+	pub const SignOffDelay: BlockNumber = 100;
+	pub const MaxPendingSignOffs: u32 = 10;
+	pub const MaxSignOffsPerBlock: u32 = 10;
+}
+
 impl pallet_finalizer::Config for Test {
 	type Event = Event;
 	type WeightInfo = ();
+	type NotariesPerPurchase = NotariesPerPurchase;
+	type RequiredApprovals = RequiredApprovals;
+	type SignOffDelay = SignOffDelay;
+	type MaxPendingSignOffs = MaxPendingSignOffs;
+	type MaxSignOffsPerBlock = MaxSignOffsPerBlock;
 }
 
 parameter_types! {
@@ -244,6 +281,7 @@ impl pallet_bidding::Config for Test {
 	type MaximumSharePerInvestor = MaximumSharePerInvestor;
 	type MinimumSharePerInvestor = MinimumSharePerInvestor;
 	type NewAssetScanPeriod = NewAssetScanPeriod;
+	type OnPurchaseFinalising = Finalise;
 }
 
 parameter_types! {
@@ -401,19 +439,28 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributor;
 	type Extra = ();
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const AssetsFees: Balance = 15000;
+	pub const MarketFee: Percent = Percent::from_percent(2);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(50);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxOwners: u32 = 50;
 }
 impl pallet_share_distributor::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = AssetManagement;
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
@@ -444,6 +491,19 @@ parameter_types! {
 	pub const ContractLength: BlockNumber = 365;
 	pub const Lease: u32 = 12;
 	pub const Maintenance:Percent = Percent::from_percent(3);
+	pub const NoticePeriod: BlockNumber = 10;
+	pub const MaintenanceSpendLimit: u64 = 100;
+	pub const MaintenanceSpendPeriod: BlockNumber = 20;
+	pub const RepresentativeFee: pallet_asset_management::ManagementFee<u64> =
+		pallet_asset_management::ManagementFee::Percent(Percent::from_percent(5));
+	pub const RepresentativeTerm: BlockNumber = 1000;
+	pub const ReelectionFallback: pallet_asset_management::ReelectionFallback<BlockNumber> =
+		pallet_asset_management::ReelectionFallback::KeepIncumbent(100);
+	pub const MinimumProposalShare: Percent = Percent::from_percent(10);
+	pub const RevaluationPeriod: BlockNumber = 20;
+	pub const MaxQueuedTasks: u32 = 100;
+	pub const MaxScannedItems: u32 = 100;
+	pub const MaxIncumbentExtensions: u32 = 3;
 }
 
 impl pallet_asset_management::Config for Test {
@@ -462,6 +522,17 @@ impl pallet_asset_management::Config for Test {
 	type RoR = RoR;
 	type Lease = Lease;
 	type Maintenance = Maintenance;
+	type NoticePeriod = NoticePeriod;
+	type MaintenanceSpendLimit = MaintenanceSpendLimit;
+	type MaintenanceSpendPeriod = MaintenanceSpendPeriod;
+	type ManagementFee = RepresentativeFee;
+	type RepresentativeTerm = RepresentativeTerm;
+	type ReelectionFallback = ReelectionFallback;
+	type MinimumProposalShare = MinimumProposalShare;
+	type RevaluationPeriod = RevaluationPeriod;
+	type MaxQueuedTasks = MaxQueuedTasks;
+	type MaxScannedItems = MaxScannedItems;
+	type MaxIncumbentExtensions = MaxIncumbentExtensions;
 	type WeightInfo = ();
 }
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
@@ -1,5 +1,5 @@
 pub use super::*;
-pub use frame_support::{assert_err, assert_ok};
+pub use frame_support::{assert_err, assert_noop, assert_ok};
 use mock::*;
 
 pub type Bvec<Test> = BoundedVec<u8, <Test as pallet_uniques::Config>::StringLimit>;
@@ -28,6 +28,17 @@ pub fn prep_roles() {
 }
 
 pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bvec<Test>) {
+	prep_tenants(price1, price2, metadata0, metadata1, None);
+}
+
+//Purchase of two assets, and election of their Representative. Returns the collection, item
+//and virtual account of the first asset
+pub fn prep_assets(
+	price1: u64,
+	price2: u64,
+	metadata0: Bvec<Test>,
+	metadata1: Bvec<Test>,
+) -> (u32, u32, <Test as frame_system::Config>::AccountId) {
 	prep_roles();
 
 	//Dave and EVE contribute to the fund
@@ -258,7 +269,18 @@ pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bve
 	println!("\n\nAsset status is:{:?}\n\n", house.status);
 	assert_eq!(house1.status, pallet_onboarding::AssetStatus::FINALISING);
 
-	//The Notary will now Finalize the asset
+	//The Notary completes the closing checklists, and will now Finalize the asset
+	for (coll_id, item_id) in [(coll_id0, item_id0), (coll_id1, item_id1)] {
+		for (item, _) in Finalise::checklist_templates(coll_id) {
+			assert_ok!(Finalise::complete_checklist_item(
+				Origin::signed(NOTARY),
+				coll_id,
+				item_id,
+				item,
+				sp_core::H256::repeat_byte(1)
+			));
+		}
+	}
 	assert_ok!(Finalise::validate_transaction_asset(Origin::signed(NOTARY), coll_id0, item_id0,));
 	assert_ok!(Finalise::validate_transaction_asset(Origin::signed(NOTARY), coll_id1, item_id1,));
 	house = OnboardingModule::houses(coll_id0, item_id0).unwrap();
@@ -407,6 +429,19 @@ pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bve
 	let rep_assets = representative.assets_accounts;
 	assert_eq!(rep_assets.len(), 2);
 
+	(coll_id0, item_id0, asset_account)
+}
+
+//Same as `prep_test`, with the rent of the asset optionally shared between the two tenants
+pub fn prep_tenants(
+	price1: u64,
+	price2: u64,
+	metadata0: Bvec<Test>,
+	metadata1: Bvec<Test>,
+	shares: Option<(Percent, Percent)>,
+) {
+	let (coll_id0, item_id0, asset_account) = prep_assets(price1, price2, metadata0, metadata1);
+
 	//Now that we have a Tenant/Representative/Asset. Let the Tenant0 & Tenant1 ask for the same
 	// asset
 	let tenant_bal_init = Balances::free_balance(TENANT0);
@@ -432,6 +467,24 @@ pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bve
 	//Check that the identity was correctly created
 	assert_eq!(pallet_identity::Pallet::<Test>::identity(TENANT0).unwrap().info, ten0());
 
+	//Representative shares the rent of the asset between the tenants
+	if let Some((share0, share1)) = shares {
+		assert_ok!(AssetManagement::set_tenant_share(
+			Origin::signed(REPRESENTATIVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			TENANT0,
+			share0,
+		));
+		assert_ok!(AssetManagement::set_tenant_share(
+			Origin::signed(REPRESENTATIVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			TENANT1,
+			share1,
+		));
+	}
+
 	//Representative gives a positive Judgement and start a referendum for the tenant0
 	assert_ok!(AssetManagement::launch_tenant_session(
 		Origin::signed(REPRESENTATIVE),
@@ -475,7 +528,7 @@ pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bve
 		.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get());
 
 	fast_forward_to(end_block_number);
-	ref_infos = Democracy::referendum_info(0).unwrap();
+	let ref_infos = Democracy::referendum_info(0).unwrap();
 
 	println!(
 		"\n\nTenant Referendum status after vote is: {:?}\n present block is: {:?}\n\n",
@@ -515,6 +568,13 @@ pub fn prep_test(price1: u64, price2: u64, metadata0: Bvec<Test>, metadata1: Bve
 	println!("Payed amount is {:?}", payed_amount);
 	println!("Received amount is {:?}", payed_amount1);
 
+	//Check that the accepted guaranty payments are completed
+	let payment_info0 = AssetManagement::guaranty(TENANT0, asset_account.clone()).unwrap();
+	assert_eq!(payment_info0.state, pallet_payment::PaymentState::PaymentCompleted);
+
+	let payment_info1 = AssetManagement::guaranty(TENANT1, asset_account.clone()).unwrap();
+	assert_eq!(payment_info1.state, pallet_payment::PaymentState::PaymentCompleted);
+
 	//Check that the Tenant is connected to the asset
 	let asset = OnboardingModule::houses(coll_id0, item_id0).unwrap();
 	let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
@@ -535,6 +595,8 @@ fn next_block() {
 	Bidding::on_initialize(System::block_number());
 	AssetManagement::on_initialize(System::block_number());
 	AssetManagement::on_idle(System::block_number(), Weight::MAX);
+	TenancyModule::on_initialize(System::block_number());
+	TenancyModule::on_idle(System::block_number(), Weight::MAX);
 }
 
 fn fast_forward_to(n: u64) {
@@ -668,3 +730,1152 @@ fn test_00() {
 		println!("\n\nrecent events2:\n{:?}", event);
 	})
 }
+
+#[test]
+fn lease_should_be_renewed_or_terminated() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+
+		//The guaranty deposit payments started the leases of the tenants
+		let lease_id0 = TenancyModule::active_lease(TENANT0).unwrap();
+		let lease_id1 = TenancyModule::active_lease(TENANT1).unwrap();
+		let lease0 = TenancyModule::leases(lease_id0).unwrap();
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		assert_eq!(lease0.tenants, vec![TENANT0]);
+		assert_eq!(lease0.status, LeaseStatus::Active);
+		assert_eq!(lease0.rent, tenant0_inf.rent);
+		assert_eq!(lease0.start, tenant0_inf.contract_start);
+		assert_eq!(lease0.end, lease0.start + 365);
+
+		//The representative offers a 6 months renewal to TENANT0, who accepts it
+		assert_err!(
+			TenancyModule::offer_renewal(Origin::signed(TENANT0), lease_id0, 1_000, 6),
+			Error::<Test>::NotTheAssetRepresentative
+		);
+		assert_err!(
+			TenancyModule::accept_renewal(Origin::signed(TENANT0), lease_id0),
+			Error::<Test>::NoRenewalOffer
+		);
+		let max_months = <Test as crate::Config>::MaxLeaseMonths::get();
+		assert_err!(
+			TenancyModule::offer_renewal(
+				Origin::signed(REPRESENTATIVE),
+				lease_id0,
+				1_000,
+				max_months + 1
+			),
+			Error::<Test>::InvalidLeaseLength
+		);
+		assert_ok!(TenancyModule::offer_renewal(
+			Origin::signed(REPRESENTATIVE),
+			lease_id0,
+			1_000,
+			6
+		));
+		assert_err!(
+			TenancyModule::accept_renewal(Origin::signed(TENANT1), lease_id0),
+			Error::<Test>::NotALeaseTenant
+		);
+		assert_ok!(TenancyModule::accept_renewal(Origin::signed(TENANT0), lease_id0));
+		assert_err!(
+			TenancyModule::accept_renewal(Origin::signed(TENANT0), lease_id0),
+			Error::<Test>::RenewalAlreadyAccepted
+		);
+
+		//TENANT1 terminates his lease, which ends after the notice period
+		assert_ok!(TenancyModule::give_notice(Origin::signed(TENANT1), lease_id1));
+		let lease1 = TenancyModule::leases(lease_id1).unwrap();
+		assert_eq!(lease1.status, LeaseStatus::Terminating);
+		assert_eq!(lease1.end, System::block_number() + 30);
+		assert_err!(
+			TenancyModule::give_notice(Origin::signed(TENANT1), lease_id1),
+			Error::<Test>::LeaseNotActive
+		);
+
+		fast_forward_to(lease1.end);
+		assert_eq!(TenancyModule::leases(lease_id1).unwrap().status, LeaseStatus::Ended);
+		assert!(TenancyModule::active_lease(TENANT1).is_none());
+		assert!(pallet_roles::Pallet::<Test>::tenants(TENANT1).unwrap().asset_account.is_none());
+
+		//The lease of TENANT0 is renewed with the new terms
+		fast_forward_to(lease0.end);
+		let lease0 = TenancyModule::leases(lease_id0).unwrap();
+		assert_eq!(lease0.status, LeaseStatus::Active);
+		assert_eq!(lease0.rent, 1_000);
+		assert_eq!(lease0.end, lease0.start + 182);
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		assert_eq!(tenant0_inf.rent, 1_000);
+		assert_eq!(tenant0_inf.remaining_payments, 6);
+		assert_eq!(tenant0_inf.remaining_rent, 6_000);
+		assert!(tenant0_inf.asset_account.is_some());
+		assert!(TenancyModule::renewal_offers(lease_id0).is_none());
+
+		//Without renewal, the lease ends and TENANT0 is unlinked from the asset
+		fast_forward_to(lease0.end);
+		assert_eq!(TenancyModule::leases(lease_id0).unwrap().status, LeaseStatus::Ended);
+		assert!(pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap().asset_account.is_none());
+	})
+}
+
+#[test]
+fn arrears_should_be_charged_deducted_and_lead_to_eviction() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+
+		//TENANT1 leaves, and TENANT0 never pays the rent
+		let lease_id0 = TenancyModule::active_lease(TENANT0).unwrap();
+		let lease_id1 = TenancyModule::active_lease(TENANT1).unwrap();
+		assert_ok!(TenancyModule::give_notice(Origin::signed(TENANT1), lease_id1));
+
+		let lease0 = TenancyModule::leases(lease_id0).unwrap();
+		let asset_account = lease0.asset_account.clone();
+		let rent = lease0.rent;
+		let deposit = lease0.deposit;
+		assert_eq!(deposit, 3 * rent);
+
+		//The lease falls into arrears at the next rent check
+		let check = <Test as pallet_asset_management::Config>::RentCheck::get();
+		let since = (System::block_number() / check + 1) * check;
+		fast_forward_to(since);
+		let record = TenancyModule::arrears(lease_id0, TENANT0).unwrap();
+		assert_eq!(record.since, since);
+		assert_eq!(record.amount, AssetManagement::tenant_debt(&TENANT0, since) as u64);
+		assert!(record.amount > 0);
+
+		//No late fee is charged during the grace period
+		fast_forward_to(since + check);
+		let record = TenancyModule::arrears(lease_id0, TENANT0).unwrap();
+		assert_eq!(record.fees, 0);
+		assert_eq!(record.late_periods, 0);
+		assert_eq!(TenancyModule::leases(lease_id0).unwrap().deposit, deposit);
+
+		//After the grace period, a late fee is charged and the arrears are payed
+		//with the guaranty deposit
+		let late = since + 2 * check;
+		let debt = AssetManagement::tenant_debt(&TENANT0, late) as u64;
+		let fee = Percent::from_percent(5).mul_floor(debt);
+		let rents = ((debt + rent - 1) / rent).min(3);
+		let left = deposit - rents * rent;
+		let recovered = fee.min(left);
+		let reserved = Balances::reserved_balance(&asset_account);
+		fast_forward_to(late);
+
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		assert_eq!(tenant0_inf.remaining_payments, 12 - rents as u8);
+		assert_eq!(TenancyModule::ledger(TENANT0).missed, ((debt + rent - 1) / rent) as u32);
+		assert_eq!(TenancyModule::leases(lease_id0).unwrap().deposit, left - recovered);
+		assert_eq!(
+			AssetManagement::guaranty(TENANT0, asset_account.clone()).unwrap().amount,
+			left - recovered
+		);
+		assert_eq!(reserved - Balances::reserved_balance(&asset_account), rents * rent + recovered);
+		match TenancyModule::arrears(lease_id0, TENANT0) {
+			Some(record) => {
+				assert_eq!(record.amount, 0);
+				assert_eq!(record.fees, fee - recovered);
+				assert_eq!(record.late_periods, 1);
+			},
+			None => assert_eq!(fee, recovered),
+		}
+
+		//Once the guaranty deposit is spent, the arrears start an eviction vote session
+		while TenancyModule::arrears(lease_id0, TENANT0)
+			.and_then(|record| record.eviction)
+			.is_none() &&
+			System::block_number() < lease0.end
+		{
+			next_block();
+		}
+		let record = TenancyModule::arrears(lease_id0, TENANT0).unwrap();
+		let ref_index = record.eviction.unwrap();
+		assert!(record.late_periods >= 3);
+		assert!(TenancyModule::leases(lease_id0).unwrap().deposit < rent);
+		let proposal = AssetManagement::proposals(ref_index).unwrap();
+		assert_eq!(proposal.candidate_account, TENANT0);
+		assert_eq!(proposal.caller_account, REPRESENTATIVE);
+
+		//The owners vote for the eviction of TENANT0
+		let owners = ShareDistributor::virtual_acc(lease0.collection, lease0.item).unwrap().owners;
+		for owner in owners {
+			assert_ok!(AssetManagement::owners_vote(Origin::signed(owner), ref_index, true));
+		}
+		let end_block_number = System::block_number()
+			.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get())
+			.saturating_add(<Test as pallet_asset_management::Config>::Delay::get());
+		fast_forward_to(end_block_number + check + 1);
+
+		//TENANT0 was unlinked from the asset, and the lease ended
+		assert!(pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap().asset_account.is_none());
+		assert_eq!(TenancyModule::leases(lease_id0).unwrap().status, LeaseStatus::Ended);
+		assert!(TenancyModule::active_lease(TENANT0).is_none());
+		assert!(TenancyModule::arrears(lease_id0, TENANT0).is_none());
+	})
+}
+
+#[test]
+fn arrears_checks_should_be_queued_and_executed_within_the_block_weight() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let lease_id0 = TenancyModule::active_lease(TENANT0).unwrap();
+		let lease_id1 = TenancyModule::active_lease(TENANT1).unwrap();
+
+		//The rent check starts a scan of the running leases
+		let check = <Test as pallet_asset_management::Config>::RentCheck::get();
+		let now = (System::block_number() / check + 1) * check;
+		System::set_block_number(now);
+		TenancyModule::on_initialize(now);
+		assert_eq!(TenancyModule::arrears_scan(), Some(vec![]));
+
+		//The weight left in the block only allows one lease to be queued
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let max_weight = db_weight.reads_writes(2, 2).saturating_add(db_weight.reads(1));
+		TenancyModule::finish_block(now, max_weight);
+		assert_eq!(TenancyModule::arrears_queue().len(), 1);
+		assert!(TenancyModule::arrears_scan().is_some());
+		assert!(TenancyModule::arrears(lease_id0, TENANT0).is_none());
+		assert!(TenancyModule::arrears(lease_id1, TENANT1).is_none());
+
+		//The scan resumes and the queued leases are checked in the next block
+		TenancyModule::finish_block(now + 1, Weight::MAX);
+		assert!(TenancyModule::arrears_queue().is_empty());
+		assert!(TenancyModule::arrears_scan().is_none());
+		assert!(TenancyModule::arrears(lease_id0, TENANT0).is_some());
+		assert!(TenancyModule::arrears(lease_id1, TENANT1).is_some());
+	})
+}
+
+#[test]
+fn guaranty_deposit_should_be_returned_after_deductions() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+
+		//Both tenants leave the asset
+		let lease_id0 = TenancyModule::active_lease(TENANT0).unwrap();
+		let lease_id1 = TenancyModule::active_lease(TENANT1).unwrap();
+		assert_ok!(TenancyModule::give_notice(Origin::signed(TENANT0), lease_id0));
+		assert_ok!(TenancyModule::give_notice(Origin::signed(TENANT1), lease_id1));
+		let lease1 = TenancyModule::leases(lease_id1).unwrap();
+		let asset_account = lease1.asset_account.clone();
+		assert!(TenancyModule::deposit_returns(lease_id1, TENANT1).is_none());
+
+		//The deposit return opens at the end of the lease, with the unpaid rent claimed
+		fast_forward_to(lease1.end);
+		let deposit_return = TenancyModule::deposit_returns(lease_id1, TENANT1).unwrap();
+		assert_eq!(deposit_return.status, DepositReturnStatus::Open);
+		assert_eq!(deposit_return.deposit, lease1.deposit);
+		assert_eq!(deposit_return.tenant, TENANT1);
+		assert!(deposit_return
+			.claims
+			.iter()
+			.all(|claim| claim.reason == DeductionReason::UnpaidRent && claim.evidence.is_none()));
+		assert!(TenancyModule::arrears(lease_id1, TENANT1).is_none());
+		let arrears = deposit_return.claimed();
+		let deposit = deposit_return.deposit;
+
+		//The representative claims damages, with evidence
+		let evidence = <Test as frame_system::Config>::Hashing::hash_of(&b"damages".to_vec());
+		assert_err!(
+			TenancyModule::claim_deduction(
+				Origin::signed(TENANT1),
+				lease_id1,
+				TENANT1,
+				DeductionReason::Damages,
+				500,
+				evidence
+			),
+			Error::<Test>::NotTheAssetRepresentative
+		);
+		assert_err!(
+			TenancyModule::claim_deduction(
+				Origin::signed(REPRESENTATIVE),
+				lease_id1,
+				TENANT1,
+				DeductionReason::Damages,
+				deposit + 1,
+				evidence
+			),
+			Error::<Test>::ExcessiveDeduction
+		);
+		assert_ok!(TenancyModule::claim_deduction(
+			Origin::signed(REPRESENTATIVE),
+			lease_id1,
+			TENANT1,
+			DeductionReason::Damages,
+			500,
+			evidence
+		));
+		let deposit_return = TenancyModule::deposit_returns(lease_id1, TENANT1).unwrap();
+		assert_eq!(deposit_return.claims.last().unwrap().evidence, Some(evidence));
+
+		//TENANT1 accepts the deductions, and gets the rest of the deposit back
+		let tenant_balance = Balances::total_balance(&TENANT1);
+		let asset_balance = Balances::total_balance(&asset_account);
+		let refunded = deposit - arrears - 500;
+		assert_ok!(TenancyModule::accept_deductions(Origin::signed(TENANT1), lease_id1));
+		assert_eq!(Balances::total_balance(&TENANT1), tenant_balance + refunded);
+		assert_eq!(Balances::total_balance(&asset_account), asset_balance - refunded);
+		assert!(AssetManagement::guaranty(TENANT1, asset_account.clone()).is_none());
+		assert_eq!(
+			TenancyModule::deposit_returns(lease_id1, TENANT1).unwrap().status,
+			DepositReturnStatus::Settled
+		);
+		assert_err!(
+			TenancyModule::accept_deductions(Origin::signed(TENANT1), lease_id1),
+			Error::<Test>::DepositReturnClosed
+		);
+
+		//TENANT0 disputes the deductions, which are settled by the payment resolver
+		assert_ok!(TenancyModule::claim_deduction(
+			Origin::signed(REPRESENTATIVE),
+			lease_id0,
+			TENANT0,
+			DeductionReason::Cleaning,
+			300,
+			evidence
+		));
+		let payment_id = AssetManagement::guaranty_id(TENANT0, asset_account.clone()).unwrap();
+		let guaranty0 = pallet_payment::Pallet::<Test>::payment(payment_id).unwrap().amount;
+		let claimed0 = TenancyModule::deposit_returns(lease_id0, TENANT0).unwrap().claimed();
+		let tenant_balance = Balances::free_balance(&TENANT0);
+		assert_ok!(TenancyModule::dispute_deductions(Origin::signed(TENANT0), lease_id0));
+
+		//Only the claimed amount is reviewed, the rest of the deposit is refunded
+		let payment = pallet_payment::Pallet::<Test>::payment(payment_id).unwrap();
+		assert_eq!(payment.state, pallet_payment::PaymentState::NeedsReview);
+		assert_eq!(payment.amount, claimed0);
+		assert_eq!(Balances::free_balance(&TENANT0), tenant_balance + guaranty0 - claimed0);
+		let tenant_balance = Balances::free_balance(&TENANT0);
+		assert_ok!(pallet_payment::Pallet::<Test>::resolve_payment(
+			Origin::signed(RESOLVER_ACCOUNT),
+			payment_id,
+			Percent::from_percent(50)
+		));
+		let returned0 = claimed0 - Percent::from_percent(50).mul_floor(claimed0);
+		assert_eq!(Balances::free_balance(&TENANT0), tenant_balance + returned0);
+
+		let deposit_return = TenancyModule::deposit_returns(lease_id0, TENANT0).unwrap();
+		assert_eq!(deposit_return.status, DepositReturnStatus::Disputed);
+		fast_forward_to(deposit_return.settle_at);
+		assert_eq!(
+			TenancyModule::deposit_returns(lease_id0, TENANT0).unwrap().status,
+			DepositReturnStatus::Settled
+		);
+		assert!(AssetManagement::guaranty(TENANT0, asset_account).is_none());
+	})
+}
+
+#[test]
+fn co_tenants_should_share_rent_and_have_separate_arrears() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		let share0 = Percent::from_percent(60);
+		let share1 = Percent::from_percent(40);
+		prep_tenants(450_000, 500_000, metadata0, metadata1, Some((share0, share1)));
+
+		//Both tenants are in the same lease
+		let lease_id = TenancyModule::active_lease(TENANT0).unwrap();
+		assert_eq!(TenancyModule::active_lease(TENANT1), Some(lease_id));
+		let lease = TenancyModule::leases(lease_id).unwrap();
+		let asset_account = lease.asset_account.clone();
+		assert_eq!(lease.tenants, vec![TENANT0, TENANT1]);
+
+		//A renewal offer is accepted once all the co-tenants accepted it
+		assert_ok!(TenancyModule::offer_renewal(
+			Origin::signed(REPRESENTATIVE),
+			lease_id,
+			1_000,
+			6
+		));
+		assert_ok!(TenancyModule::accept_renewal(Origin::signed(TENANT0), lease_id));
+		let offer = TenancyModule::renewal_offers(lease_id).unwrap();
+		assert_eq!(offer.accepted_by, vec![TENANT0]);
+		assert!(!offer.accepted_by_all(&lease.tenants));
+		assert_ok!(TenancyModule::accept_renewal(Origin::signed(TENANT1), lease_id));
+		let offer = TenancyModule::renewal_offers(lease_id).unwrap();
+		assert!(offer.accepted_by_all(&lease.tenants));
+
+		//Each co-tenant pays his share of the rent and of the guaranty
+		let asset_rent = AssetManagement::asset_rent(lease.collection, lease.item).unwrap();
+		let guaranty = AssetManagement::calculate_guaranty(lease.collection, lease.item).unwrap();
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		let tenant1_inf = pallet_roles::Pallet::<Test>::tenants(TENANT1).unwrap();
+		assert_eq!(tenant0_inf.rent, share0.mul_floor(asset_rent) as u64);
+		assert_eq!(tenant1_inf.rent, share1.mul_floor(asset_rent) as u64);
+		assert_eq!(lease.rent, tenant0_inf.rent + tenant1_inf.rent);
+		let deposit0 = AssetManagement::guaranty(TENANT0, asset_account.clone()).unwrap().amount;
+		let deposit1 = AssetManagement::guaranty(TENANT1, asset_account.clone()).unwrap().amount;
+		assert_eq!(deposit0, share0.mul_floor(guaranty) as u64);
+		assert_eq!(deposit1, share1.mul_floor(guaranty) as u64);
+		assert_eq!(lease.deposit, deposit0 + deposit1);
+
+		//The share of a linked tenant cannot be changed
+		assert_err!(
+			AssetManagement::set_tenant_share(
+				Origin::signed(REPRESENTATIVE),
+				NftColl::OFFICESTEST,
+				lease.item,
+				TENANT0,
+				share1,
+			),
+			pallet_asset_management::Error::<Test>::TenantAlreadyLinkedWithAsset
+		);
+
+		//TENANT1 pays his rents, while TENANT0 falls into arrears
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT1)));
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT1)));
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT1)));
+		let check = <Test as pallet_asset_management::Config>::RentCheck::get();
+		let since = (System::block_number() / check + 1) * check;
+		fast_forward_to(since);
+		assert!(TenancyModule::arrears(lease_id, TENANT0).unwrap().amount > 0);
+		assert!(TenancyModule::arrears(lease_id, TENANT1).is_none());
+
+		//Only the guaranty deposit of TENANT0 pays his arrears
+		fast_forward_to(since + 2 * check);
+		let left0 = AssetManagement::guaranty(TENANT0, asset_account.clone()).unwrap().amount;
+		assert!(left0 < deposit0);
+		assert_eq!(
+			AssetManagement::guaranty(TENANT1, asset_account.clone()).unwrap().amount,
+			deposit1
+		);
+		assert_eq!(TenancyModule::leases(lease_id).unwrap().deposit, left0 + deposit1);
+		assert!(TenancyModule::arrears(lease_id, TENANT1).is_none());
+		assert_eq!(
+			pallet_roles::Pallet::<Test>::tenants(TENANT1).unwrap().remaining_payments,
+			tenant1_inf.remaining_payments - 3
+		);
+	})
+}
+
+#[test]
+fn rent_should_be_payed_by_installments() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		let asset_account = tenant0_inf.asset_account.clone().unwrap();
+		let rent = tenant0_inf.rent as u128;
+
+		//The default schedule has one installment per month
+		let schedule = RentSchedule::<Test>::of(&TENANT0).unwrap();
+		assert_eq!(schedule.period, RentPeriod::Monthly);
+		assert_eq!(schedule.installments, 12);
+		assert_eq!(schedule.next_payment(), rent);
+
+		//TENANT0 switches to weekly installments
+		assert_err!(
+			TenancyModule::set_rent_schedule(Origin::signed(TENANT0), RentPeriod::Custom(0)),
+			Error::<Test>::InvalidRentPeriod
+		);
+		assert_ok!(TenancyModule::set_rent_schedule(Origin::signed(TENANT0), RentPeriod::Weekly));
+		let schedule = RentSchedule::<Test>::of(&TENANT0).unwrap();
+		let week = <Test as pallet_asset_management::Config>::ContractLength::get() / 52;
+		assert_eq!(schedule.period_blocks, week);
+		assert_eq!(schedule.installments, 53);
+		let installment = schedule.installment(0);
+		assert_eq!(installment, 12 * rent / 53);
+		assert_eq!(schedule.due(schedule.start + week), 2 * installment);
+
+		//pay_rent pays the first weekly installment
+		let asset_balance = Balances::free_balance(&asset_account);
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT0)));
+		assert_eq!(Balances::free_balance(&asset_account), asset_balance + installment as u64);
+
+		//A partial payment is applied to the oldest unpaid installment
+		assert_ok!(TenancyModule::pay_rent_amount(Origin::signed(TENANT0), 10));
+		let schedule = RentSchedule::<Test>::of(&TENANT0).unwrap();
+		assert_eq!(schedule.payed, installment + 10);
+		assert_eq!(schedule.next_payment(), installment - 10);
+
+		//Several months can be prepayed
+		assert_ok!(TenancyModule::pay_rent_amount(Origin::signed(TENANT0), 3 * rent as u64));
+		let tenant0_inf = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap();
+		let payed = installment + 10 + 3 * rent;
+		assert_eq!(tenant0_inf.remaining_rent as u128, 12 * rent - payed);
+		assert_eq!(tenant0_inf.remaining_payments as u128, (12 * rent - payed + rent - 1) / rent);
+		assert_eq!(AssetManagement::tenant_debt(&TENANT0, System::block_number()), 0);
+
+		//The payments cannot exceed the rent of the lease
+		assert_err!(
+			TenancyModule::pay_rent_amount(Origin::signed(TENANT0), (12 * rent) as u64),
+			Error::<Test>::ExcessiveRentPayment
+		);
+
+		//A failed transfer leaves the rent unpaid
+		Balances::make_free_balance_be(&TENANT1, 1);
+		assert_noop!(
+			TenancyModule::pay_rent(Origin::signed(TENANT1)),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn rent_payment_too_small_for_maintenance_fees_should_be_distributed() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let asset_account =
+			pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap().asset_account.unwrap();
+		let now = System::block_number();
+		AssetManagement::distribute_rent(TENANT0, asset_account.clone(), now);
+		let reserve = AssetManagement::maintenance_reserve(&asset_account);
+
+		//The maintenance fees of a payment smaller than 1/Maintenance are rounded down to 0
+		let maintenance = <Test as pallet_asset_management::Config>::Maintenance::get();
+		let amount = 100 / maintenance.deconstruct() as u64;
+		assert_eq!(maintenance.mul_floor(amount), 0);
+		assert_ok!(TenancyModule::pay_rent_amount(Origin::signed(TENANT0), amount));
+		assert_eq!(ShareDistributor::pending_rent_periods(asset_account.clone()).len(), 1);
+
+		//The payment is distributed without funding the maintenance reserve
+		AssetManagement::distribute_rent(TENANT0, asset_account.clone(), now);
+		assert!(ShareDistributor::pending_rent_periods(asset_account.clone()).is_empty());
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve);
+	})
+}
+
+#[test]
+fn rent_should_buy_ownership_tokens_in_rent_to_own_mode() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let lease_id = TenancyModule::active_lease(TENANT0).unwrap();
+		let lease = TenancyModule::leases(lease_id).unwrap();
+		let asset_account = lease.asset_account.clone();
+		let item_id0 = lease.item;
+
+		//The rent-to-own mode needs the approval of the owners
+		assert_err!(
+			TenancyModule::enable_rent_to_own(Origin::signed(TENANT0)),
+			Error::<Test>::RentToOwnNotApproved
+		);
+		let mut owners = ShareDistributor::tokens_infos(asset_account.clone()).unwrap().owners;
+		owners.sort_by_key(|(_, balance)| *balance);
+		let seller = owners[0].0.clone();
+		let proposer = owners.last().unwrap().0.clone();
+		let share = Percent::from_percent(50);
+		let action = pallet_asset_management::OwnersAction::<Test>::RentToOwn(share);
+		assert_ok!(AssetManagement::launch_owners_proposal(
+			Origin::signed(proposer),
+			NftColl::OFFICESTEST,
+			item_id0,
+			action.clone()
+		));
+		let (ref_index, _) = pallet_asset_management::OwnersProposals::<Test>::iter()
+			.find(|(_, proposal)| *proposal == action)
+			.unwrap();
+		for (owner, _) in owners.iter() {
+			assert_ok!(AssetManagement::owners_vote(
+				Origin::signed(owner.clone()),
+				ref_index,
+				true
+			));
+		}
+		let end_block_number = System::block_number()
+			.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get())
+			.saturating_add(<Test as pallet_asset_management::Config>::Delay::get());
+		fast_forward_to(end_block_number + 1);
+		assert_eq!(AssetManagement::rent_to_own_share(lease.collection, item_id0), Some(share));
+
+		//The smallest owner sells his tokens to the tenants
+		assert_ok!(AssetManagement::consent_rent_to_own(
+			Origin::signed(seller.clone()),
+			NftColl::OFFICESTEST,
+			item_id0,
+			true
+		));
+		assert_ok!(TenancyModule::enable_rent_to_own(Origin::signed(TENANT0)));
+		assert_err!(
+			TenancyModule::enable_rent_to_own(Origin::signed(TENANT0)),
+			Error::<Test>::RentToOwnAlreadyEnabled
+		);
+
+		//Half of the rent buys ownership tokens at the net asset value per token
+		let rent = pallet_roles::Pallet::<Test>::tenants(TENANT0).unwrap().rent;
+		let nav = AssetManagement::nav_per_token(lease.collection, item_id0).unwrap() as u64;
+		let equity = share.mul_floor(rent);
+		let tokens = equity / nav;
+		assert!(tokens > 0);
+		let asset_balance = Balances::free_balance(&asset_account);
+		let seller_balance = Balances::free_balance(&seller);
+		let reserved = Balances::reserved_balance(&TENANT0);
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT0)));
+
+		assert_eq!(Balances::free_balance(&asset_account), asset_balance + rent - equity);
+		assert_eq!(Balances::free_balance(&seller), seller_balance + tokens * nav);
+		let contract = TenancyModule::rent_to_own(TENANT0).unwrap();
+		assert_eq!(contract.tokens, tokens as u128);
+		assert_eq!(contract.credit, equity - tokens * nav);
+		assert_eq!(Balances::reserved_balance(&TENANT0), reserved + contract.credit);
+		assert!(ShareDistributor::virtual_acc(lease.collection, item_id0)
+			.unwrap()
+			.owners
+			.contains(&TENANT0));
+
+		//TENANT0 buys out the remaining tokens of the seller
+		assert_ok!(TenancyModule::buy_out_shares(Origin::signed(TENANT0)));
+		let owners = ShareDistributor::virtual_acc(lease.collection, item_id0).unwrap().owners;
+		assert!(!owners.contains(&seller));
+		let contract = TenancyModule::rent_to_own(TENANT0).unwrap();
+		assert_eq!(contract.tokens, owners_tokens(&asset_account, &TENANT0));
+		assert_eq!(Balances::reserved_balance(&TENANT0), reserved + contract.credit);
+		assert_err!(
+			TenancyModule::buy_out_shares(Origin::signed(TENANT0)),
+			Error::<Test>::NoTokensForSale
+		);
+
+		//Without tokens for sale, the credit is kept and refunded when the tenant leaves
+		let asset_balance = Balances::free_balance(&asset_account);
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT0)));
+		let credit = TenancyModule::rent_to_own(TENANT0).unwrap().credit;
+		assert!(credit >= equity);
+		let tenant_balance = Balances::free_balance(&TENANT0);
+		TenancyModule::close_rent_to_own(&TENANT0);
+		assert!(TenancyModule::rent_to_own(TENANT0).is_none());
+		assert_eq!(Balances::free_balance(&TENANT0), tenant_balance + credit);
+		assert_eq!(Balances::reserved_balance(&TENANT0), reserved);
+		assert_eq!(Balances::free_balance(&asset_account), asset_balance + rent - equity);
+		System::assert_last_event(crate::mock::Event::TenancyModule(
+			crate::Event::RentToOwnClosed { tenant: TENANT0, tokens: contract.tokens, credit },
+		));
+	})
+}
+
+fn owners_tokens(
+	asset_account: &<Test as frame_system::Config>::AccountId,
+	owner: &<Test as frame_system::Config>::AccountId,
+) -> u128 {
+	ShareDistributor::tokens_infos(asset_account.clone())
+		.unwrap()
+		.owners
+		.iter()
+		.find(|(account, _)| account == owner)
+		.map(|(_, balance)| *balance as u128)
+		.unwrap_or_default()
+}
+
+#[test]
+fn applicants_should_be_screened_and_released_when_asset_is_let() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		let (coll_id0, item_id0, asset_account) =
+			prep_assets(450_000, 500_000, metadata0, metadata1);
+
+		//Both tenants apply for the asset, and are queued in order of application
+		assert_ok!(TenancyModule::request_asset(
+			Origin::signed(TENANT0),
+			Box::new(ten0()),
+			NftColl::OFFICESTEST,
+			item_id0,
+		));
+		assert_ok!(TenancyModule::request_asset(
+			Origin::signed(TENANT1),
+			Box::new(ten1()),
+			NftColl::OFFICESTEST,
+			item_id0,
+		));
+		let queue = TenancyModule::applications(&asset_account);
+		assert_eq!(queue.len(), 2);
+		assert_eq!(queue[0].tenant, TENANT0);
+		assert_eq!(queue[1].tenant, TENANT1);
+		assert!(queue.iter().all(|application| application.status == ApplicationStatus::Pending));
+
+		//Only the Representative screens the applicants, and only shortlisted applicants are
+		//selected
+		let notes = <Test as frame_system::Config>::Hashing::hash_of(&b"notes".to_vec());
+		assert_noop!(
+			TenancyModule::screen_applicant(
+				Origin::signed(TENANT0),
+				NftColl::OFFICESTEST,
+				item_id0,
+				TENANT0,
+				notes,
+				Some(0)
+			),
+			Error::<Test>::NotTheAssetRepresentative
+		);
+		assert_noop!(
+			TenancyModule::select_applicant(
+				Origin::signed(REPRESENTATIVE),
+				NftColl::OFFICESTEST,
+				item_id0,
+				TENANT1,
+				Ident::Judgement::Reasonable
+			),
+			Error::<Test>::ApplicantNotShortlisted
+		);
+
+		//The Representative ranks TENANT1 before TENANT0
+		assert_ok!(TenancyModule::screen_applicant(
+			Origin::signed(REPRESENTATIVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			TENANT0,
+			notes,
+			Some(0)
+		));
+		assert_ok!(TenancyModule::screen_applicant(
+			Origin::signed(REPRESENTATIVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			TENANT1,
+			notes,
+			Some(0)
+		));
+		assert_eq!(TenancyModule::shortlist(&asset_account), vec![TENANT1, TENANT0]);
+		let application = TenancyModule::application(&TENANT1, &asset_account).unwrap();
+		assert_eq!(application.status, ApplicationStatus::Shortlisted);
+		assert_eq!(application.notes, Some(notes));
+
+		//The Representative launches the tenant session of TENANT1
+		assert_ok!(TenancyModule::select_applicant(
+			Origin::signed(REPRESENTATIVE),
+			NftColl::OFFICESTEST,
+			item_id0,
+			TENANT1,
+			Ident::Judgement::Reasonable
+		));
+		assert_eq!(TenancyModule::shortlist(&asset_account), vec![TENANT0]);
+		assert_eq!(
+			TenancyModule::application(&TENANT1, &asset_account).unwrap().status,
+			ApplicationStatus::Selected
+		);
+
+		let ref_index = pallet_asset_management::ProposalsLog::<Test>::iter()
+			.find(|(_, proposal)| proposal.caller_account == REPRESENTATIVE)
+			.unwrap()
+			.0;
+		let owners = ShareDistributor::virtual_acc(coll_id0, item_id0).unwrap().owners;
+		for owner in owners {
+			assert_ok!(AssetManagement::owners_vote(Origin::signed(owner), ref_index, true));
+		}
+		let end_block_number = System::block_number()
+			.saturating_add(<Test as pallet_democracy::Config>::VotingPeriod::get());
+		fast_forward_to(end_block_number);
+		fast_forward_to(
+			end_block_number
+				.saturating_add(<Test as pallet_asset_management::Config>::Delay::get()),
+		);
+
+		//Only the requested tenant can pay a guaranty deposit
+		assert_noop!(
+			TenancyModule::pay_guaranty_deposit(
+				Origin::signed(TENANT0),
+				NftColl::OFFICESTEST,
+				item_id0,
+			),
+			Error::<Test>::NotAValidPayment
+		);
+
+		//A deposit that cannot be payed does not link the tenant
+		let free = Balances::free_balance(TENANT1);
+		let reserved = Balances::reserved_balance(TENANT1);
+		assert_ok!(Balances::set_balance(Origin::root(), TENANT1, 1, reserved));
+		assert!(TenancyModule::pay_guaranty_deposit(
+			Origin::signed(TENANT1),
+			NftColl::OFFICESTEST,
+			item_id0,
+		)
+		.is_err());
+		assert_eq!(
+			AssetManagement::guaranty(TENANT1, asset_account.clone()).unwrap().state,
+			pallet_payment::PaymentState::PaymentRequested
+		);
+		assert_eq!(pallet_roles::Pallet::<Test>::tenants(TENANT1).unwrap().asset_account, None);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			crate::mock::Event::TenancyModule(crate::Event::GuarantyDepositPayment { .. })
+		)));
+		assert_ok!(Balances::set_balance(Origin::root(), TENANT1, free, reserved));
+
+		//Once the asset is let, the other applicants are released
+		assert_ok!(TenancyModule::pay_guaranty_deposit(
+			Origin::signed(TENANT1),
+			NftColl::OFFICESTEST,
+			item_id0,
+		));
+		assert_eq!(
+			pallet_roles::Pallet::<Test>::tenants(TENANT1).unwrap().asset_account,
+			Some(asset_account.clone())
+		);
+		assert!(TenancyModule::application(&TENANT1, &asset_account).is_none());
+		assert_eq!(
+			TenancyModule::application(&TENANT0, &asset_account).unwrap().status,
+			ApplicationStatus::Released
+		);
+		assert!(TenancyModule::shortlist(&asset_account).is_empty());
+		assert_eq!(TenancyModule::infos(TENANT0).unwrap().asset_requested, None);
+		System::assert_has_event(crate::mock::Event::TenancyModule(
+			crate::Event::ApplicantReleased { tenant: TENANT0, asset_account },
+		));
+	})
+}
+
+#[test]
+fn application_queue_and_schedules_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		let (_, item_id0, asset_account) = prep_assets(450_000, 500_000, metadata0, metadata1);
+
+		//The application queue of the asset is full
+		let max = <Test as crate::Config>::MaxApplications::get();
+		let queue: Vec<Application<Test>> = (0..max)
+			.map(|i| {
+				Application::<Test>::new(sp_core::crypto::AccountId32::new([100 + i as u8; 32]))
+			})
+			.collect();
+		Applications::<Test>::insert(&asset_account, BoundedVec::try_from(queue).unwrap());
+
+		assert_err!(
+			TenancyModule::request_asset(
+				Origin::signed(TENANT0),
+				Box::new(ten0()),
+				NftColl::OFFICESTEST,
+				item_id0,
+			),
+			Error::<Test>::TooManyApplications
+		);
+		assert!(TenancyModule::application(&TENANT0, &asset_account).is_none());
+
+		//A lease ending at a full block ends at the next block with room left
+		let max = <Test as crate::Config>::MaxScheduledPerBlock::get();
+		LeaseExpiries::<Test>::insert(1_000, BoundedVec::try_from(vec![0; max as usize]).unwrap());
+		assert_eq!(TenancyModule::schedule_lease_end(7, 1_000), 1_001);
+		assert_eq!(TenancyModule::lease_expiries(1_001), vec![7]);
+	})
+}
+
+#[test]
+fn maintenance_ticket_should_be_payed_from_escrow_after_sign_off() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let lease_id = TenancyModule::active_lease(TENANT0).unwrap();
+		let asset_account = TenancyModule::leases(lease_id).unwrap().asset_account;
+
+		//Fund the maintenance reserve of the asset
+		let reserve = AssetManagement::maintenance_reserve(&asset_account);
+		assert_ok!(<Test as pallet::Config>::Currency::reserve(&asset_account, 1_000));
+		pallet_asset_management::MaintenanceReserves::<Test>::insert(
+			&asset_account,
+			reserve + 1_000,
+		);
+
+		//TENANT0 opens a maintenance ticket
+		let description = <Test as frame_system::Config>::Hashing::hash_of(&b"leak".to_vec());
+		assert_ok!(TenancyModule::open_ticket(
+			Origin::signed(TENANT0),
+			MaintenanceCategory::Plumbing,
+			TicketUrgency::High,
+			description
+		));
+		let ticket_id = TenancyModule::ticket_id() - 1;
+		let ticket = TenancyModule::tickets(ticket_id).unwrap();
+		assert_eq!(ticket.status, TicketStatus::Open);
+		assert_eq!(ticket.asset_account, asset_account);
+
+		//The Representative assigns the ticket to a servicer
+		assert_noop!(
+			TenancyModule::assign_ticket(Origin::signed(TENANT0), ticket_id, CHARLIE),
+			Error::<Test>::NotTheAssetRepresentative
+		);
+		assert_noop!(
+			TenancyModule::assign_ticket(Origin::signed(REPRESENTATIVE), ticket_id, TENANT1),
+			Error::<Test>::NotAServicer
+		);
+		assert_ok!(TenancyModule::assign_ticket(
+			Origin::signed(REPRESENTATIVE),
+			ticket_id,
+			CHARLIE
+		));
+
+		//The servicer quotes the work, and the quote is put in escrow once approved
+		assert_noop!(
+			TenancyModule::submit_quote(Origin::signed(BOB), ticket_id, 60),
+			Error::<Test>::NotTheTicketServicer
+		);
+		assert_noop!(
+			TenancyModule::submit_quote(Origin::signed(CHARLIE), ticket_id, 0),
+			Error::<Test>::InvalidQuote
+		);
+
+		//A quote above the maintenance spend limit cannot be approved
+		assert_ok!(TenancyModule::submit_quote(Origin::signed(CHARLIE), ticket_id, 600));
+		assert_noop!(
+			TenancyModule::approve_quote(Origin::signed(REPRESENTATIVE), ticket_id),
+			pallet_asset_management::Error::<Test>::MaintenanceSpendLimitExceeded
+		);
+
+		assert_ok!(TenancyModule::submit_quote(Origin::signed(CHARLIE), ticket_id, 60));
+		assert_ok!(TenancyModule::approve_quote(Origin::signed(REPRESENTATIVE), ticket_id));
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve + 940);
+		let payment_id = TenancyModule::tickets(ticket_id).unwrap().payment_id.unwrap();
+		let payment = pallet_payment::Pallet::<Test>::payment(payment_id).unwrap();
+		assert_eq!(payment.to, CHARLIE);
+		assert_eq!(payment.amount, 60);
+		assert_eq!(payment.incentive_amount, 0);
+		assert_eq!(payment.fee_detail, None);
+		assert_eq!(payment.state, pallet_payment::PaymentState::Created);
+
+		//The tenant rejects the first completion of the work
+		assert_noop!(
+			TenancyModule::sign_off_ticket(Origin::signed(TENANT0), ticket_id, true),
+			Error::<Test>::InvalidTicketStatus
+		);
+		let proof = <Test as frame_system::Config>::Hashing::hash_of(&b"repaired".to_vec());
+		assert_ok!(TenancyModule::submit_completion(Origin::signed(CHARLIE), ticket_id, proof));
+		assert_ok!(TenancyModule::sign_off_ticket(Origin::signed(TENANT0), ticket_id, false));
+		let ticket = TenancyModule::tickets(ticket_id).unwrap();
+		assert_eq!(ticket.status, TicketStatus::Approved);
+		assert_eq!(ticket.proof, None);
+
+		//The tenant signs off the work, and the servicer is payed
+		assert_ok!(TenancyModule::submit_completion(Origin::signed(CHARLIE), ticket_id, proof));
+		assert_noop!(
+			TenancyModule::sign_off_ticket(Origin::signed(TENANT1), ticket_id, true),
+			Error::<Test>::NotTheTicketTenant
+		);
+		let servicer_balance = Balances::free_balance(&CHARLIE);
+		assert_ok!(TenancyModule::sign_off_ticket(Origin::signed(TENANT0), ticket_id, true));
+		assert_eq!(Balances::free_balance(&CHARLIE), servicer_balance + 60);
+		assert!(pallet_payment::Pallet::<Test>::payment(payment_id).is_none());
+		assert_eq!(TenancyModule::tickets(ticket_id).unwrap().status, TicketStatus::Closed);
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve + 940);
+	})
+}
+
+#[test]
+fn overdue_or_cancelled_maintenance_ticket_should_refund_the_reserve() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+		let lease_id = TenancyModule::active_lease(TENANT0).unwrap();
+		let asset_account = TenancyModule::leases(lease_id).unwrap().asset_account;
+
+		//Fund the maintenance reserve of the asset
+		let reserve = AssetManagement::maintenance_reserve(&asset_account) + 1_000;
+		assert_ok!(<Test as pallet::Config>::Currency::reserve(&asset_account, 1_000));
+		pallet_asset_management::MaintenanceReserves::<Test>::insert(&asset_account, reserve);
+
+		let description = <Test as frame_system::Config>::Hashing::hash_of(&b"leak".to_vec());
+		let approved_ticket = |quote: u64| {
+			assert_ok!(TenancyModule::open_ticket(
+				Origin::signed(TENANT0),
+				MaintenanceCategory::Plumbing,
+				TicketUrgency::High,
+				description
+			));
+			let ticket_id = TenancyModule::ticket_id() - 1;
+			assert_ok!(TenancyModule::assign_ticket(
+				Origin::signed(REPRESENTATIVE),
+				ticket_id,
+				CHARLIE
+			));
+			assert_ok!(TenancyModule::submit_quote(Origin::signed(CHARLIE), ticket_id, quote));
+			assert_ok!(TenancyModule::approve_quote(Origin::signed(REPRESENTATIVE), ticket_id));
+			ticket_id
+		};
+
+		//The tenant keeps rejecting the work of the servicer
+		let ticket_id = approved_ticket(40);
+		let proof = <Test as frame_system::Config>::Hashing::hash_of(&b"repaired".to_vec());
+		assert_ok!(TenancyModule::submit_completion(Origin::signed(CHARLIE), ticket_id, proof));
+		assert_ok!(TenancyModule::sign_off_ticket(Origin::signed(TENANT0), ticket_id, false));
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve - 40);
+
+		//The Representative resolves the ticket once its deadline is over
+		assert_noop!(
+			TenancyModule::resolve_ticket(
+				Origin::signed(REPRESENTATIVE),
+				ticket_id,
+				Percent::from_percent(25)
+			),
+			Error::<Test>::TicketNotOverdue
+		);
+		let deadline = TenancyModule::tickets(ticket_id).unwrap().deadline.unwrap();
+		System::set_block_number(deadline);
+		let servicer_balance = Balances::free_balance(&CHARLIE);
+		assert_ok!(TenancyModule::resolve_ticket(
+			Origin::signed(REPRESENTATIVE),
+			ticket_id,
+			Percent::from_percent(25)
+		));
+		assert_eq!(Balances::free_balance(&CHARLIE), servicer_balance + 10);
+		assert_eq!(TenancyModule::tickets(ticket_id).unwrap().status, TicketStatus::Closed);
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve - 10);
+		System::assert_has_event(crate::mock::Event::AssetManagement(
+			pallet_asset_management::Event::MaintenanceReserveRefunded {
+				asset_account: asset_account.clone(),
+				amount: 30,
+				when: deadline,
+			},
+		));
+
+		//A servicer cancelling an escrow gives the whole quote back to the reserve
+		let ticket_id = approved_ticket(50);
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve - 60);
+		let payment_id = TenancyModule::tickets(ticket_id).unwrap().payment_id.unwrap();
+		assert_ok!(pallet_payment::Pallet::<Test>::cancel(Origin::signed(CHARLIE), payment_id));
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve - 10);
+
+		//The Representative closes the ticket of the cancelled escrow once it is overdue
+		let deadline = TenancyModule::tickets(ticket_id).unwrap().deadline.unwrap();
+		System::set_block_number(deadline);
+		assert_ok!(TenancyModule::resolve_ticket(
+			Origin::signed(REPRESENTATIVE),
+			ticket_id,
+			Percent::from_percent(100)
+		));
+		assert_eq!(TenancyModule::tickets(ticket_id).unwrap().status, TicketStatus::Closed);
+		assert_eq!(AssetManagement::maintenance_reserve(&asset_account), reserve - 10);
+	})
+}
+
+#[test]
+fn payment_history_should_build_tenant_reputation() {
+	new_test_ext().execute_with(|| {
+		let metadata0 = b"metadata0".to_vec().try_into().unwrap();
+		let metadata1 = b"metadata1".to_vec().try_into().unwrap();
+
+		//put some funds in FairSquare SlashFees account
+		let fees_account = OnboardingModule::account_id();
+		<Test as pallet::Config>::Currency::make_free_balance_be(&fees_account, 150_000u32.into());
+		prep_test(450_000, 500_000, metadata0, metadata1);
+
+		//A tenant without history has a neutral score
+		assert_eq!(TenancyModule::ledger(TENANT0), TenantLedger::default());
+		assert_eq!(TenancyModule::reputation(&TENANT0), Percent::from_percent(50));
+
+		//TENANT1 pays on time
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT1)));
+		assert_eq!(TenancyModule::ledger(TENANT1).on_time, 1);
+		assert_eq!(TenancyModule::reputation(&TENANT1), Percent::from_percent(80));
+
+		//TENANT0 pays once his lease is in arrears
+		let lease_id0 = TenancyModule::active_lease(TENANT0).unwrap();
+		let check = <Test as pallet_asset_management::Config>::RentCheck::get();
+		let since = (System::block_number() / check + 1) * check;
+		fast_forward_to(since);
+		assert!(TenancyModule::arrears(lease_id0, TENANT0).is_some());
+		assert_ok!(TenancyModule::pay_rent(Origin::signed(TENANT0)));
+		let ledger = TenancyModule::ledger(TENANT0);
+		assert_eq!(ledger.late, 1);
+		assert_eq!(ledger.on_time, 0);
+		assert_eq!(TenancyModule::reputation(&TENANT0), Percent::from_percent(50));
+
+		//TENANT0 shares his history with an application for another asset
+		let coll_id1 = NftColl::APPARTMENTSTEST.value();
+		let item_id1 = pallet_nft::ItemsCount::<Test>::get()[coll_id1 as usize] - 1;
+		let asset_account1 =
+			ShareDistributor::virtual_acc(coll_id1, item_id1).unwrap().virtual_account;
+		assert_noop!(
+			TenancyModule::share_reputation(
+				Origin::signed(TENANT0),
+				NftColl::APPARTMENTSTEST,
+				item_id1
+			),
+			Error::<Test>::NotAnApplicant
+		);
+		assert_ok!(TenancyModule::request_asset(
+			Origin::signed(TENANT0),
+			Box::new(ten0()),
+			NftColl::APPARTMENTSTEST,
+			item_id1,
+		));
+		assert_ok!(TenancyModule::share_reputation(
+			Origin::signed(TENANT0),
+			NftColl::APPARTMENTSTEST,
+			item_id1
+		));
+		let application = TenancyModule::application(&TENANT0, &asset_account1).unwrap();
+		assert_eq!(application.record, Some(ledger));
+		System::assert_has_event(crate::mock::Event::TenancyModule(
+			crate::Event::ReputationShared {
+				tenant: TENANT0,
+				asset_account: asset_account1,
+				score: Percent::from_percent(50),
+			},
+		));
+	})
+}
+
+#[test]
+fn reputation_score_should_reward_leases_and_penalize_evictions() {
+	let ledger = TenantLedger { on_time: 9, late: 2, missed: 1, ..Default::default() };
+	//20 + (9 * 60 + 2 * 30) / 12
+	assert_eq!(ledger.score(), Percent::from_percent(70));
+
+	let ledger = TenantLedger { completed_leases: 6, ..ledger };
+	assert_eq!(ledger.score(), Percent::from_percent(90));
+
+	let ledger = TenantLedger { evictions: 1, deductions: 2, ..ledger };
+	assert_eq!(ledger.score(), Percent::from_percent(60));
+
+	let ledger = TenantLedger { evictions: 5, ..ledger };
+	assert_eq!(ledger.score(), Percent::from_percent(0));
+}
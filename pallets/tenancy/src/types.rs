@@ -5,12 +5,15 @@ pub use frame_support::{
 	inherent::Vec,
 	pallet_prelude::*,
 	sp_runtime::{
-		traits::{AccountIdConversion, Hash, One, Saturating, StaticLookup, Zero},
+		traits::{
+			AccountIdConversion, Hash, One, SaturatedConversion, Saturating, StaticLookup, Zero,
+		},
 		PerThing, Percent,
 	},
-	storage::child,
+	storage::{child, with_transaction, TransactionOutcome},
 	traits::{
-		Currency, ExistenceRequirement, Get, LockableCurrency, ReservableCurrency, WithdrawReasons,
+		BalanceStatus, Currency, ExistenceRequirement, Get, LockableCurrency, ReservableCurrency,
+		WithdrawReasons,
 	},
 	PalletId,
 };
@@ -62,3 +65,570 @@ impl<T: Config> RegisteredTenant<T> {
 		Ok(())
 	}
 }
+
+///Status of a lease contract
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum LeaseStatus {
+	///The lease is running
+	Active,
+	///A notice was given, and the lease ends at the end of the notice period
+	Terminating,
+	///The lease is over, and the tenants are unlinked from the asset
+	Ended,
+}
+
+///Lease contract between the owners of an asset and its tenants
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Lease<T: Config> {
+	///Id of the lease
+	pub id: u32,
+	///Virtual account of the asset
+	pub asset_account: T::AccountId,
+	///Collection of the asset
+	pub collection: T::NftCollectionId,
+	///Item of the asset
+	pub item: T::NftItemId,
+	///Tenants of the asset
+	pub tenants: Vec<T::AccountId>,
+	///Monthly rent, shared by the co-tenants
+	pub rent: Roles::BalanceOf<T>,
+	///Guaranty deposits payed by the tenants
+	pub deposit: Payment::BalanceOf<T>,
+	///Block at which the lease started
+	pub start: BlockNumberOf<T>,
+	///Block at which the lease ends
+	pub end: BlockNumberOf<T>,
+	///Number of blocks between a notice and the end of the lease
+	pub notice_period: BlockNumberOf<T>,
+	///Status of the lease
+	pub status: LeaseStatus,
+}
+
+impl<T: Config> Lease<T> {
+	pub fn new(
+		tenant: T::AccountId,
+		asset_account: T::AccountId,
+		collection: T::NftCollectionId,
+		item: T::NftItemId,
+		deposit: Payment::BalanceOf<T>,
+	) -> Result<u32, DispatchError> {
+		let infos = Roles::Pallet::<T>::tenants(&tenant).ok_or(Error::<T>::NotATenant)?;
+		let months = Assets::Pallet::<T>::tenant_leases(&tenant)
+			.unwrap_or_else(<T as Assets::Config>::Lease::get);
+		let start = infos.contract_start;
+		let id = LeaseId::<T>::get();
+		let end = Pallet::<T>::schedule_lease_end(
+			id,
+			start.saturating_add(Pallet::<T>::lease_blocks(months)),
+		);
+		let lease = Lease::<T> {
+			id,
+			asset_account,
+			collection,
+			item,
+			tenants: vec![tenant.clone()],
+			rent: infos.rent,
+			deposit,
+			start,
+			end,
+			notice_period: T::LeaseNoticePeriod::get(),
+			status: LeaseStatus::Active,
+		};
+		Leases::<T>::insert(id, lease);
+		ActiveLeases::<T>::insert(tenant, id);
+		LeaseId::<T>::mutate(|val| {
+			let val0 = *val;
+			*val = val0 + 1;
+		});
+		Ok(id)
+	}
+}
+
+///Renewal offer of a lease, made by the representative of the asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RenewalOffer<T: Config> {
+	///Representative who made the offer
+	pub offered_by: T::AccountId,
+	///Monthly rent of the renewed lease
+	pub rent: Roles::BalanceOf<T>,
+	///Length of the renewed lease, in number of months
+	pub months: u32,
+	///Tenants of the lease who accepted the offer
+	pub accepted_by: Vec<T::AccountId>,
+}
+
+impl<T: Config> RenewalOffer<T> {
+	///The offer was accepted by all the tenants of the lease
+	pub fn accepted_by_all(&self, tenants: &[T::AccountId]) -> bool {
+		tenants.iter().all(|tenant| self.accepted_by.contains(tenant))
+	}
+}
+
+///Unpaid rent and late fees of a lease
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ArrearsRecord<T: Config> {
+	///Unpaid rent at the last arrears check
+	pub amount: Roles::BalanceOf<T>,
+	///Late fees charged and not yet recovered
+	pub fees: Roles::BalanceOf<T>,
+	///Block at which the lease fell into arrears
+	pub since: BlockNumberOf<T>,
+	///Number of arrears checks with late fees charged
+	pub late_periods: u32,
+	///Unpaid rents already counted as missed in the ledger of the tenant
+	pub missed_rents: u32,
+	///Referendum of the eviction vote session
+	pub eviction: Option<Assets::Dem::ReferendumIndex>,
+}
+
+impl<T: Config> ArrearsRecord<T> {
+	pub fn new(since: BlockNumberOf<T>) -> Self {
+		ArrearsRecord::<T> {
+			amount: Zero::zero(),
+			fees: Zero::zero(),
+			since,
+			late_periods: 0,
+			missed_rents: 0,
+			eviction: None,
+		}
+	}
+
+	pub fn is_cleared(&self) -> bool {
+		self.amount.is_zero() && self.fees.is_zero()
+	}
+}
+
+///Reason of a deduction from a guaranty deposit
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum DeductionReason {
+	UnpaidRent,
+	Damages,
+	Cleaning,
+	Other,
+}
+
+///Deduction claimed on the guaranty deposit of a leaving tenant
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct DeductionClaim<T: Config> {
+	///Reason of the deduction
+	pub reason: DeductionReason,
+	///Amount deducted from the deposit
+	pub amount: Payment::BalanceOf<T>,
+	///Hash of the evidence provided by the representative
+	pub evidence: Option<T::Hash>,
+}
+
+///Status of the return of a guaranty deposit
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum DepositReturnStatus {
+	Open,
+	Disputed,
+	Settled,
+}
+
+///Return of the guaranty deposit of a tenant leaving a lease
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct DepositReturn<T: Config> {
+	///Virtual account of the asset
+	pub asset_account: T::AccountId,
+	///Tenant who payed the deposit
+	pub tenant: T::AccountId,
+	///Remaining guaranty deposit of the tenant at the end of the lease
+	pub deposit: Payment::BalanceOf<T>,
+	///Deductions claimed on the deposit
+	pub claims: Vec<DeductionClaim<T>>,
+	///Last block for the representative to claim deductions
+	pub claims_end: BlockNumberOf<T>,
+	///Block at which undisputed deductions are settled, and the deposit is refunded
+	pub settle_at: BlockNumberOf<T>,
+	///Status of the deposit return
+	pub status: DepositReturnStatus,
+}
+
+impl<T: Config> DepositReturn<T> {
+	pub fn new(lease: &Lease<T>, tenant: T::AccountId, claims: Vec<DeductionClaim<T>>) -> Self {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period = T::DepositClaimPeriod::get();
+		let deposit = Assets::Pallet::<T>::guaranty(&tenant, &lease.asset_account)
+			.map(|payment| payment.amount)
+			.unwrap_or_default();
+		let deposit_return = DepositReturn::<T> {
+			asset_account: lease.asset_account.clone(),
+			tenant: tenant.clone(),
+			deposit,
+			claims,
+			claims_end: now.saturating_add(period),
+			settle_at: now.saturating_add(period).saturating_add(period),
+			status: DepositReturnStatus::Open,
+		};
+		DepositReturns::<T>::insert(lease.id, tenant, deposit_return.clone());
+		deposit_return
+	}
+
+	///Total amount of the claimed deductions
+	pub fn claimed(&self) -> Payment::BalanceOf<T> {
+		self.claims.iter().fold(Zero::zero(), |total: Payment::BalanceOf<T>, claim| {
+			total.saturating_add(claim.amount)
+		})
+	}
+}
+
+///Frequency of the rent installments of a tenant
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum RentPeriod<T: Config> {
+	///One installment per month of the lease
+	Monthly,
+	///One installment per week
+	Weekly,
+	///One installment every given number of blocks
+	Custom(BlockNumberOf<T>),
+}
+
+impl<T: Config> RentPeriod<T> {
+	///Number of blocks between two installments
+	pub fn blocks(&self) -> BlockNumberOf<T> {
+		let year = <T as Assets::Config>::ContractLength::get();
+		let blocks = match self {
+			RentPeriod::Monthly => Pallet::<T>::lease_blocks(1),
+			RentPeriod::Weekly => year / 52u32.into(),
+			RentPeriod::Custom(blocks) => *blocks,
+		};
+		blocks.max(One::one())
+	}
+}
+
+///Installments of the rent of a tenant over his lease. The payments are applied to the
+///oldest installments first.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RentSchedule<T: Config> {
+	///Frequency of the installments
+	pub period: RentPeriod<T>,
+	///Block at which the first installment is due
+	pub start: BlockNumberOf<T>,
+	///Number of blocks between two installments
+	pub period_blocks: BlockNumberOf<T>,
+	///Number of installments
+	pub installments: u32,
+	///Rent of the whole lease
+	pub total: u128,
+	///Rent already payed
+	pub payed: u128,
+}
+
+impl<T: Config> RentSchedule<T> {
+	///Rent schedule of a tenant linked to an asset
+	pub fn of(tenant: &T::AccountId) -> Option<Self> {
+		let infos = Roles::Pallet::<T>::tenants(tenant)?;
+		if infos.asset_account.is_none() {
+			return None
+		}
+		let period = Pallet::<T>::rent_period(tenant).unwrap_or(RentPeriod::Monthly);
+		let months = Assets::Pallet::<T>::tenant_leases(tenant)
+			.unwrap_or_else(<T as Assets::Config>::Lease::get);
+		let rent = Pallet::<T>::roles_bal_to_u128(infos.rent)?;
+		let total = rent.saturating_mul(months as u128);
+
+		//Partial payments only decrease the remaining rent
+		let remaining = Pallet::<T>::roles_bal_to_u128(infos.remaining_rent)?;
+		let payed_rents = months.saturating_sub(infos.remaining_payments as u32) as u128;
+		let payed =
+			total.saturating_sub(remaining).max(payed_rents.saturating_mul(rent)).min(total);
+
+		let lease_blocks = Pallet::<T>::lease_blocks(months).max(One::one());
+		let period_blocks = period.blocks().min(lease_blocks);
+		let length: u128 = lease_blocks.saturated_into();
+		let step: u128 = period_blocks.saturated_into();
+		let installments = length.saturating_add(step - 1).saturating_div(step).max(1) as u32;
+
+		Some(RentSchedule::<T> {
+			period,
+			start: infos.contract_start,
+			period_blocks,
+			installments,
+			total,
+			payed,
+		})
+	}
+
+	///Amount of an installment, the last one including the rounding remainder
+	pub fn installment(&self, index: u32) -> u128 {
+		let base = self.total / self.installments as u128;
+		if index.saturating_add(1) >= self.installments {
+			self.total.saturating_sub(base.saturating_mul(self.installments as u128 - 1))
+		} else {
+			base
+		}
+	}
+
+	///Block at which an installment is due
+	pub fn due_at(&self, index: u32) -> BlockNumberOf<T> {
+		self.start.saturating_add(self.period_blocks.saturating_mul(index.into()))
+	}
+
+	///Unpaid amount of the installments due at the given block
+	pub fn due(&self, now: BlockNumberOf<T>) -> u128 {
+		if now < self.start {
+			return 0
+		}
+		let elapsed: u128 = (now - self.start).saturated_into();
+		let step: u128 = self.period_blocks.saturated_into();
+		let due_installments = elapsed / step.max(1) + 1;
+		let amount = if due_installments >= self.installments as u128 {
+			self.total
+		} else {
+			(self.total / self.installments as u128).saturating_mul(due_installments)
+		};
+		amount.saturating_sub(self.payed)
+	}
+
+	///Unpaid amount of the oldest installment which is not fully payed
+	pub fn next_payment(&self) -> u128 {
+		let base = self.total / self.installments as u128;
+		let payed_installments = if base > 0 { self.payed / base } else { 0 };
+		if payed_installments.saturating_add(1) >= self.installments as u128 {
+			return self.total.saturating_sub(self.payed)
+		}
+		base.saturating_mul(payed_installments + 1).saturating_sub(self.payed)
+	}
+
+	///Rent left to pay over the lease
+	pub fn remaining(&self) -> u128 {
+		self.total.saturating_sub(self.payed)
+	}
+}
+
+///Rent-to-own contract of a tenant. A share of each rent payment is put aside, and used to buy
+///ownership tokens of the asset at its net asset value per token.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RentToOwn<T: Config> {
+	///Virtual account of the asset
+	pub asset_account: T::AccountId,
+	///Collection of the asset
+	pub collection: T::NftCollectionId,
+	///Item of the asset
+	pub item: T::NftItemId,
+	///Rent put aside, and reserved on the tenant account, to buy ownership tokens
+	pub credit: BalanceOf<T>,
+	///Ownership tokens bought by the tenant
+	pub tokens: u128,
+	///Amount payed for the ownership tokens
+	pub payed: BalanceOf<T>,
+	///Block at which the contract started
+	pub start: BlockNumberOf<T>,
+}
+
+///Status of an application for an asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ApplicationStatus {
+	///The application waits for the screening of the Representative
+	Pending,
+	///The Representative shortlisted the applicant
+	Shortlisted,
+	///The Representative turned down the applicant
+	Rejected,
+	///The Representative launched a tenant session for the applicant
+	Selected,
+	///The asset was let to another tenant
+	Released,
+}
+
+///Application of a tenant for an asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Application<T: Config> {
+	///Applicant
+	pub tenant: T::AccountId,
+	///Status of the application
+	pub status: ApplicationStatus,
+	///Hash of the screening notes of the Representative
+	pub notes: Option<T::Hash>,
+	///Block of the application
+	pub applied_at: BlockNumberOf<T>,
+	///Payment history shared by the applicant
+	pub record: Option<TenantLedger>,
+}
+
+impl<T: Config> Application<T> {
+	pub fn new(tenant: T::AccountId) -> Self {
+		let applied_at = <frame_system::Pallet<T>>::block_number();
+		Application::<T> {
+			tenant,
+			status: ApplicationStatus::Pending,
+			notes: None,
+			applied_at,
+			record: None,
+		}
+	}
+}
+
+///Payment history of a tenant
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct TenantLedger {
+	///Rent payments made without arrears
+	pub on_time: u32,
+	///Rent payments made while in arrears
+	pub late: u32,
+	///Rents left unpaid after the grace period
+	pub missed: u32,
+	///Leases ended with the tenant still linked to the asset
+	pub completed_leases: u32,
+	///Leases left after an eviction
+	pub evictions: u32,
+	///Deductions on the guaranty deposits of the tenant
+	pub deductions: u32,
+	///Total amount deducted from the guaranty deposits
+	pub deducted: u128,
+}
+
+impl TenantLedger {
+	///Reputation score of the tenant. A tenant without history scores 50%.
+	///The payments count for 60 points, late payments for half of it, and up to four
+	///completed leases for 5 points each. Each eviction costs 20 points, and each deposit
+	///deduction 5 points.
+	pub fn score(&self) -> Percent {
+		let on_time = self.on_time as u64;
+		let late = self.late as u64;
+		let payments = on_time.saturating_add(late).saturating_add(self.missed as u64);
+		let payments_score = if payments == 0 {
+			30
+		} else {
+			on_time.saturating_mul(60).saturating_add(late.saturating_mul(30)) / payments
+		};
+		let leases_score = (self.completed_leases.min(4) as u64).saturating_mul(5);
+		let penalty = (self.evictions as u64)
+			.saturating_mul(20)
+			.saturating_add((self.deductions as u64).saturating_mul(5));
+		let score = 20u64
+			.saturating_add(payments_score)
+			.saturating_add(leases_score)
+			.saturating_sub(penalty)
+			.min(100);
+		Percent::from_percent(score as u8)
+	}
+}
+
+///Category of a maintenance ticket
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum MaintenanceCategory {
+	Plumbing,
+	Electrical,
+	Heating,
+	Appliance,
+	Structural,
+	Other,
+}
+
+///Urgency of a maintenance ticket
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum TicketUrgency {
+	Low,
+	Normal,
+	High,
+	Emergency,
+}
+
+///Status of a maintenance ticket
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, Copy)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum TicketStatus {
+	///The ticket waits for a servicer
+	Open,
+	///The Representative assigned a servicer to the ticket
+	Assigned,
+	///The servicer submitted a quote
+	Quoted,
+	///The Representative approved the quote, and the payment is in escrow
+	Approved,
+	///The servicer submitted a completion proof
+	Completed,
+	///The tenant signed off the work, or the Representative resolved it, and the servicer
+	///was payed
+	Closed,
+}
+
+///Maintenance ticket opened by a tenant against his asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MaintenanceTicket<T: Config> {
+	///Tenant who opened the ticket
+	pub tenant: T::AccountId,
+	///Virtual account of the asset
+	pub asset_account: T::AccountId,
+	///Collection of the asset
+	pub collection: T::NftCollectionId,
+	///Item of the asset
+	pub item: T::NftItemId,
+	pub category: MaintenanceCategory,
+	pub urgency: TicketUrgency,
+	///Hash of the description of the problem
+	pub description: T::Hash,
+	///Servicer assigned by the Representative
+	pub servicer: Option<T::AccountId>,
+	///Amount quoted by the servicer
+	pub quote: Option<Assets::BalanceOf<T>>,
+	///Escrow payment of the approved quote
+	pub payment_id: Option<Payment::PaymentId>,
+	///Hash of the completion proof of the servicer
+	pub proof: Option<T::Hash>,
+	///Block before which the servicer must complete the work of the approved quote
+	pub deadline: Option<BlockNumberOf<T>>,
+	pub status: TicketStatus,
+	///Block at which the ticket was opened
+	pub opened_at: BlockNumberOf<T>,
+}
+
+impl<T: Config> MaintenanceTicket<T> {
+	pub fn new(
+		tenant: T::AccountId,
+		lease: &Lease<T>,
+		category: MaintenanceCategory,
+		urgency: TicketUrgency,
+		description: T::Hash,
+	) -> u32 {
+		let ticket = MaintenanceTicket::<T> {
+			tenant,
+			asset_account: lease.asset_account.clone(),
+			collection: lease.collection,
+			item: lease.item,
+			category,
+			urgency,
+			description,
+			servicer: None,
+			quote: None,
+			payment_id: None,
+			proof: None,
+			deadline: None,
+			status: TicketStatus::Open,
+			opened_at: <frame_system::Pallet<T>>::block_number(),
+		};
+		let ticket_id = TicketId::<T>::get();
+		Tickets::<T>::insert(ticket_id, ticket);
+		TicketId::<T>::put(ticket_id.saturating_add(1));
+		ticket_id
+	}
+}
@@ -616,12 +616,20 @@ impl pallet_onboarding::Config for Runtime {
 
 parameter_types! {
 	pub const AssetsFees: Balance = 25 * DOLLARS;
+	pub const MarketFee: Percent = Percent::from_percent(1);
+	pub const MaxNavDeviation: Percent = Percent::from_percent(30);
+	pub const MaxOwners: u32 = 100;
 }
 impl pallet_share_distributor::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AssetId = u32;
 	type Fees = AssetsFees;
+	type MarketFee = MarketFee;
+	type Treasury = TreasuryPalletId;
+	type MaxOwners = MaxOwners;
+	type NetAssetValue = AssetManagementModule;
+	type MaxNavDeviation = MaxNavDeviation;
 }
 
 parameter_types! {
@@ -643,7 +651,7 @@ impl pallet_assets::Config for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = ShareDistributor;
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 }
@@ -667,6 +675,7 @@ impl pallet_bidding::Config for Runtime {
 	type MaximumSharePerInvestor = MaximumSharePerInvestor;
 	type MinimumSharePerInvestor = MinimumSharePerInvestor;
 	type NewAssetScanPeriod = NewAssetScanPeriod;
+	type OnPurchaseFinalising = FinalizerModule;
 }
 
 parameter_types! {
@@ -684,6 +693,29 @@ parameter_types! {
 	pub const Lease: u32 = 12;
 	//Maintenance fees taken on monthly rent
 	pub const Maintenance:Percent = Percent::from_percent(3);
+	//Notice period given to tenants when an asset is sold
+	pub const NoticePeriod: BlockNumber = 60*DAYS;
+	//Maximum maintenance reserve spending without an owners vote
+	pub const MaintenanceSpendLimit: Balance = 500*DOLLARS;
+	//Period of the maintenance reserve spending limit
+	pub const MaintenanceSpendPeriod: BlockNumber = 30*DAYS;
+	//Management fee paid to the representative on each rent
+	pub const RepresentativeFee: pallet_asset_management::ManagementFee<Balance> =
+		pallet_asset_management::ManagementFee::Percent(Percent::from_percent(5));
+	//Term of a representative
+	pub const RepresentativeTerm: BlockNumber = 365*DAYS;
+	//A re-election without winner keeps the incumbent for a month
+	pub const ReelectionFallback: pallet_asset_management::ReelectionFallback<BlockNumber> =
+		pallet_asset_management::ReelectionFallback::KeepIncumbent(30*DAYS);
+	//Minimum share of the ownership tokens needed to submit an owners proposal
+	pub const MinimumProposalShare: Percent = Percent::from_percent(10);
+	//Assets can be revalued every six months
+	pub const RevaluationPeriod: BlockNumber = 180*DAYS;
+	//Maximum number of background tasks of each priority waiting for on_idle
+	pub const MaxQueuedTasks: u32 = 1000;
+	//Maximum number of storage items read by the background scan in one block
+	pub const MaxScannedItems: u32 = 100;
+	pub const MaxIncumbentExtensions: u32 = 3;
 }
 impl pallet_asset_management::Config for Runtime {
 	type Event = Event;
@@ -701,19 +733,72 @@ impl pallet_asset_management::Config for Runtime {
 	type RoR = RoR;
 	type Lease = Lease;
 	type Maintenance = Maintenance;
+	type NoticePeriod = NoticePeriod;
+	type MaintenanceSpendLimit = MaintenanceSpendLimit;
+	type MaintenanceSpendPeriod = MaintenanceSpendPeriod;
+	type ManagementFee = RepresentativeFee;
+	type RepresentativeTerm = RepresentativeTerm;
+	type ReelectionFallback = ReelectionFallback;
+	type MinimumProposalShare = MinimumProposalShare;
+	type RevaluationPeriod = RevaluationPeriod;
+	type MaxQueuedTasks = MaxQueuedTasks;
+	type MaxScannedItems = MaxScannedItems;
+	type MaxIncumbentExtensions = MaxIncumbentExtensions;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const NotariesPerPurchase: u32 = 3;
+	pub const RequiredApprovals: u32 = 2;
+	pub const SignOffDelay: BlockNumber = 7 * DAYS;
+	pub const MaxPendingSignOffs: u32 = 100;
+	pub const MaxSignOffsPerBlock: u32 = 50;
+}
+
 impl pallet_finalizer::Config for Runtime {
 	type Event = Event;
 	type WeightInfo = pallet_finalizer::weights::SubstrateWeight<Runtime>;
+	type NotariesPerPurchase = NotariesPerPurchase;
+	type RequiredApprovals = RequiredApprovals;
+	type SignOffDelay = SignOffDelay;
+	type MaxPendingSignOffs = MaxPendingSignOffs;
+	type MaxSignOffsPerBlock = MaxSignOffsPerBlock;
 }
 
 
+parameter_types! {
+	//A lease ends one month after a termination notice
+	pub const LeaseNoticePeriod: BlockNumber = 30 * DAYS;
+	//Late fees are charged on arrears older than a week, and an eviction
+	//vote session is started after 3 late periods
+	pub const ArrearsGracePeriod: BlockNumber = 7 * DAYS;
+	pub const LateFee: Percent = Percent::from_percent(5);
+	pub const EvictionThreshold: u32 = 3;
+	//Deductions on the guaranty deposit are claimed within two weeks after the end
+	//of a lease, and can be disputed during the following two weeks
+	pub const DepositClaimPeriod: BlockNumber = 14 * DAYS;
+	//Servicers have two weeks to complete the work of an approved maintenance ticket
+	pub const TicketCompletionPeriod: BlockNumber = 14 * DAYS;
+	pub const MaxLeaseMonths: u32 = 60;
+	pub const MaxApplications: u32 = 50;
+	pub const MaxScheduledPerBlock: u32 = 100;
+	pub const MaxQueuedLeases: u32 = 1000;
+}
+
 impl pallet_tenancy::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = pallet_tenancy::weights::SubstrateWeight<Runtime>;
+	type LeaseNoticePeriod = LeaseNoticePeriod;
+	type ArrearsGracePeriod = ArrearsGracePeriod;
+	type LateFee = LateFee;
+	type EvictionThreshold = EvictionThreshold;
+	type DepositClaimPeriod = DepositClaimPeriod;
+	type TicketCompletionPeriod = TicketCompletionPeriod;
+	type MaxQueuedLeases = MaxQueuedLeases;
+	type MaxLeaseMonths = MaxLeaseMonths;
+	type MaxApplications = MaxApplications;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 }
 
 pub struct PaymentsDisputeResolver;
@@ -752,6 +837,8 @@ impl pallet_payment::Config for Runtime {
 	type DisputeResolver = PaymentsDisputeResolver;
 	type IncentivePercentage = IncentivePercentage;
 	type FeeHandler = PaymentsFeeHandler;
+	//Refunded maintenance escrows go back to the maintenance reserve of their asset
+	type OnPaymentSettled = (FinalizerModule, AssetManagementModule);
 	type MaxRemarkLength = MaxRemarkLength;
 	type CancelBufferBlockLength = CancelBufferBlockLength;
 	type MaxScheduledTaskListLength = MaxScheduledTaskListLength;